@@ -0,0 +1,59 @@
+//! Cost of [Cache::alloc()] fast path (object from a slab with free objects) and slow path (new slab)
+//!
+//! Run with `cargo bench --bench alloc`
+
+mod common;
+
+use common::{bench, StdMemoryBackend};
+use slab_allocator::{Cache, CacheConfig, ObjectSizeType};
+use std::hint::black_box;
+use std::ptr::null_mut;
+
+const PAGE_SIZE: usize = 4096;
+const ROUNDS: usize = 100_000;
+/// Objects allocated and then freed by one round of the fast path
+const OBJECTS_NUMBER: usize = 64;
+
+fn main() {
+    unsafe {
+        // Slabs are reserved and never freed, so every alloc takes the fast path
+        let mut cache: Cache<[u64; 8], StdMemoryBackend> = Cache::with_config(
+            PAGE_SIZE,
+            PAGE_SIZE,
+            ObjectSizeType::Small,
+            StdMemoryBackend,
+            CacheConfig {
+                reclaim: false,
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        cache.reserve_objects(OBJECTS_NUMBER).unwrap();
+        let slab_allocs = cache.backend_statistics().slab_allocs;
+        let mut object_ptrs = [null_mut(); OBJECTS_NUMBER];
+        bench("alloc fast path + free", ROUNDS, OBJECTS_NUMBER, || {
+            for object_ptr in object_ptrs.iter_mut() {
+                *object_ptr = black_box(cache.alloc());
+            }
+            for object_ptr in object_ptrs {
+                cache.free(object_ptr);
+            }
+        });
+        assert_eq!(cache.backend_statistics().slab_allocs, slab_allocs);
+        cache.drain_free_slabs();
+
+        // The only slab is freed with its last object, so every alloc takes the slow path
+        let mut cache: Cache<[u64; 8], StdMemoryBackend> = Cache::new(
+            PAGE_SIZE,
+            PAGE_SIZE,
+            ObjectSizeType::Small,
+            StdMemoryBackend,
+        )
+        .unwrap();
+        bench("alloc slow path (new slab) + free", ROUNDS, 1, || {
+            let (object_ptr, slab_allocated) = cache.alloc_tracked();
+            assert!(slab_allocated);
+            cache.free(black_box(object_ptr));
+        });
+    }
+}
@@ -0,0 +1,71 @@
+use slab_allocator::{MemoryBackend, OutOfMemory, SlabInfo};
+use std::alloc::{alloc, dealloc, Layout};
+use std::time::Instant;
+
+/// Memory backend on top of std allocator
+///
+/// Supports only [slab_allocator::ObjectSizeType::Small] caches with slab_size == page_size, SlabInfo is inside slab then.
+pub struct StdMemoryBackend;
+
+impl MemoryBackend for StdMemoryBackend {
+    type Error = OutOfMemory;
+
+    unsafe fn alloc_slab(
+        &mut self,
+        slab_size: usize,
+        page_size: usize,
+    ) -> Result<*mut u8, OutOfMemory> {
+        let slab_ptr = alloc(Layout::from_size_align(slab_size, page_size).unwrap());
+        if slab_ptr.is_null() {
+            return Err(OutOfMemory);
+        }
+        Ok(slab_ptr)
+    }
+
+    unsafe fn free_slab(&mut self, slab_ptr: *mut u8, slab_size: usize, page_size: usize) {
+        dealloc(
+            slab_ptr,
+            Layout::from_size_align(slab_size, page_size).unwrap(),
+        );
+    }
+
+    unsafe fn alloc_slab_info(&mut self) -> Result<*mut SlabInfo, OutOfMemory> {
+        unreachable!();
+    }
+
+    unsafe fn free_slab_info(&mut self, _slab_info_ptr: *mut SlabInfo) {
+        unreachable!();
+    }
+
+    unsafe fn save_slab_info_ptr(
+        &mut self,
+        _object_page_addr: usize,
+        _slab_info_ptr: *mut SlabInfo,
+    ) {
+        unreachable!();
+    }
+
+    unsafe fn get_slab_info_ptr(&mut self, _object_page_addr: usize) -> *mut SlabInfo {
+        unreachable!();
+    }
+
+    unsafe fn delete_slab_info_ptr(&mut self, _page_addr: usize) {
+        unreachable!();
+    }
+}
+
+/// Runs f rounds times after a warm up and prints time per operation, f does operations_per_round operations
+pub fn bench(name: &str, rounds: usize, operations_per_round: usize, mut f: impl FnMut()) {
+    for _ in 0..rounds / 10 {
+        f();
+    }
+    let start = Instant::now();
+    for _ in 0..rounds {
+        f();
+    }
+    let elapsed_ns = start.elapsed().as_nanos() as f64;
+    println!(
+        "{name:<48} {:>8.1} ns/op",
+        elapsed_ns / (rounds * operations_per_round) as f64
+    );
+}
@@ -0,0 +1,64 @@
+//! Cost of alloc/free churn with and without [CacheConfig::split_free_lists]
+//!
+//! With [DistributionPolicy::Concentrate] the single free list keeps all partially used slabs sorted, so it may be slower.<br>
+//! [DistributionPolicy::Spread] shows the cost of threshold transitions alone.
+//!
+//! Run with `cargo bench --bench split_free_lists`
+
+mod common;
+
+use common::{bench, StdMemoryBackend};
+use slab_allocator::{Cache, CacheConfig, DistributionPolicy, ObjectSizeType};
+use std::hint::black_box;
+
+const PAGE_SIZE: usize = 4096;
+const ROUNDS: usize = 1_000;
+/// Objects which are live during the churn, they take a few dozen slabs
+const LIVE_OBJECTS_NUMBER: usize = 2048;
+/// Objects freed and allocated again by one round
+const CHURN_OBJECTS_NUMBER: usize = 1024;
+
+fn main() {
+    for (distribution_policy, split_free_lists) in [
+        (DistributionPolicy::Concentrate, true),
+        (DistributionPolicy::Concentrate, false),
+        (DistributionPolicy::Spread, true),
+        (DistributionPolicy::Spread, false),
+    ] {
+        unsafe {
+            let mut cache: Cache<[u64; 8], StdMemoryBackend> = Cache::with_config(
+                PAGE_SIZE,
+                PAGE_SIZE,
+                ObjectSizeType::Small,
+                StdMemoryBackend,
+                CacheConfig {
+                    distribution_policy,
+                    split_free_lists,
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+            let mut object_ptrs: Vec<_> = (0..LIVE_OBJECTS_NUMBER).map(|_| cache.alloc()).collect();
+            // Xorshift, frees objects of random slabs, so slabs move between lists
+            let mut random = 0x2545_F491_4F6C_DD1D_u64;
+            bench(
+                &format!("churn alloc + free, {distribution_policy:?}, split: {split_free_lists}"),
+                ROUNDS,
+                CHURN_OBJECTS_NUMBER,
+                || {
+                    for _ in 0..CHURN_OBJECTS_NUMBER {
+                        random ^= random << 13;
+                        random ^= random >> 7;
+                        random ^= random << 17;
+                        let object_ptr = &mut object_ptrs[random as usize % LIVE_OBJECTS_NUMBER];
+                        cache.free(*object_ptr);
+                        *object_ptr = black_box(cache.alloc());
+                    }
+                },
+            );
+            for object_ptr in object_ptrs {
+                cache.free(object_ptr);
+            }
+        }
+    }
+}
@@ -0,0 +1,160 @@
+use crate::{AssertLevel, OffsetFreeList};
+use core::mem::MaybeUninit;
+use core::ptr::{null, null_mut};
+
+/// Cache of objects stored in an inline buffer of N bytes, without memory backend
+///
+/// Buffer is one slab, when all its objects are allocated [FixedCache::alloc()] returns null.<br>
+/// Free objects are linked by u32 offsets in the buffer like [crate::CacheConfig::offset_free_links] slabs, so size of T must be >= 4 and N must be < u32::MAX.<br>
+/// Double free is checked with the default [AssertLevel].
+///
+/// Allocated pointers point into the cache, so it must not be moved while it has allocated objects.
+#[repr(C)]
+pub struct FixedCache<T, const N: usize> {
+    /// Aligns the buffer for T
+    align: [T; 0],
+    buffer: [MaybeUninit<u8>; N],
+    /// Free objects list with offsets from the buffer start
+    free_objects_list: OffsetFreeList,
+    /// Number of objects at the buffer start which were allocated at least once
+    ///
+    /// The rest of objects are not in free objects list
+    carved_objects_number: usize,
+    allocated_objects_number: usize,
+}
+
+impl<T, const N: usize> FixedCache<T, N> {
+    const OBJECTS_NUMBER: usize = N / size_of::<T>();
+    const CHECK_LAYOUT: () = {
+        assert!(
+            size_of::<T>() >= size_of::<u32>(),
+            "Object size smaller than 4 (offset link)"
+        );
+        assert!(
+            align_of::<T>() >= align_of::<u32>()
+                || size_of::<T>().is_multiple_of(align_of::<u32>()),
+            "Objects can't be aligned for free object links"
+        );
+        assert!(N < u32::MAX as usize, "Buffer is too big for offset links");
+        assert!(
+            N >= size_of::<T>(),
+            "No memory for any object, buffer too small"
+        );
+    };
+
+    /// Creates empty cache
+    ///
+    /// Objects are carved on demand, so creation doesn't touch the buffer
+    pub const fn new() -> Self {
+        #[allow(clippy::let_unit_value)]
+        let () = Self::CHECK_LAYOUT;
+        Self {
+            align: [],
+            buffer: [MaybeUninit::uninit(); N],
+            free_objects_list: OffsetFreeList::new(),
+            carved_objects_number: 0,
+            allocated_objects_number: 0,
+        }
+    }
+
+    /// Allocs object from cache
+    ///
+    /// # Safety
+    /// Returns null pointer if all objects are allocated<br>
+    /// Allocated memory is not initialized
+    #[must_use = "the allocated pointer must be freed with FixedCache::free"]
+    pub unsafe fn alloc(&mut self) -> *mut T {
+        let object_ptr = if !self.free_objects_list.is_empty() {
+            let buffer_ptr = self.buffer_ptr();
+            self.free_objects_list.pop(buffer_ptr)
+        } else if self.carved_objects_number < Self::OBJECTS_NUMBER {
+            let object_ptr = self
+                .buffer_ptr()
+                .add(self.carved_objects_number * size_of::<T>());
+            self.carved_objects_number += 1;
+            object_ptr
+        } else {
+            return null_mut();
+        };
+        self.allocated_objects_number += 1;
+        object_ptr.cast()
+    }
+
+    /// Returns object to cache
+    ///
+    /// # Safety
+    /// Pointer must be a previously allocated pointer from the same cache
+    pub unsafe fn free(&mut self, object_ptr: *mut T) {
+        assert!(!object_ptr.is_null(), "Try to free null ptr");
+        let buffer_addr = self.buffer_ptr() as usize;
+        let object_addr = object_ptr as usize;
+        assert!(
+            object_addr >= buffer_addr
+                && object_addr < buffer_addr + self.carved_objects_number * size_of::<T>(),
+            "It looks like you try free an invalid address or an object of another cache."
+        );
+        let object_offset = object_addr - buffer_addr;
+        assert!(
+            object_offset.is_multiple_of(size_of::<T>()),
+            "Attempting to free a pointer which is not the start of an object."
+        );
+        assert_ne!(
+            self.allocated_objects_number, 0,
+            "Attempting to free an unallocated object! It looks like invalid address or double free."
+        );
+
+        self.check_object_not_free(object_ptr.cast());
+
+        let buffer_ptr = self.buffer_ptr();
+        self.free_objects_list.push(buffer_ptr, object_ptr.cast());
+        self.allocated_objects_number -= 1;
+    }
+
+    /// Checks that object is not in free objects list, like [crate::Cache] does
+    ///
+    /// Catches double free before it loops the free objects list. Walks the list, so it is [AssertLevel::Normal] check.
+    unsafe fn check_object_not_free(&self, object_ptr: *const u8) {
+        if !AssertLevel::default().enables(AssertLevel::Normal) {
+            return;
+        }
+        let buffer_ptr: *const u8 = self.buffer.as_ptr().cast();
+        // List may be already corrupted, don't walk more nodes than it must have
+        let free_objects_number = self.carved_objects_number - self.allocated_objects_number;
+        let mut free_object_ptr = self.free_objects_list.next(buffer_ptr, null());
+        for _ in 0..free_objects_number {
+            if free_object_ptr.is_null() {
+                break;
+            }
+            assert_ne!(
+                free_object_ptr, object_ptr,
+                "Double free! The object is already in free objects list."
+            );
+            free_object_ptr = self.free_objects_list.next(buffer_ptr, free_object_ptr);
+        }
+    }
+
+    /// Gets total number of objects in cache
+    pub const fn capacity(&self) -> usize {
+        Self::OBJECTS_NUMBER
+    }
+
+    /// Gets number of allocated objects
+    pub fn allocated_objects_number(&self) -> usize {
+        self.allocated_objects_number
+    }
+
+    /// Gets number of objects which can be allocated
+    pub fn remaining_capacity(&self) -> usize {
+        Self::OBJECTS_NUMBER - self.allocated_objects_number
+    }
+
+    fn buffer_ptr(&mut self) -> *mut u8 {
+        self.buffer.as_mut_ptr().cast()
+    }
+}
+
+impl<T, const N: usize> Default for FixedCache<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
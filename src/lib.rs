@@ -1,28 +1,96 @@
 #![no_std]
 
+mod fixed_cache;
+mod raw_cache;
+mod slab_box;
 #[cfg(test)]
 mod tests;
 
+pub use fixed_cache::FixedCache;
+pub use raw_cache::RawCache;
+pub use slab_box::SlabBox;
+
 /// Slab allocator for my OS
 ///
 /// Well-synergized with buddy allocator
 use core::cell::UnsafeCell;
 use core::cmp::PartialEq;
-use core::ptr::null_mut;
+use core::fmt::Debug;
+use core::mem::{ManuallyDrop, MaybeUninit};
+use core::ptr::{null, null_mut};
+use core::sync::atomic::{AtomicU64, Ordering};
 use intrusive_collections::{intrusive_adapter, LinkedList, LinkedListLink, UnsafeRef};
-// TODO: It might be worth adding a Drop implementation that will panic if not all objects are freed
+
+/// Increases [CacheStatistics] field, in debug builds panics with the field name on overflow
+macro_rules! statistics_add {
+    ($statistics:expr, $field:ident, $value:expr) => {{
+        let value = $value;
+        $statistics.$field = if cfg!(debug_assertions) {
+            $statistics.$field.checked_add(value).unwrap_or_else(|| {
+                panic!(concat!(
+                    "CacheStatistics::",
+                    stringify!($field),
+                    " overflow, statistics accounting is broken"
+                ))
+            })
+        } else {
+            $statistics.$field.wrapping_add(value)
+        };
+    }};
+}
+
+/// Decreases [CacheStatistics] field, in debug builds panics with the field name on underflow
+///
+/// Accounting bug (e.g. undetected double free) is caught where it happens, not as a huge number in statistics.
+macro_rules! statistics_sub {
+    ($statistics:expr, $field:ident, $value:expr) => {{
+        let value = $value;
+        $statistics.$field = if cfg!(debug_assertions) {
+            $statistics.$field.checked_sub(value).unwrap_or_else(|| {
+                panic!(concat!(
+                    "CacheStatistics::",
+                    stringify!($field),
+                    " underflow, statistics accounting is broken"
+                ))
+            })
+        } else {
+            $statistics.$field.wrapping_sub(value)
+        };
+    }};
+}
+
+/// Asserts the condition if checks of the level are enabled by [CacheConfig::assert_level]
+macro_rules! check {
+    ($cache:expr, $level:expr, $($arg:tt)+) => {
+        if $cache.config.assert_level.enables($level) {
+            assert!($($arg)+);
+        }
+    };
+}
+
+/// Source of unique cache ids
+static NEXT_CACHE_ID: AtomicU64 = AtomicU64::new(0);
 
 /// Slab cache
 ///
 /// Stores objects of the type T
+///
+/// Cache may be moved after objects have been allocated, slabs refer to the cache by its [Cache::cache_id()], not by address.<br>
+/// So the cache is not self-referential and doesn't need to be pinned, [Cache::free()] only checks that the object's slab has the same id.
 pub struct Cache<T, M: MemoryBackend + Sized> {
     object_size: usize,
+    object_align: usize,
+    /// Offset of the first object from the slab start, not 0 only for [SmallInfoPlacement::Head]
+    objects_offset: usize,
     slab_size: usize,
     page_size: usize,
     object_size_type: ObjectSizeType,
     /// Total objects in slab
     objects_per_slab: usize,
     /// List of slabs with free objects (with occupacy less than 75%)
+    ///
+    /// With [DistributionPolicy::Concentrate] sorted by occupancy, the most occupied slab is at the front and object taken from it.<br>
+    /// This concentrates allocations in fewer slabs, so the least occupied slabs are more likely to become empty and be freed.
     free_slabs_list_occupacy_less_75: LinkedList<SlabInfoAdapter>,
     /// List of slabs with free objects (with occupacy more than 75%)
     ///
@@ -30,11 +98,36 @@ pub struct Cache<T, M: MemoryBackend + Sized> {
     free_slabs_list_occupacy_more_75: LinkedList<SlabInfoAdapter>,
     /// Minimum number of allocated objects in more than 75 list
     occupacy_more_75_minimum_allocated_objects_number: usize,
+    /// Percent from which occupacy_more_75_minimum_allocated_objects_number is calculated, see [Cache::set_occupancy_threshold_percent()]
+    occupancy_threshold_percent: u8,
     /// List of full slabs
     full_slabs_list: LinkedList<SlabInfoAdapter>,
+    /// Empty slabs used by alloc only when memory backend fails, see [Cache::set_emergency_reserve()]
+    ///
+    /// They are not counted in statistics
+    emergency_slabs_list: LinkedList<SlabInfoAdapter>,
+    emergency_slabs_number: usize,
+    /// Number of slabs to which the emergency reserve is refilled
+    emergency_reserve_slabs: usize,
     memory_backend: M,
     phantom_data: core::marker::PhantomData<T>,
     statistics: CacheStatistics,
+    backend_statistics: BackendStatistics,
+    /// Unique cache id, stored in SlabInfo to check that the object belongs to the cache
+    ///
+    /// Doesn't change when the cache is moved
+    id: u64,
+    config: CacheConfig,
+    /// Recently got SlabInfo ptrs, used if [CacheConfig::slab_info_lookup_cache] is set
+    slab_info_lookup_cache: SlabInfoLookupCache,
+    /// Number of alloc/free operations, slabs are stamped with it, see [Cache::reap_idle()]
+    operations_number: u64,
+    /// Statistics after the last alloc/free calls, see [Cache::stats_history()]
+    #[cfg(feature = "stats_history")]
+    stats_history: StatsHistory,
+    /// Live objects of allocation sites, see [Cache::live_by_site()]
+    #[cfg(feature = "alloc_sites")]
+    alloc_sites: AllocSites,
 }
 
 impl<T, M: MemoryBackend + Sized> Cache<T, M> {
@@ -53,7 +146,138 @@ impl<T, M: MemoryBackend + Sized> Cache<T, M> {
         object_size_type: ObjectSizeType,
         memory_backend: M,
     ) -> Result<Self, &'static str> {
-        if slab_size % page_size != 0 {
+        Self::with_config(
+            slab_size,
+            page_size,
+            object_size_type,
+            memory_backend,
+            CacheConfig::default(),
+        )
+    }
+
+    /// Same as [Cache::new()], but slab and page sizes are set by orders (log2)
+    ///
+    /// slab_size = 1 << slab_order, page_size = 1 << page_order, so they are always powers of two.<br>
+    /// slab_order must be >= page_order.
+    pub fn new_from_orders(
+        slab_order: u32,
+        page_order: u32,
+        object_size_type: ObjectSizeType,
+        memory_backend: M,
+    ) -> Result<Self, &'static str> {
+        let Some(slab_size) = 1usize.checked_shl(slab_order) else {
+            return Err("Slab order is too big");
+        };
+        let Some(page_size) = 1usize.checked_shl(page_order) else {
+            return Err("Page order is too big");
+        };
+        Self::new(slab_size, page_size, object_size_type, memory_backend)
+    }
+
+    /// Same as [Cache::new()], with optional behaviors set by [CacheConfig]
+    pub fn with_config(
+        slab_size: usize,
+        page_size: usize,
+        object_size_type: ObjectSizeType,
+        memory_backend: M,
+        config: CacheConfig,
+    ) -> Result<Self, &'static str> {
+        Self::with_layout(
+            size_of::<T>(),
+            align_of::<T>(),
+            slab_size,
+            page_size,
+            object_size_type,
+            memory_backend,
+            config,
+        )
+    }
+
+    /// Creates cache of objects with runtime size and alignment, used by [RawCache]
+    pub(crate) fn with_layout(
+        object_size: usize,
+        object_align: usize,
+        slab_size: usize,
+        page_size: usize,
+        object_size_type: ObjectSizeType,
+        memory_backend: M,
+        config: CacheConfig,
+    ) -> Result<Self, &'static str> {
+        // Objects are placed one after another, so the stride is rounded up to keep every object aligned
+        let (object_size, object_align) = match config.object_alignment {
+            Some(object_alignment) => {
+                if !object_alignment.is_power_of_two() {
+                    return Err("Object alignment is not power of two");
+                }
+                (
+                    object_size.next_multiple_of(object_alignment),
+                    object_align.max(object_alignment),
+                )
+            }
+            None => (object_size, object_align),
+        };
+        let objects_per_slab = Self::calculate_objects_per_slab(
+            object_size,
+            object_align,
+            slab_size,
+            page_size,
+            object_size_type,
+            &config,
+        )?;
+
+        // Without split, slab goes to free (>75) list only when it is full, i.e. never
+        let occupancy_threshold_percent = if config.split_free_lists { 75 } else { 100 };
+        Ok(Self {
+            object_size,
+            object_align,
+            objects_offset: Self::calculate_objects_offset(object_align, object_size_type, &config),
+            slab_size,
+            page_size,
+            object_size_type,
+            objects_per_slab,
+            free_slabs_list_occupacy_less_75: LinkedList::new(SlabInfoAdapter::new()),
+            free_slabs_list_occupacy_more_75: LinkedList::new(SlabInfoAdapter::new()),
+            occupacy_more_75_minimum_allocated_objects_number:
+                occupacy_more_75_minimum_allocated_objects_number(
+                    objects_per_slab,
+                    occupancy_threshold_percent,
+                ),
+            occupancy_threshold_percent,
+            full_slabs_list: LinkedList::new(SlabInfoAdapter::new()),
+            emergency_slabs_list: LinkedList::new(SlabInfoAdapter::new()),
+            emergency_slabs_number: 0,
+            emergency_reserve_slabs: 0,
+            memory_backend,
+            phantom_data: core::marker::PhantomData,
+            statistics: CacheStatistics::zeroed(),
+            backend_statistics: BackendStatistics::default(),
+            id: NEXT_CACHE_ID.fetch_add(1, Ordering::Relaxed),
+            config,
+            slab_info_lookup_cache: SlabInfoLookupCache::new(),
+            operations_number: 0,
+            #[cfg(feature = "stats_history")]
+            stats_history: StatsHistory::new(),
+            #[cfg(feature = "alloc_sites")]
+            alloc_sites: AllocSites::new(),
+        })
+    }
+
+    /// Checks cache geometry and calculates number of objects in slab
+    fn calculate_objects_per_slab(
+        object_size: usize,
+        object_align: usize,
+        slab_size: usize,
+        page_size: usize,
+        object_size_type: ObjectSizeType,
+        config: &CacheConfig,
+    ) -> Result<usize, &'static str> {
+        if page_size == 0 {
+            return Err("Page size is zero");
+        }
+        if slab_size < page_size {
+            return Err("Slab size is less than page size");
+        }
+        if !slab_size.is_multiple_of(page_size) {
             return Err(
                 "slab_size is not exactly within the page boundaries. Slab must consist of pages.",
             );
@@ -62,13 +286,33 @@ impl<T, M: MemoryBackend + Sized> Cache<T, M> {
             return Err("Slab size is not power of two");
         }
 
-        if page_size % align_of::<T>() != 0 {
-            return Err("Type can't be aligned");
+        if !object_align.is_power_of_two() {
+            return Err("Object alignment is not power of two");
+        }
+        // Memory backend gives only page aligned slabs
+        if object_align > page_size {
+            return Err("Object alignment is bigger than page size, slabs are only page aligned");
+        }
+        if !page_size.is_multiple_of(object_align) {
+            return Err("Page size is not multiple of object alignment");
+        }
+        if !object_size.is_multiple_of(object_align) {
+            return Err("Object size is not multiple of object alignment");
         }
 
-        let object_size = size_of::<T>();
-        if object_size < size_of::<FreeObject>() {
-            return Err("Object size smaller than 8/16 (two pointers)");
+        let link_align = if config.offset_free_links {
+            if object_size < Self::min_object_size_with_offset_links() {
+                return Err("Object size smaller than 4 (offset link)");
+            }
+            if slab_size > u32::MAX as usize {
+                return Err("Slab size is too big for offset links");
+            }
+            align_of::<u32>()
+        } else {
+            if object_size < Self::min_object_size() {
+                return Err("Object size smaller than 8/16 (two pointers)");
+            };
+            align_of::<FreeObject>()
         };
         if let ObjectSizeType::Small = object_size_type {
             if slab_size < size_of::<SlabInfo>() + object_size {
@@ -76,381 +320,2951 @@ impl<T, M: MemoryBackend + Sized> Cache<T, M> {
             }
         }
         assert_eq!(size_of::<FreeObject>(), size_of::<*const u8>() * 2);
+        // Free objects links are written into objects, objects are placed one after another from the slab start (page aligned).
+        // So low alignment types are fine, if their size keeps every object aligned for the links.
+        if object_align < link_align && !object_size.is_multiple_of(link_align) {
+            return Err("Objects can't be aligned for free object links, object size must be multiple of pointer size");
+        }
 
         // Calculate number of objects in slab
-        let objects_per_slab = match object_size_type {
-            ObjectSizeType::Small => {
+        let objects_per_slab = match (object_size_type, config.small_info_placement) {
+            (ObjectSizeType::Small, SmallInfoPlacement::Tail) => {
                 let fake_slab_addr = 0usize;
                 let fake_slab_info_addr = calculate_slab_info_addr_in_small_object_cache(
                     fake_slab_addr as *mut u8,
                     slab_size,
+                    SmallInfoPlacement::Tail,
                 );
                 assert!(fake_slab_info_addr > fake_slab_addr);
                 assert!(fake_slab_info_addr <= fake_slab_addr + slab_size - size_of::<SlabInfo>());
                 (fake_slab_info_addr - fake_slab_addr) / object_size
             }
-            ObjectSizeType::Large => slab_size / object_size,
+            (ObjectSizeType::Small, SmallInfoPlacement::Head) => {
+                let objects_offset =
+                    Self::calculate_objects_offset(object_align, object_size_type, config);
+                slab_size.saturating_sub(objects_offset) / object_size
+            }
+            (ObjectSizeType::Large, _) => slab_size / object_size,
         };
         if objects_per_slab == 0 {
             return Err("No memory for any object, slab size too small");
         }
-
-        Ok(Self {
-            object_size,
-            slab_size,
-            page_size,
-            object_size_type,
-            objects_per_slab,
-            free_slabs_list_occupacy_less_75: LinkedList::new(SlabInfoAdapter::new()),
-            free_slabs_list_occupacy_more_75: LinkedList::new(SlabInfoAdapter::new()),
-            occupacy_more_75_minimum_allocated_objects_number: (75 * objects_per_slab) / 100,
-            full_slabs_list: LinkedList::new(SlabInfoAdapter::new()),
-            memory_backend,
-            phantom_data: core::marker::PhantomData,
-            statistics: CacheStatistics {
-                free_slabs_number: 0,
-                full_slabs_number: 0,
-                free_objects_number: 0,
-                allocated_objects_number: 0,
-            },
-        })
+        if object_size_type == ObjectSizeType::Small
+            && config.small_info_placement == SmallInfoPlacement::Tail
+        {
+            // The last object must not overlap SlabInfo
+            let fake_slab_addr = 0usize;
+            let fake_slab_info_addr = calculate_slab_info_addr_in_small_object_cache(
+                fake_slab_addr as *mut u8,
+                slab_size,
+                SmallInfoPlacement::Tail,
+            );
+            let last_object_end_addr = fake_slab_addr + objects_per_slab * object_size;
+            if last_object_end_addr > fake_slab_info_addr {
+                return Err("Slab size is too small");
+            }
+        }
+        if let Some(max_overhead_percent) = config.max_overhead_percent {
+            let overhead_bytes = slab_size - objects_per_slab * object_size;
+            if overhead_bytes * 100 > slab_size * max_overhead_percent as usize {
+                return Err("Slab overhead exceeds max_overhead_percent");
+            }
+        }
+        Ok(objects_per_slab)
     }
 
-    /// Allocs object from cache
+    /// Calculates offset of the first object from the slab start
     ///
-    /// # Safety
-    /// May return null pointer<br>
-    /// Allocated memory is not initialized
-    pub unsafe fn alloc(&mut self) -> *mut T {
-        if self.free_slabs_list_occupacy_more_75.is_empty()
-            && self.free_slabs_list_occupacy_less_75.is_empty()
+    /// Objects follow SlabInfo if it is placed at the slab start, see [SmallInfoPlacement::Head].
+    fn calculate_objects_offset(
+        object_align: usize,
+        object_size_type: ObjectSizeType,
+        config: &CacheConfig,
+    ) -> usize {
+        if object_size_type == ObjectSizeType::Small
+            && config.small_info_placement == SmallInfoPlacement::Head
         {
-            // Need to allocate new slab
-            let slab_ptr = self
-                .memory_backend
-                .alloc_slab(self.slab_size, self.page_size);
-            if slab_ptr.is_null() {
-                return null_mut();
-            }
+            size_of::<SlabInfo>().next_multiple_of(object_align)
+        } else {
+            0
+        }
+    }
 
-            // Calculate/allocate SlabInfo ptr
-            let slab_info_ptr = match self.object_size_type {
-                ObjectSizeType::Small => {
-                    // SlabInfo stored inside slab, at end
-                    let slab_info_addr =
-                        calculate_slab_info_addr_in_small_object_cache(slab_ptr, self.slab_size);
-                    assert!(slab_info_addr > slab_ptr as usize);
-                    assert!(
-                        slab_info_addr
-                            <= slab_ptr as usize + self.slab_size - size_of::<SlabInfo>()
-                    );
+    /// Calculates address of the object with index in slab
+    fn object_ptr(&self, slab_ptr: *mut u8, index: usize) -> *mut u8 {
+        slab_ptr.wrapping_add(self.objects_offset + index * self.object_size)
+    }
 
-                    slab_info_addr as *mut SlabInfo
-                }
-                ObjectSizeType::Large => {
-                    // Allocate memory using memory backend
-                    let slab_info_ptr = self.memory_backend.alloc_slab_info();
-                    if slab_info_ptr.is_null() {
-                        // Failed to allocate SlabInfo
-                        // Free slab
-                        self.memory_backend
-                            .free_slab(slab_ptr, self.slab_size, self.page_size);
-                        return null_mut();
-                    }
-                    assert!(
-                        slab_info_ptr.is_aligned(),
-                        "Memory backend allocates not aligned SlabInfo"
-                    );
-                    slab_info_ptr
-                }
+    /// Calculates the smallest slab size with at least min_objects objects in slab
+    ///
+    /// Slab size is the smallest power of two multiple of page_size, for which [Cache::new()] with default [CacheConfig] would give objects_per_slab >= min_objects.<br>
+    /// Returns error if there is no such slab size, e.g. object alignment is invalid.
+    pub fn slab_size_for_objects(
+        object_size: usize,
+        object_align: usize,
+        page_size: usize,
+        object_size_type: ObjectSizeType,
+        min_objects: usize,
+    ) -> Result<usize, &'static str> {
+        if page_size == 0 {
+            return Err("Page size is zero");
+        }
+        let config = CacheConfig::default();
+        let mut slab_size = page_size;
+        loop {
+            // Errors for too small slab disappear when slab grows, others are returned when slab size overflows
+            let error = match Self::calculate_objects_per_slab(
+                object_size,
+                object_align,
+                slab_size,
+                page_size,
+                object_size_type,
+                &config,
+            ) {
+                Ok(objects_per_slab) if objects_per_slab >= min_objects => return Ok(slab_size),
+                Ok(_) => "Slab size for min_objects is too big",
+                Err(error) => error,
             };
-            assert!(!slab_info_ptr.is_null());
-            assert!(slab_info_ptr.is_aligned());
+            slab_size = slab_size.checked_mul(2).ok_or(error)?;
+        }
+    }
 
-            // Fill SlabInfo
-            slab_info_ptr.write(SlabInfo {
-                slab_link: LinkedListLink::new(),
-                data: UnsafeCell::new(SlabInfoData {
-                    free_objects_list: LinkedList::new(FreeObjectAdapter::new()),
-                    cache_ptr: self as *mut Self as *mut _,
-                    free_objects_number: self.objects_per_slab,
-                    slab_ptr,
-                }),
-            });
+    /// Creates cache with another slab size, moving memory backend into it
+    ///
+    /// The cache must not have allocated objects, empty slabs are freed.<br>
+    /// On error returns the cache unchanged.
+    ///
+    /// # Safety
+    /// Calls memory backend to free slabs
+    #[allow(clippy::result_large_err)]
+    pub unsafe fn migrate_to_slab_size(
+        mut self,
+        new_slab_size: usize,
+    ) -> Result<Self, (Self, &'static str)> {
+        if self.statistics.allocated_objects_number != 0 {
+            return Err((self, "Cache has allocated objects"));
+        }
+        if let Err(error) = Self::calculate_objects_per_slab(
+            self.object_size,
+            self.object_align,
+            new_slab_size,
+            self.page_size,
+            self.object_size_type,
+            &self.config,
+        ) {
+            return Err((self, error));
+        }
 
-            // Make SlabInfo ref
-            let slab_info_ref = UnsafeRef::from_raw(slab_info_ptr);
-            // Add SlabInfo to free list
-            self.free_slabs_list_occupacy_less_75
-                .push_back(slab_info_ref);
-            self.statistics.free_slabs_number += 1;
-            self.statistics.free_objects_number += self.objects_per_slab;
-
-            // Fill FreeObjects list
-            for free_object_index in 0..self.objects_per_slab {
-                // Free object stored in slab
-                let free_object_addr = slab_ptr as usize + (free_object_index * self.object_size);
-                assert_eq!(
-                    free_object_addr % align_of::<FreeObject>(),
-                    0,
-                    "FreeObject addr not aligned!"
-                );
-                let free_object_ptr = free_object_addr as *mut FreeObject;
-                free_object_ptr.write(FreeObject {
-                    free_object_link: LinkedListLink::new(),
-                });
-                let free_object_ref = UnsafeRef::from_raw(free_object_ptr);
+        self.drain_free_slabs();
+        let emergency_reserve_slabs = self.emergency_reserve_slabs;
+        self.set_emergency_reserve(0).unwrap();
 
-                // Add free object to free objects list
-                (*self
-                    .free_slabs_list_occupacy_less_75
-                    .front()
-                    .get()
-                    .unwrap()
-                    .data
-                    .get())
-                .free_objects_list
-                .push_back(free_object_ref);
-            }
+        let object_size = self.object_size;
+        let object_align = self.object_align;
+        let page_size = self.page_size;
+        let object_size_type = self.object_size_type;
+        let config = self.config;
+        let occupancy_threshold_percent = self.occupancy_threshold_percent;
+        let memory_backend = self.take_memory_backend();
+        let mut cache = Self::with_layout(
+            object_size,
+            object_align,
+            new_slab_size,
+            page_size,
+            object_size_type,
+            memory_backend,
+            config,
+        )
+        .unwrap();
+        if occupancy_threshold_percent != cache.occupancy_threshold_percent {
+            cache
+                .set_occupancy_threshold_percent(occupancy_threshold_percent)
+                .unwrap();
         }
-        // Allocate object
+        // If memory backend fails now, the reserve is refilled later
+        let _ = cache.set_emergency_reserve(emergency_reserve_slabs);
+        Ok(cache)
+    }
 
-        // Get free slab info
-        let free_slab_info = {
-            // First we try to choose the slab with the highest occupancy.
-            // This should allow to concentrate the allocations inside the most occupied slabs,
-            // while slabs with a small allocated number of objects are more likely to be freed.
-            if let Some(slab_info) = self.free_slabs_list_occupacy_more_75.front().get() {
-                slab_info
-            } else {
-                self.free_slabs_list_occupacy_less_75.front().get().unwrap()
-            }
-        };
-        // Get slab data
-        let free_slab_info_data = &mut *free_slab_info.data.get();
+    /// Consumes the cache and returns its memory backend
+    ///
+    /// Cache must have no slabs: objects must be freed and free slabs drained by [Cache::drain_free_slabs()],
+    /// the emergency reserve must be released by set_emergency_reserve(0).<br>
+    /// Otherwise returns the cache unchanged.
+    #[allow(clippy::result_large_err)]
+    pub fn into_backend(self) -> Result<M, Self> {
+        if self.statistics.allocated_objects_number != 0
+            || !self.full_slabs_list.is_empty()
+            || !self.free_slabs_list_occupacy_less_75.is_empty()
+            || !self.free_slabs_list_occupacy_more_75.is_empty()
+            || !self.emergency_slabs_list.is_empty()
+        {
+            return Err(self);
+        }
+        Ok(self.take_memory_backend())
+    }
 
-        // Get object from FreeObject list
-        let free_object_ref = free_slab_info_data.free_objects_list.pop_back().unwrap();
-        free_slab_info_data.free_objects_number -= 1;
-        self.statistics.free_objects_number -= 1;
-        let free_object_ptr = UnsafeRef::<FreeObject>::into_raw(free_object_ref);
+    /// Moves memory backend out of the cache without dropping the cache
+    ///
+    /// Cache must have no slabs, they would be leaked.
+    fn take_memory_backend(self) -> M {
+        check!(self, AssertLevel::Normal, self.full_slabs_list.is_empty());
+        check!(
+            self,
+            AssertLevel::Normal,
+            self.free_slabs_list_occupacy_less_75.is_empty()
+        );
+        check!(
+            self,
+            AssertLevel::Normal,
+            self.free_slabs_list_occupacy_more_75.is_empty()
+        );
+        check!(
+            self,
+            AssertLevel::Normal,
+            self.emergency_slabs_list.is_empty()
+        );
+        let cache = ManuallyDrop::new(self);
+        // The cache is not used after that and its other fields own nothing
+        unsafe { core::ptr::read(&cache.memory_backend) }
+    }
 
-        // Save SlabInfo ptr
-        if !(self.object_size_type == ObjectSizeType::Small && self.slab_size == self.page_size) {
-            let free_slab_info_ptr = free_slab_info as *const _ as *mut _;
-            let free_object_page_addr = align_down(free_object_ptr as usize, self.page_size);
-            debug_assert_eq!(free_object_page_addr % self.page_size, 0);
+    /// Sets occupancy percent which splits slabs with free objects between free (<75) and free (>75) lists, 75 by default
+    ///
+    /// Slabs with at least this percent of allocated objects are in free (>75) list, objects are allocated from them first.<br>
+    /// Existing slabs are moved between the lists to match the new threshold.<br>
+    /// Returns error if free lists are not split, see [CacheConfig::split_free_lists].
+    pub fn set_occupancy_threshold_percent(&mut self, percent: u8) -> Result<(), &'static str> {
+        if !self.config.split_free_lists {
+            return Err("Free lists are not split");
+        }
+        if percent > 100 {
+            return Err("Occupancy threshold percent is more than 100");
+        }
+        if percent == 0 {
+            return Err("Occupancy threshold is less than one object");
+        }
+        let minimum_allocated_objects_number =
+            occupacy_more_75_minimum_allocated_objects_number(self.objects_per_slab, percent);
+        self.occupancy_threshold_percent = percent;
+        self.occupacy_more_75_minimum_allocated_objects_number = minimum_allocated_objects_number;
+        unsafe {
+            self.rebalance_free_slabs_lists();
+        }
+        Ok(())
+    }
 
-            // In this case we can avoid unnecessary saving for this page, if it already has allocated objects, the slab into ptr is already saved.
-            let mut dont_save = false;
-            if self.objects_per_slab >= 2 {
-                dont_save = self.slab_size == self.page_size
-                    && free_slab_info_data.free_objects_number <= self.objects_per_slab - 2;
-            }
+    /// Moves slabs between free (<75) and free (>75) lists according to the current threshold
+    unsafe fn rebalance_free_slabs_lists(&mut self) {
+        let minimum_allocated_objects_number =
+            self.occupacy_more_75_minimum_allocated_objects_number;
+        let is_more_75 = |slab_info: &SlabInfo| {
+            self.objects_per_slab - (*slab_info.data.get()).free_objects_number
+                >= minimum_allocated_objects_number
+        };
 
-            if !dont_save {
-                self.memory_backend
-                    .save_slab_info_ptr(free_object_page_addr, free_slab_info_ptr);
+        // Collect slabs which are in wrong list
+        let mut to_more_75_list = LinkedList::new(SlabInfoAdapter::new());
+        let mut cursor = self.free_slabs_list_occupacy_less_75.front_mut();
+        while let Some(slab_info) = cursor.get() {
+            if is_more_75(slab_info) {
+                to_more_75_list.push_back(cursor.remove().unwrap());
+            } else {
+                cursor.move_next();
+            }
+        }
+        let mut to_less_75_list = LinkedList::new(SlabInfoAdapter::new());
+        let mut cursor = self.free_slabs_list_occupacy_more_75.front_mut();
+        while let Some(slab_info) = cursor.get() {
+            if !is_more_75(slab_info) {
+                to_less_75_list.push_back(cursor.remove().unwrap());
+            } else {
+                cursor.move_next();
             }
         }
 
-        // Slab occupacy become more than 75? (free (<75) -> free (>75))
-        let allocated_objects_number =
-            self.objects_per_slab - (*free_slab_info.data.get()).free_objects_number;
-        let previously_was_in_less_75_list =
-            allocated_objects_number - 1 < self.occupacy_more_75_minimum_allocated_objects_number;
-        let now_in_more_75_list =
-            allocated_objects_number >= self.occupacy_more_75_minimum_allocated_objects_number;
-        if previously_was_in_less_75_list && now_in_more_75_list {
-            // Move slab info from free (<75) to free (>75)
-            let mut slab_info_free_less_75_list_cursor = self
-                .free_slabs_list_occupacy_less_75
-                .cursor_mut_from_ptr(free_slab_info as *const SlabInfo);
-            let free_slab_info = slab_info_free_less_75_list_cursor.remove().unwrap();
-
-            // Add to free (>75)
+        while let Some(slab_info_ref) = to_more_75_list.pop_front() {
+            let slab_ptr = (*slab_info_ref.data.get()).slab_ptr;
             self.free_slabs_list_occupacy_more_75
-                .push_front(free_slab_info);
+                .push_front(slab_info_ref);
+            self.notify_transition(slab_ptr, SlabListKind::FreeLess75, SlabListKind::FreeMore75);
         }
-
-        // Slab become empty? (free (>75) -> full)
-        if free_slab_info_data.free_objects_list.is_empty() {
-            // Slab is empty now
-            // Remove from free list
-            let free_slab_info = self.free_slabs_list_occupacy_more_75.pop_front().unwrap();
-            self.statistics.free_slabs_number -= 1;
-            // Add to full list
-            self.full_slabs_list.push_back(free_slab_info);
-            self.statistics.full_slabs_number += 1;
+        while let Some(slab_info_ref) = to_less_75_list.pop_front() {
+            let slab_ptr = (*slab_info_ref.data.get()).slab_ptr;
+            let slab_info_ptr = UnsafeRef::into_raw(slab_info_ref.clone());
+            self.free_slabs_list_occupacy_less_75
+                .push_front(slab_info_ref);
+            if self.config.distribution_policy == DistributionPolicy::Concentrate {
+                self.sort_slab_in_free_less_75_list(slab_info_ptr);
+            }
+            self.notify_transition(slab_ptr, SlabListKind::FreeMore75, SlabListKind::FreeLess75);
         }
-
-        self.statistics.allocated_objects_number += 1;
-        free_object_ptr.cast()
     }
 
-    /// Returns object to cache
+    /// Allocs object from cache
+    ///
+    /// New slab gives its objects in address order, each next object is right before the previous one (see [Cache::predicted_slot_addr()]).<br>
+    /// So objects allocated one after another from a new slab fill one cache line before moving to the next one,
+    /// with any [CacheConfig::lazy_carve] and [CacheConfig::offset_free_links], except [DistributionPolicy::Spread] which switches slabs.
     ///
     /// # Safety
-    /// Pointer must be a previously allocated pointer from the same cache
-    pub unsafe fn free(&mut self, object_ptr: *mut T) {
-        assert!(!object_ptr.is_null(), "Try to free null ptr");
-        assert!(
-            object_ptr.is_aligned(),
-            "Try to free null ptr (aligned pointer has been allocated)"
-        );
+    /// May return null pointer<br>
+    /// Allocated memory is not initialized<br>
+    /// Pointer must be freed with [Cache::free()], use [Cache::alloc_box()] to free it automatically
+    #[inline]
+    #[must_use = "the allocated pointer must be freed with Cache::free"]
+    pub unsafe fn alloc(&mut self) -> *mut T {
+        self.alloc_tracked().0
+    }
 
-        // Calculate/Get slab_addr and slab_info_addr
-        let (slab_addr, slab_info_addr) = {
-            if self.object_size_type == ObjectSizeType::Small && self.slab_size == self.page_size {
-                // In this case we may calculate slab info addr
-                let slab_addr = align_down(object_ptr as usize, self.page_size);
-                let slab_info_addr = calculate_slab_info_addr_in_small_object_cache(
-                    slab_addr as *mut u8,
-                    self.slab_size,
-                );
-                assert_ne!(slab_addr, 0);
-                assert_ne!(slab_info_addr, 0);
-                debug_assert!(slab_info_addr > slab_addr);
-                debug_assert!(slab_info_addr <= slab_addr + self.slab_size - size_of::<SlabInfo>());
-                assert_eq!(slab_info_addr % align_of::<SlabInfo>(), 0);
-                (slab_addr, slab_info_addr)
-            } else {
-                // Get slab info addr from memory backend
-                let object_addr = object_ptr as usize;
-                let object_page_addr = align_down(object_addr, self.page_size);
-                let slab_info_ptr = self.memory_backend.get_slab_info_ptr(object_page_addr);
-                assert!(!slab_info_ptr.is_null());
-                assert!(slab_info_ptr.is_aligned());
-                let slab_ptr = (*(*slab_info_ptr).data.get()).slab_ptr;
-                assert!(!slab_ptr.is_null());
-                (slab_ptr as usize, slab_info_ptr as usize)
-            }
+    /// Allocs object from cache with options set by flags
+    ///
+    /// Single entry point for callers which choose options at runtime, see [AllocFlags].<br>
+    /// With [AllocFlags::EMPTY] same as [Cache::alloc()].
+    ///
+    /// # Safety
+    /// May return null pointer<br>
+    /// Allocated memory is not initialized, unless [AllocFlags::ZERO] is set
+    #[must_use = "the allocated pointer must be freed with Cache::free"]
+    pub unsafe fn alloc_flags(&mut self, flags: AllocFlags) -> *mut T {
+        let object_ptr = if flags.contains(AllocFlags::NO_GROW) {
+            self.alloc_no_grow()
+        } else {
+            self.alloc()
         };
-        let free_object_ptr = object_ptr as *mut FreeObject;
-        free_object_ptr.write(FreeObject {
-            free_object_link: LinkedListLink::new(),
-        });
+        if !object_ptr.is_null() && flags.contains(AllocFlags::ZERO) && !self.config.zero_objects {
+            object_ptr.cast::<u8>().write_bytes(0, self.object_size);
+        }
+        object_ptr
+    }
 
-        // Return object to slab
-        let free_object_ref = UnsafeRef::from_raw(free_object_ptr);
-        let slab_info_ptr = slab_info_addr as *mut SlabInfo;
-        let slab_info_ref = UnsafeRef::from_raw(slab_info_ptr);
+    /// Same as [Cache::alloc()], but allocated object is zeroed
+    ///
+    /// # Safety
+    /// May return null pointer
+    #[must_use = "the allocated pointer must be freed with Cache::free"]
+    pub unsafe fn alloc_zeroed(&mut self) -> *mut T {
+        self.alloc_flags(AllocFlags::ZERO)
+    }
 
-        // Check cache
-        assert_eq!((*slab_info_ref.data.get()).cache_ptr, self as *mut _ as *mut u8, "It was not possible to verify that the object belongs to the cache. It looks like you try free an invalid address.");
-        assert_ne!((*slab_info_ref.data.get()).free_objects_number, self.objects_per_slab, "Attempting to free an unallocated object! There are no allocated objects in this slab. It looks like invalid address or double free.");
+    /// Same as [Cache::alloc()], but the pointer type says that memory is not initialized
+    ///
+    /// Object can be initialized by [MaybeUninit::write()], then freed by [Cache::free()] after cast or by [Cache::free_uninit()].
+    ///
+    /// # Safety
+    /// May return null pointer
+    #[must_use = "the allocated pointer must be freed with Cache::free_uninit"]
+    pub unsafe fn alloc_uninit(&mut self) -> *mut MaybeUninit<T> {
+        self.alloc().cast()
+    }
 
-        // Add object to free list
-        (*slab_info_ref.data.get())
-            .free_objects_list
-            .push_back(free_object_ref);
-        (*slab_info_ref.data.get()).free_objects_number += 1;
-        self.statistics.free_objects_number += 1;
-        self.statistics.allocated_objects_number -= 1;
+    /// Same as [Cache::alloc()], but reports why the object was not allocated
+    ///
+    /// Errors of [MemoryBackend::alloc_slab()] and [MemoryBackend::alloc_slab_info()] are returned as [CacheError::Backend].
+    ///
+    /// # Safety
+    /// Allocated memory is not initialized
+    pub unsafe fn try_alloc(&mut self) -> Result<*mut T, CacheError<M::Error>> {
+        self.alloc_with_grow().0.map(|(object_ptr, _)| object_ptr)
+    }
 
-        // Slab become free? (full -> free (>75))
-        if (*slab_info_ref.data.get()).free_objects_number == 1 {
-            // Move slab info from full list to free
-            let mut slab_info_full_list_cursor =
-                self.full_slabs_list.cursor_mut_from_ptr(slab_info_ptr);
-            self.statistics.full_slabs_number -= 1;
-            assert!(slab_info_full_list_cursor.remove().is_some());
+    /// Same as [Cache::alloc()], also reports whether this call allocated a new slab (slow path)
+    ///
+    /// The bool is true if [MemoryBackend::alloc_slab()] was called, even if it failed.
+    ///
+    /// # Safety
+    /// May return null pointer<br>
+    /// Allocated memory is not initialized
+    #[inline]
+    #[must_use = "the allocated pointer must be freed with Cache::free"]
+    pub unsafe fn alloc_tracked(&mut self) -> (*mut T, bool) {
+        let (allocated, slab_allocated) = self.alloc_with_grow();
+        (
+            allocated.map_or(null_mut(), |(object_ptr, _)| object_ptr),
+            slab_allocated,
+        )
+    }
 
-            // Add slab to free list
-            self.free_slabs_list_occupacy_more_75
-                .push_front(slab_info_ref.clone());
-            self.statistics.free_slabs_number += 1;
+    /// Same as [Cache::alloc()], also returns index of the object in its slab
+    ///
+    /// Index is (object_ptr - objects start in slab) / object stride, it is < [Cache::objects_per_slab()].<br>
+    /// Returns None if the object was not allocated.
+    ///
+    /// # Safety
+    /// Allocated memory is not initialized
+    #[must_use = "the allocated pointer must be freed with Cache::free"]
+    pub unsafe fn alloc_indexed(&mut self) -> Option<(*mut T, u32)> {
+        let (object_ptr, slab_ptr) = self.alloc_with_grow().0.ok()?;
+        let object_index = self
+            .geometry()
+            .object_index(slab_ptr, object_ptr.cast())
+            .unwrap();
+        let object_index = u32::try_from(object_index).expect("Object index doesn't fit in u32");
+        Some((object_ptr, object_index))
+    }
+
+    /// Same as [Cache::alloc()], also counts the object as live object of the allocation site
+    ///
+    /// site is any caller-supplied id, see [Cache::live_by_site()].<br>
+    /// Object must be freed by [Cache::free_tagged()] with the same site.
+    ///
+    /// # Safety
+    /// May return null pointer<br>
+    /// Allocated memory is not initialized
+    #[cfg(feature = "alloc_sites")]
+    #[must_use = "the allocated pointer must be freed with Cache::free_tagged"]
+    pub unsafe fn alloc_tagged(&mut self, site: u32) -> *mut T {
+        let object_ptr = self.alloc();
+        if !object_ptr.is_null() {
+            self.alloc_sites.increment(site);
         }
+        object_ptr
+    }
 
-        // Slab occupacy become less than 75? (free (>75) -> free (<75))
-        let allocated_objects_number =
-            self.objects_per_slab - (*slab_info_ref.data.get()).free_objects_number;
-        let previously_was_in_more_75_list =
-            allocated_objects_number + 1 >= self.occupacy_more_75_minimum_allocated_objects_number;
-        let now_in_less_75_list =
-            allocated_objects_number < self.occupacy_more_75_minimum_allocated_objects_number;
-        if previously_was_in_more_75_list && now_in_less_75_list {
-            // Move slab info from free (>75) to free (<75)
-            let mut slab_info_free_more_75_list_cursor = self
+    /// Allocs object, allocates new slab if there are no free objects
+    ///
+    /// Returns object ptr with its slab ptr, also returns whether new slab was requested
+    #[inline]
+    #[allow(clippy::type_complexity)]
+    unsafe fn alloc_with_grow(
+        &mut self,
+    ) -> (Result<(*mut T, *mut u8), CacheError<M::Error>>, bool) {
+        if self.is_max_allocated_objects_reached() {
+            return (
+                Err(CacheError::Cache(
+                    "Maximum allocated objects number is reached",
+                )),
+                false,
+            );
+        }
+        // Fast path, slab with free objects exists
+        match self.next_free_slab_info() {
+            Some(free_slab_info_ptr) => {
+                let slab_ptr = (*(*free_slab_info_ptr).data.get()).slab_ptr;
+                (
+                    Ok((self.alloc_in_slab(free_slab_info_ptr), slab_ptr)),
+                    false,
+                )
+            }
+            None => self.alloc_with_new_slab(NO_NODE),
+        }
+    }
+
+    /// Slow path of [Cache::alloc()] and [Cache::alloc_on_node()]: allocates new slab and allocs object from it
+    ///
+    /// Kept out of line, so slab setup code doesn't bloat the fast path.<br>
+    /// If the slab can't be allocated on the node, free slabs of other nodes are used, then slab without node is allocated.
+    #[cold]
+    #[inline(never)]
+    #[allow(clippy::type_complexity)]
+    unsafe fn alloc_with_new_slab(
+        &mut self,
+        node: u32,
+    ) -> (Result<(*mut T, *mut u8), CacheError<M::Error>>, bool) {
+        let start_time = self.config.clock.map(|clock| clock());
+        let mut grow_result = self.grow_on_node(node);
+        if grow_result.is_err() && node != NO_NODE && self.next_free_slab_info().is_none() {
+            grow_result = self.grow_on_node(NO_NODE);
+        }
+        if let Some(start_time) = start_time {
+            self.record_slow_path_time(start_time);
+        }
+        let free_slab_info_ptr = match grow_result {
+            Ok(free_slab_info_ptr) => {
+                // Memory backend works, refill the emergency reserve if it was used
+                self.refill_emergency_reserve();
+                free_slab_info_ptr
+            }
+            Err(error) => match self.next_free_slab_info() {
+                // Slab of another node
+                Some(free_slab_info_ptr) => free_slab_info_ptr,
+                None => {
+                    if !self.take_emergency_slab() {
+                        return (Err(CacheError::Backend(error)), true);
+                    }
+                    self.next_free_slab_info().unwrap()
+                }
+            },
+        };
+        let slab_ptr = (*(*free_slab_info_ptr).data.get()).slab_ptr;
+        let object_ptr = self.alloc_in_slab(free_slab_info_ptr);
+        self.sort_taken_slab(free_slab_info_ptr);
+        (Ok((object_ptr, slab_ptr)), true)
+    }
+
+    /// Allocs object from slab of the NUMA node
+    ///
+    /// Slabs allocated by [MemoryBackend::alloc_slab_on_node()] remember their node and their free objects are preferred.<br>
+    /// If the node has no slabs with free objects, new slab is allocated on it even if slabs of other nodes have free objects.<br>
+    /// Node is only a hint: if memory backend fails to allocate slab on the node, object is allocated like by [Cache::alloc()],
+    /// with [CacheConfig::on_oom] and the emergency reserve (see [Cache::set_emergency_reserve()]).<br>
+    /// node must not be u32::MAX, it marks slabs allocated without node.
+    ///
+    /// # Safety
+    /// May return null pointer<br>
+    /// Allocated memory is not initialized
+    #[must_use = "the allocated pointer must be freed with Cache::free"]
+    pub unsafe fn alloc_on_node(&mut self, node: u32) -> *mut T {
+        assert_ne!(node, NO_NODE, "Node u32::MAX is reserved");
+        if self.is_max_allocated_objects_reached() {
+            return null_mut();
+        }
+        match self.find_free_slab_on_node(node) {
+            Some(free_slab_info_ptr) => {
+                let object_ptr = self.alloc_in_slab(free_slab_info_ptr);
+                self.sort_taken_slab(free_slab_info_ptr);
+                object_ptr
+            }
+            None => self
+                .alloc_with_new_slab(node)
+                .0
+                .map_or(null_mut(), |(object_ptr, _)| object_ptr),
+        }
+    }
+
+    /// Finds slab of the NUMA node with free objects, the most occupied first
+    fn find_free_slab_on_node(&self, node: u32) -> Option<*const SlabInfo> {
+        self.free_slabs_list_occupacy_more_75
+            .iter()
+            .chain(self.free_slabs_list_occupacy_less_75.iter())
+            .find(|slab_info| unsafe { (*slab_info.data.get()).node } == node)
+            .map(|slab_info| slab_info as *const SlabInfo)
+    }
+
+    /// Adds time of alloc slow path started at start_time to [BackendStatistics]
+    fn record_slow_path_time(&mut self, start_time: u64) {
+        let clock = self.config.clock.unwrap();
+        let slow_path_time = clock().wrapping_sub(start_time);
+        self.backend_statistics.slow_path_time_total = self
+            .backend_statistics
+            .slow_path_time_total
+            .wrapping_add(slow_path_time);
+        self.backend_statistics.slow_path_time_last = slow_path_time;
+        self.backend_statistics.slow_path_time_max = self
+            .backend_statistics
+            .slow_path_time_max
+            .max(slow_path_time);
+    }
+
+    /// Allocs object from cache only if it has free objects, never allocates new slab
+    ///
+    /// Memory backend is not called, so it is safe to use inside the memory backend itself (e.g. cache backs part of the page allocator).<br>
+    /// Callers should reserve slabs ahead of time, e.g. by [Cache::reserve_contiguous()].
+    ///
+    /// # Safety
+    /// Returns null pointer if there are no free objects<br>
+    /// Allocated memory is not initialized
+    #[must_use = "the allocated pointer must be freed with Cache::free"]
+    pub unsafe fn alloc_no_grow(&mut self) -> *mut T {
+        if self.is_max_allocated_objects_reached() {
+            return null_mut();
+        }
+        match self.next_free_slab_info() {
+            Some(free_slab_info_ptr) => self.alloc_in_slab(free_slab_info_ptr),
+            None => null_mut(),
+        }
+    }
+
+    /// Allocs object from the slab, it must have free objects
+    unsafe fn alloc_in_slab(&mut self, free_slab_info_ptr: *const SlabInfo) -> *mut T {
+        // Get slab data
+        let free_slab_info_data = &mut *(*free_slab_info_ptr).data.get();
+
+        // Get object from not carved objects or from free objects list
+        let free_object_ptr = if free_slab_info_data.uncarved_objects_number != 0 {
+            free_slab_info_data.uncarved_objects_number -= 1;
+            self.object_ptr(
+                free_slab_info_data.slab_ptr,
+                free_slab_info_data.uncarved_objects_number,
+            )
+        } else {
+            self.pop_free_object(free_slab_info_data)
+        };
+        self.take_object(free_slab_info_ptr, free_object_ptr);
+        free_object_ptr.cast()
+    }
+
+    /// Gets slab from which the next object is allocated, by [CacheConfig::distribution_policy]
+    ///
+    /// Returns None if there are no slabs with free objects
+    fn next_free_slab_info(&self) -> Option<*const SlabInfo> {
+        let free_slab_info = match self.config.distribution_policy {
+            // First we try to choose the slab with the highest occupancy.
+            // This should allow to concentrate the allocations inside the most occupied slabs,
+            // while slabs with a small allocated number of objects are more likely to be freed.
+            DistributionPolicy::Concentrate => self
                 .free_slabs_list_occupacy_more_75
-                .cursor_mut_from_ptr(slab_info_ptr);
-            assert!(slab_info_free_more_75_list_cursor.remove().is_some());
+                .front()
+                .get()
+                .or_else(|| self.free_slabs_list_occupacy_less_75.front().get()),
+            // Choose the least occupied slabs first, the slab is moved to the back of its list after allocation
+            DistributionPolicy::Spread => self
+                .free_slabs_list_occupacy_less_75
+                .front()
+                .get()
+                .or_else(|| self.free_slabs_list_occupacy_more_75.front().get()),
+        };
+        free_slab_info.map(|slab_info| slab_info as *const SlabInfo)
+    }
+
+    /// Gets slab from which the next [Cache::alloc()] would take the object, without allocating
+    ///
+    /// Returns None if there are no slabs with free objects, then alloc would allocate a new slab.<br>
+    /// Allows to make placement decisions (e.g. redirect to another cache) before allocating.<br>
+    /// [CacheConfig::max_allocated_objects] is not checked.
+    pub fn peek_next_slab(&self) -> Option<*const u8> {
+        self.next_free_slab_info()
+            .map(|slab_info_ptr| unsafe { (*(*slab_info_ptr).data.get()).slab_ptr as *const u8 })
+    }
+
+    /// Allocs object from cache with address aligned to align
+    ///
+    /// Searches free objects for an aligned one, objects are not moved inside slabs.<br>
+    /// If there is no such object, allocates new slab, unless objects at such addresses can't be in any slab.<br>
+    /// Returns null if the new slab also has no aligned object, then the new slab is freed (kept if [CacheConfig::reclaim] is not set).<br>
+    /// Same as [Cache::alloc()] if all objects are aligned.
+    ///
+    /// # Safety
+    /// May return null pointer<br>
+    /// Allocated memory is not initialized
+    #[must_use = "the allocated pointer must be freed with Cache::free"]
+    pub unsafe fn alloc_aligned(&mut self, align: usize) -> *mut T {
+        assert!(align.is_power_of_two(), "Alignment is not power of two");
+        if self.is_max_allocated_objects_reached() {
+            return null_mut();
+        }
+        if align <= self.page_size
+            && self.object_size.is_multiple_of(align)
+            && self.objects_offset.is_multiple_of(align)
+        {
+            // Slab is page aligned, all objects are aligned
+            return self.alloc();
+        }
+
+        let mut aligned_object = self.find_aligned_free_object(align);
+        if aligned_object.is_none() {
+            if !self.can_align_object(align) {
+                // New slab would not help
+                return null_mut();
+            }
+            let Ok(slab_info_ptr) = self.grow_on_node(NO_NODE) else {
+                return null_mut();
+            };
+            aligned_object = self.find_aligned_free_object(align);
+            if aligned_object.is_none() && self.config.reclaim {
+                // Slab is not aligned enough
+                let slab_ptr = (*(*slab_info_ptr).data.get()).slab_ptr;
+                self.free_empty_slabs_where(
+                    |slab_info_data| slab_info_data.slab_ptr == slab_ptr,
+                    false,
+                );
+            }
+        }
+        let Some((slab_info_ptr, object_ptr)) = aligned_object else {
+            return null_mut();
+        };
+        self.take_object(slab_info_ptr, object_ptr);
+        self.sort_taken_slab(slab_info_ptr);
+        object_ptr.cast()
+    }
+
+    /// Checks whether object of some slab may have address aligned to align
+    ///
+    /// Slab is only known to be page aligned, so objects offsets are checked for alignment up to page_size.
+    fn can_align_object(&self, align: usize) -> bool {
+        let known_align = align.min(self.page_size);
+        (0..self.objects_per_slab)
+            .any(|i| (self.objects_offset + i * self.object_size).is_multiple_of(known_align))
+    }
+
+    /// Moves slab to its place in free (<75) list after an object was taken from it, for [DistributionPolicy::Concentrate]
+    ///
+    /// Needed when the slab was not the front of the list, i.e. the object was taken not by [Cache::next_free_slab_info()].
+    unsafe fn sort_taken_slab(&mut self, slab_info_ptr: *const SlabInfo) {
+        let slab_info_data = &*(*slab_info_ptr).data.get();
+        let allocated_objects_number = self.objects_per_slab - slab_info_data.free_objects_number;
+        if slab_info_data.free_objects_number != 0
+            && allocated_objects_number < self.occupacy_more_75_minimum_allocated_objects_number
+            && self.config.distribution_policy == DistributionPolicy::Concentrate
+        {
+            // Slab could be taken from the middle of free (<75) list, its occupancy increased, so move it to the right place
+            self.check_slab_info_linked(slab_info_ptr);
+            let slab_info = self
+                .free_slabs_list_occupacy_less_75
+                .cursor_mut_from_ptr(slab_info_ptr)
+                .remove()
+                .unwrap();
+            self.free_slabs_list_occupacy_less_75.push_front(slab_info);
+            self.sort_slab_in_free_less_75_list(slab_info_ptr as *mut SlabInfo);
+        }
+    }
+
+    /// Checks [CacheConfig::max_allocated_objects]
+    fn is_max_allocated_objects_reached(&self) -> bool {
+        self.config
+            .max_allocated_objects
+            .is_some_and(|max_allocated_objects| {
+                self.statistics.allocated_objects_number >= max_allocated_objects
+            })
+    }
+
+    /// Finds free object with address aligned to align and removes it from free objects list
+    unsafe fn find_aligned_free_object(&self, align: usize) -> Option<(*const SlabInfo, *mut u8)> {
+        for slab_info in self
+            .free_slabs_list_occupacy_more_75
+            .iter()
+            .chain(self.free_slabs_list_occupacy_less_75.iter())
+        {
+            let slab_info_data = &mut *slab_info.data.get();
+            let has_uncarved_aligned_object =
+                (0..slab_info_data.uncarved_objects_number).any(|i| {
+                    (self.object_ptr(slab_info_data.slab_ptr, i) as usize).is_multiple_of(align)
+                });
+            if has_uncarved_aligned_object {
+                // Carve all objects, the aligned one is taken from the list
+                for i in 0..slab_info_data.uncarved_objects_number {
+                    self.push_free_object(
+                        slab_info_data,
+                        self.object_ptr(slab_info_data.slab_ptr, i),
+                    );
+                }
+                slab_info_data.uncarved_objects_number = 0;
+            }
+
+            let mut previous_free_object_ptr = null();
+            let mut free_object_ptr = self.next_free_object(slab_info_data, null());
+            while !free_object_ptr.is_null() {
+                if (free_object_ptr as usize).is_multiple_of(align) {
+                    self.remove_free_object(
+                        slab_info_data,
+                        previous_free_object_ptr,
+                        free_object_ptr as *mut u8,
+                    );
+                    return Some((slab_info as *const SlabInfo, free_object_ptr as *mut u8));
+                }
+                previous_free_object_ptr = free_object_ptr;
+                free_object_ptr = self.next_free_object(slab_info_data, free_object_ptr);
+            }
+        }
+        None
+    }
+
+    /// Allocates new slab and adds it to the cache
+    ///
+    /// If memory backend fails and [CacheConfig::on_oom] reports that memory was freed, allocation is retried once.
+    unsafe fn grow(&mut self) -> Result<(), M::Error> {
+        self.grow_on_node(NO_NODE).map(|_| ())
+    }
+
+    /// Same as [Cache::grow()], but slab is allocated on the NUMA node, see [Cache::alloc_on_node()]
+    ///
+    /// With NO_NODE slab is allocated without node. Returns SlabInfo ptr of the new slab
+    unsafe fn grow_on_node(&mut self, node: u32) -> Result<*const SlabInfo, M::Error> {
+        let mut slab_ptr = self.alloc_slab_memory(node);
+        if slab_ptr.is_err() && self.config.on_oom.is_some_and(|on_oom| on_oom()) {
+            slab_ptr = self.alloc_slab_memory(node);
+        }
+        let slab_ptr = slab_ptr?;
+        self.backend_statistics.slab_allocs += 1;
+
+        self.add_slab(slab_ptr)?;
+        // New slab is at the back of free (<75) list
+        let slab_info_ref = self.free_slabs_list_occupacy_less_75.back().get().unwrap();
+        (*slab_info_ref.data.get()).node = node;
+        Ok(slab_info_ref as *const SlabInfo)
+    }
+
+    /// Allocates slab memory by memory backend on the node (NO_NODE for any), zeroed if [CacheConfig::zero_objects] is set
+    unsafe fn alloc_slab_memory(&mut self, node: u32) -> Result<*mut u8, M::Error> {
+        let slab_ptr = if node == NO_NODE {
+            if self.config.zero_objects {
+                self.memory_backend
+                    .alloc_slab_zeroed(self.slab_size, self.page_size)?
+            } else {
+                self.memory_backend
+                    .alloc_slab(self.slab_size, self.page_size)?
+            }
+        } else {
+            let slab_ptr =
+                self.memory_backend
+                    .alloc_slab_on_node(self.slab_size, self.page_size, node)?;
+            if self.config.zero_objects {
+                slab_ptr.write_bytes(0, self.slab_size);
+            }
+            slab_ptr
+        };
+        assert!(
+            !slab_ptr.is_null(),
+            "Memory backend returns null slab as Ok"
+        );
+        Ok(slab_ptr)
+    }
+
+    /// Updates slab and cache after the object has been taken from free objects of the slab
+    unsafe fn take_object(
+        &mut self,
+        free_slab_info_ptr: *const SlabInfo,
+        free_object_ptr: *mut u8,
+    ) {
+        Self::assert_slab_info_canary(free_slab_info_ptr);
+        let free_slab_info = &*free_slab_info_ptr;
+        // Get slab data
+        let free_slab_info_data = &mut *free_slab_info.data.get();
+
+        free_slab_info_data.free_objects_number -= 1;
+        statistics_sub!(self.statistics, free_objects_number, 1);
+        self.operations_number += 1;
+        free_slab_info_data.last_used_operation = self.operations_number;
+        if self.config.zero_objects {
+            // Rest of the object was zeroed by slab allocation or by free
+            free_object_ptr.write_bytes(0, self.free_object_link_size());
+        }
+
+        // Save SlabInfo ptr
+        if !(self.object_size_type == ObjectSizeType::Small && self.slab_size == self.page_size) {
+            let free_slab_info_ptr = free_slab_info as *const _ as *mut _;
+            let free_object_page_addr = align_down(free_object_ptr as usize, self.page_size);
+            check!(
+                self,
+                AssertLevel::Normal,
+                free_object_page_addr.is_multiple_of(self.page_size)
+            );
+
+            // In this case we can avoid unnecessary saving for this page, if it already has allocated objects, the slab into ptr is already saved.
+            let mut dont_save = false;
+            if self.objects_per_slab >= 2 {
+                dont_save = self.slab_size == self.page_size
+                    && free_slab_info_data.free_objects_number <= self.objects_per_slab - 2;
+            }
+
+            if !dont_save {
+                self.memory_backend
+                    .save_slab_info_ptr(free_object_page_addr, free_slab_info_ptr);
+                self.backend_statistics.save_calls += 1;
+            }
+        }
+
+        // Occupancy is read once, the slab is moved to its new list at most once
+        let free_objects_number = free_slab_info_data.free_objects_number;
+        let allocated_objects_number = self.objects_per_slab - free_objects_number;
+        let previous_slab_list_kind = self.free_slab_list_kind(allocated_objects_number - 1);
+        let slab_list_kind = if free_objects_number == 0 {
+            SlabListKind::Full
+        } else {
+            self.free_slab_list_kind(allocated_objects_number)
+        };
+
+        if slab_list_kind != previous_slab_list_kind {
+            // Remove from the previous free list
+            self.check_slab_info_linked(free_slab_info);
+            let previous_free_slabs_list = match previous_slab_list_kind {
+                SlabListKind::FreeLess75 => &mut self.free_slabs_list_occupacy_less_75,
+                SlabListKind::FreeMore75 => &mut self.free_slabs_list_occupacy_more_75,
+                SlabListKind::Full => unreachable!(),
+            };
+            let free_slab_info = previous_free_slabs_list
+                .cursor_mut_from_ptr(free_slab_info as *const SlabInfo)
+                .remove()
+                .unwrap();
+
+            match slab_list_kind {
+                // free (<75) -> free (>75)
+                SlabListKind::FreeMore75 => self
+                    .free_slabs_list_occupacy_more_75
+                    .push_front(free_slab_info),
+                // free -> full
+                SlabListKind::Full => {
+                    statistics_sub!(self.statistics, free_slabs_number, 1);
+                    self.push_full_slab(free_slab_info);
+                    statistics_add!(self.statistics, full_slabs_number, 1);
+                }
+                SlabListKind::FreeLess75 => unreachable!(),
+            }
+
+            if previous_slab_list_kind == SlabListKind::FreeLess75
+                && slab_list_kind == SlabListKind::Full
+                && self.has_free_more_75_list()
+            {
+                // Slab has crossed the threshold and become full by one object
+                self.notify_transition(
+                    free_slab_info_data.slab_ptr,
+                    SlabListKind::FreeLess75,
+                    SlabListKind::FreeMore75,
+                );
+                self.notify_transition(
+                    free_slab_info_data.slab_ptr,
+                    SlabListKind::FreeMore75,
+                    SlabListKind::Full,
+                );
+            } else {
+                self.notify_transition(
+                    free_slab_info_data.slab_ptr,
+                    previous_slab_list_kind,
+                    slab_list_kind,
+                );
+            }
+        } else if self.config.distribution_policy == DistributionPolicy::Spread {
+            // Rotate, next allocation is taken from another slab
+            self.check_slab_info_linked(free_slab_info);
+            let free_slabs_list = match slab_list_kind {
+                SlabListKind::FreeLess75 => &mut self.free_slabs_list_occupacy_less_75,
+                SlabListKind::FreeMore75 => &mut self.free_slabs_list_occupacy_more_75,
+                SlabListKind::Full => unreachable!(),
+            };
+            let free_slab_info = free_slabs_list
+                .cursor_mut_from_ptr(free_slab_info as *const SlabInfo)
+                .remove()
+                .unwrap();
+            free_slabs_list.push_back(free_slab_info);
+        }
+
+        statistics_add!(self.statistics, allocated_objects_number, 1);
+        self.record_statistics();
+        self.check_paranoid();
+    }
+
+    /// Checks whether slabs go through free (>75) list between free (<75) and full lists
+    ///
+    /// Single object slab has no occupancy between empty and full, also free lists may be not split by [CacheConfig::split_free_lists].
+    fn has_free_more_75_list(&self) -> bool {
+        self.objects_per_slab != 1 && self.config.split_free_lists
+    }
+
+    /// Gets free list of slab with allocated_objects_number allocated objects, the slab must have free objects
+    fn free_slab_list_kind(&self, allocated_objects_number: usize) -> SlabListKind {
+        if allocated_objects_number >= self.occupacy_more_75_minimum_allocated_objects_number {
+            SlabListKind::FreeMore75
+        } else {
+            SlabListKind::FreeLess75
+        }
+    }
+
+    /// Allocs object from cache and moves value into it
+    ///
+    /// Returns [SlabBox] which drops the value and returns object to cache when dropped.<br>
+    /// Returns None (and drops value) if the cache failed to allocate object.
+    #[must_use = "dropping the box immediately frees the object"]
+    pub fn alloc_box(&mut self, value: T) -> Option<SlabBox<'_, T, M>> {
+        unsafe {
+            let object_ptr = self.alloc();
+            if object_ptr.is_null() {
+                return None;
+            }
+            object_ptr.write(value);
+            Some(SlabBox::from_raw(object_ptr, self))
+        }
+    }
+
+    /// Adds slab memory owned by the caller to the cache, without calling [MemoryBackend::alloc_slab()]
+    ///
+    /// E.g. a statically reserved page lets the cache allocate objects before memory backend can allocate slabs.<br>
+    /// Slab is set up the same way as a slab from memory backend and becomes an ordinary slab of the cache,
+    /// but it is never passed to [MemoryBackend::free_slab()]: when it is freed (with [CacheConfig::reclaim], when drained or dropped),
+    /// the cache just stops using it and the memory is owned by the caller again.<br>
+    /// [ObjectSizeType::Large] caches allocate its SlabInfo by memory backend.<br>
+    /// Returns error if slab_ptr is null, not page aligned or overlaps another slab, or if SlabInfo allocation failed. Then the slab is not used.
+    ///
+    /// # Safety
+    /// slab_ptr must point to slab_size bytes of memory which are not used by anything else
+    pub unsafe fn donate_slab(&mut self, slab_ptr: *mut u8) -> Result<(), CacheError<M::Error>> {
+        if slab_ptr.is_null() {
+            return Err(CacheError::Cache("Donated slab is null"));
+        }
+        if !(slab_ptr as usize).is_multiple_of(self.page_size) {
+            return Err(CacheError::Cache("Donated slab is not page aligned"));
+        }
+        if self.slab_base_addresses().any(|(slab_addr, slab_size)| {
+            (slab_ptr as usize) < slab_addr + slab_size
+                && slab_ptr as usize + self.slab_size > slab_addr
+        }) {
+            return Err(CacheError::Cache(
+                "Donated slab overlaps another slab of the cache",
+            ));
+        }
+        if self.config.zero_objects {
+            slab_ptr.write_bytes(0, self.slab_size);
+        }
+        self.init_slab(slab_ptr)?;
+        // New slab is at the back of free (<75) list
+        let slab_info_ref = self.free_slabs_list_occupacy_less_75.back().get().unwrap();
+        (*slab_info_ref.data.get()).donated = true;
+        Ok(())
+    }
+
+    /// Adds slab allocated by memory backend to the cache, see [Cache::init_slab()]
+    ///
+    /// Returns error if SlabInfo allocation failed, in this case the slab is freed (leaked if [CacheConfig::reclaim] is not set).
+    unsafe fn add_slab(&mut self, slab_ptr: *mut u8) -> Result<(), M::Error> {
+        let result = self.init_slab(slab_ptr);
+        if result.is_err() {
+            self.free_unused_slab_memory(slab_ptr);
+        }
+        result
+    }
+
+    /// Frees slab memory which was allocated but not added to the cache
+    ///
+    /// Memory backend free methods must not be called without [CacheConfig::reclaim], so the slab is leaked then.
+    unsafe fn free_unused_slab_memory(&mut self, slab_ptr: *mut u8) {
+        if self.config.reclaim {
+            self.free_slab_memory(slab_ptr);
+        }
+    }
+
+    /// Sets up SlabInfo for the new slab, carves free objects and adds slab to free (<75) list
+    ///
+    /// Returns error if SlabInfo allocation failed, the slab is not freed.
+    unsafe fn init_slab(&mut self, slab_ptr: *mut u8) -> Result<(), M::Error> {
+        assert!(!slab_ptr.is_null());
+        // Objects are found by aligning their address down to the page, it would give wrong slab
+        assert!(
+            (slab_ptr as usize).is_multiple_of(self.page_size),
+            "Memory backend allocates not page aligned slab"
+        );
+        // Memory backend bug (e.g. double allocation) would corrupt free objects lists silently
+        check!(
+            self,
+            AssertLevel::Normal,
+            self.slab_base_addresses().all(|(slab_addr, slab_size)| {
+                slab_ptr as usize >= slab_addr + slab_size
+                    || slab_ptr as usize + self.slab_size <= slab_addr
+            }),
+            "Memory backend returned slab overlapping another slab of the cache"
+        );
+        // Calculate/allocate SlabInfo ptr
+        let slab_info_ptr = match self.object_size_type {
+            ObjectSizeType::Small => {
+                // SlabInfo stored inside slab, at start or end
+                let slab_info_addr = calculate_slab_info_addr_in_small_object_cache(
+                    slab_ptr,
+                    self.slab_size,
+                    self.config.small_info_placement,
+                );
+                assert!(slab_info_addr >= slab_ptr as usize);
+                assert!(
+                    slab_info_addr <= slab_ptr as usize + self.slab_size - size_of::<SlabInfo>()
+                );
+
+                slab_info_addr as *mut SlabInfo
+            }
+            ObjectSizeType::Large => {
+                // Allocate memory using memory backend
+                let slab_info_ptr = self.memory_backend.alloc_slab_info()?;
+                self.backend_statistics.slab_info_allocs += 1;
+                assert!(
+                    !slab_info_ptr.is_null(),
+                    "Memory backend returns null SlabInfo as Ok"
+                );
+                assert!(
+                    slab_info_ptr.is_aligned(),
+                    "Memory backend allocates not aligned SlabInfo"
+                );
+                slab_info_ptr
+            }
+        };
+        assert!(!slab_info_ptr.is_null());
+        assert!(slab_info_ptr.is_aligned());
+
+        // Fill SlabInfo
+        slab_info_ptr.write(SlabInfo {
+            slab_link: LinkedListLink::new(),
+            data: UnsafeCell::new(SlabInfoData {
+                canary: SLAB_INFO_CANARY,
+                free_objects_list: LinkedList::new(FreeObjectAdapter::new()),
+                free_objects_offset_list: OffsetFreeList::new(),
+                node: NO_NODE,
+                cache_id: self.id,
+                free_objects_number: self.objects_per_slab,
+                uncarved_objects_number: if self.config.lazy_carve {
+                    self.objects_per_slab
+                } else {
+                    0
+                },
+                slab_ptr,
+                last_used_operation: self.operations_number,
+                donated: false,
+            }),
+        });
+
+        // Make SlabInfo ref
+        let slab_info_ref = UnsafeRef::from_raw(slab_info_ptr);
+        // Add SlabInfo to free list
+        self.free_slabs_list_occupacy_less_75
+            .push_back(slab_info_ref);
+        statistics_add!(self.statistics, free_slabs_number, 1);
+        statistics_add!(self.statistics, free_objects_number, self.objects_per_slab);
+        if self.config.lazy_carve {
+            // Objects are taken by alloc directly from the slab memory
+            return Ok(());
+        }
+
+        // Fill free objects list
+        for free_object_index in 0..self.objects_per_slab {
+            // Free object stored in slab
+            let free_object_ptr = self.object_ptr(slab_ptr, free_object_index);
+            self.push_free_object((*slab_info_ptr).data.get_mut(), free_object_ptr);
+        }
+        Ok(())
+    }
+
+    /// Gets size of free object link written in free object
+    fn free_object_link_size(&self) -> usize {
+        if self.config.offset_free_links {
+            size_of::<u32>()
+        } else {
+            size_of::<FreeObject>()
+        }
+    }
+
+    /// Adds free object to free objects list of the slab
+    ///
+    /// Object memory is used for free object link
+    unsafe fn push_free_object(&self, slab_info_data: &mut SlabInfoData, free_object_ptr: *mut u8) {
+        if self.config.offset_free_links {
+            slab_info_data
+                .free_objects_offset_list
+                .push(slab_info_data.slab_ptr, free_object_ptr);
+        } else {
+            let free_object_ptr = free_object_ptr.cast::<FreeObject>();
+            assert!(free_object_ptr.is_aligned(), "FreeObject addr not aligned!");
+            free_object_ptr.write(FreeObject {
+                free_object_link: LinkedListLink::new(),
+            });
+            slab_info_data
+                .free_objects_list
+                .push_back(UnsafeRef::from_raw(free_object_ptr));
+        }
+    }
+
+    /// Takes the last added free object from free objects list of the slab
+    ///
+    /// List must not be empty
+    unsafe fn pop_free_object(&self, slab_info_data: &mut SlabInfoData) -> *mut u8 {
+        if self.config.offset_free_links {
+            slab_info_data
+                .free_objects_offset_list
+                .pop(slab_info_data.slab_ptr)
+        } else {
+            let free_object_ref = slab_info_data.free_objects_list.pop_back().unwrap();
+            UnsafeRef::into_raw(free_object_ref).cast()
+        }
+    }
+
+    /// Removes free object from free objects list of the slab
+    ///
+    /// previous_free_object_ptr is the previous object in list (null for the first), it is used by offset links
+    unsafe fn remove_free_object(
+        &self,
+        slab_info_data: &mut SlabInfoData,
+        previous_free_object_ptr: *const u8,
+        free_object_ptr: *mut u8,
+    ) {
+        if self.config.offset_free_links {
+            slab_info_data
+                .free_objects_offset_list
+                .remove(previous_free_object_ptr, free_object_ptr);
+        } else {
+            assert!(slab_info_data
+                .free_objects_list
+                .cursor_mut_from_ptr(free_object_ptr.cast::<FreeObject>())
+                .remove()
+                .is_some());
+        }
+    }
+
+    /// Checks that object is neither in free objects list of the slab nor not carved
+    ///
+    /// Catches double free before it loops the free objects list. Walks the list, so it is [AssertLevel::Normal] check.
+    unsafe fn check_object_not_free(&self, slab_info_data: &SlabInfoData, object_ptr: *const u8) {
+        if !self.config.assert_level.enables(AssertLevel::Normal) {
+            return;
+        }
+        let carved_objects_addr = self.object_ptr(
+            slab_info_data.slab_ptr,
+            slab_info_data.uncarved_objects_number,
+        ) as usize;
+        assert!(
+            object_ptr as usize >= carved_objects_addr,
+            "Attempting to free an object which has never been allocated! It looks like invalid address."
+        );
+        // List may be already corrupted, don't walk more nodes than it must have
+        let carved_free_objects_number =
+            slab_info_data.free_objects_number - slab_info_data.uncarved_objects_number;
+        let mut free_object_ptr = self.next_free_object(slab_info_data, null());
+        for _ in 0..carved_free_objects_number {
+            if free_object_ptr.is_null() {
+                break;
+            }
+            assert_ne!(
+                free_object_ptr, object_ptr,
+                "Double free! The object is already in free objects list."
+            );
+            free_object_ptr = self.next_free_object(slab_info_data, free_object_ptr);
+        }
+    }
+
+    /// Gets next object in free objects list of the slab
+    ///
+    /// Returns the first object if free_object_ptr is null and null if there is no next object.<br>
+    /// Only free_object_ptr memory is read.
+    unsafe fn next_free_object(
+        &self,
+        slab_info_data: &SlabInfoData,
+        free_object_ptr: *const u8,
+    ) -> *const u8 {
+        if self.config.offset_free_links {
+            slab_info_data
+                .free_objects_offset_list
+                .next(slab_info_data.slab_ptr, free_object_ptr)
+        } else {
+            let next_free_object_ptr = if free_object_ptr.is_null() {
+                slab_info_data
+                    .free_objects_list
+                    .front()
+                    .get()
+                    .map_or(null(), |free_object| free_object as *const FreeObject)
+            } else {
+                slab_info_data
+                    .free_objects_list
+                    .cursor_from_ptr(free_object_ptr.cast())
+                    .peek_next()
+                    .get()
+                    .map_or(null(), |free_object| free_object as *const FreeObject)
+            };
+            next_free_object_ptr.cast()
+        }
+    }
+
+    /// Allocates new slabs until the cache has at least objects free objects
+    ///
+    /// Counts objects: slabs are allocated only if current free objects are not enough.<br>
+    /// Use [Cache::reserve_slabs()] to allocate exactly the given number of slabs.<br>
+    /// On error the slabs allocated before it stay in the cache.
+    /// If [MemoryBackend::available_slabs_hint()] is less than needed slabs, returns error without allocating slabs.
+    ///
+    /// # Safety
+    /// Calls memory backend
+    pub unsafe fn reserve_objects(&mut self, objects: usize) -> Result<(), CacheError<M::Error>> {
+        let missing_objects = objects.saturating_sub(self.statistics.free_objects_number);
+        self.check_available_slabs(missing_objects.div_ceil(self.objects_per_slab))?;
+        while self.statistics.free_objects_number < objects {
+            self.grow()?;
+        }
+        Ok(())
+    }
+
+    /// Allocates exactly slabs new slabs, regardless of current free objects
+    ///
+    /// Counts slabs (i.e. pages of memory), use [Cache::reserve_objects()] to count objects.<br>
+    /// Slabs are allocated one by one like by [Cache::alloc()], use [Cache::reserve_contiguous()] to request them at once.<br>
+    /// On error the slabs allocated before it stay in the cache.
+    /// If [MemoryBackend::available_slabs_hint()] is less than slabs, returns error without allocating slabs.
+    ///
+    /// # Safety
+    /// Calls memory backend
+    pub unsafe fn reserve_slabs(&mut self, slabs: usize) -> Result<(), CacheError<M::Error>> {
+        self.check_available_slabs(slabs)?;
+        for _ in 0..slabs {
+            self.grow()?;
+        }
+        Ok(())
+    }
+
+    /// Returns out of memory error if [MemoryBackend::available_slabs_hint()] is less than slabs
+    fn check_available_slabs(&self, slabs: usize) -> Result<(), CacheError<M::Error>> {
+        if self
+            .memory_backend
+            .available_slabs_hint(self.slab_size, self.page_size)
+            .is_some_and(|available_slabs| available_slabs < slabs)
+        {
+            return Err(CacheError::Backend(OutOfMemory.into()));
+        }
+        Ok(())
+    }
+
+    /// Sets number of empty slabs kept for allocations when memory backend is out of memory
+    ///
+    /// Reserved slabs are not used while memory backend can allocate slabs, alloc takes one of them only before returning an error.<br>
+    /// The reserve is refilled by alloc when memory backend allocates slabs again. Reserved slabs are not counted in statistics
+    /// and are not freed by [Cache::drain_free_slabs()], set_emergency_reserve(0) frees them.<br>
+    /// Returns error if memory backend failed to fill the reserve, it will be refilled later.
+    ///
+    /// # Safety
+    /// Calls memory backend
+    pub unsafe fn set_emergency_reserve(
+        &mut self,
+        slabs: usize,
+    ) -> Result<(), CacheError<M::Error>> {
+        self.emergency_reserve_slabs = slabs;
+        while self.emergency_slabs_number > slabs {
+            let slab_info_ref = self.emergency_slabs_list.pop_front().unwrap();
+            self.emergency_slabs_number -= 1;
+            self.release_slab(UnsafeRef::into_raw(slab_info_ref));
+        }
+        while self.emergency_slabs_number < slabs {
+            self.grow_emergency_reserve()?;
+        }
+        Ok(())
+    }
+
+    /// Gets number of slabs in the emergency reserve, see [Cache::set_emergency_reserve()]
+    pub fn emergency_slabs_number(&self) -> usize {
+        self.emergency_slabs_number
+    }
+
+    /// Allocates slab into the emergency reserve
+    unsafe fn grow_emergency_reserve(&mut self) -> Result<(), M::Error> {
+        let slab_ptr = self.alloc_slab_memory(NO_NODE)?;
+        self.backend_statistics.slab_allocs += 1;
+        self.add_slab(slab_ptr)?;
+        // New slab is at the back of free (<75) list
+        let slab_info_ref = self.free_slabs_list_occupacy_less_75.pop_back().unwrap();
+        statistics_sub!(self.statistics, free_slabs_number, 1);
+        statistics_sub!(self.statistics, free_objects_number, self.objects_per_slab);
+        self.emergency_slabs_list.push_back(slab_info_ref);
+        self.emergency_slabs_number += 1;
+        Ok(())
+    }
+
+    /// Allocates slabs into the emergency reserve until it is full or memory backend fails
+    unsafe fn refill_emergency_reserve(&mut self) {
+        while self.emergency_slabs_number < self.emergency_reserve_slabs {
+            if self.grow_emergency_reserve().is_err() {
+                break;
+            }
+        }
+    }
+
+    /// Moves slab from the emergency reserve to free (<75) list
+    ///
+    /// Returns false if the reserve is empty
+    unsafe fn take_emergency_slab(&mut self) -> bool {
+        let Some(slab_info_ref) = self.emergency_slabs_list.pop_front() else {
+            return false;
+        };
+        self.emergency_slabs_number -= 1;
+        // Empty slab is the least occupied, the list stays sorted
+        self.free_slabs_list_occupacy_less_75
+            .push_back(slab_info_ref);
+        statistics_add!(self.statistics, free_slabs_number, 1);
+        statistics_add!(self.statistics, free_objects_number, self.objects_per_slab);
+        true
+    }
+
+    /// Allocates slabs in advance using [MemoryBackend::alloc_slabs()]
+    ///
+    /// All slabs are carved from one contiguous region got by a single memory backend call, this is cheaper than allocating slabs one by one.<br>
+    /// If memory backend can't give such region, slabs are allocated one by one with [MemoryBackend::alloc_slab()].<br>
+    /// Returns the number of allocated slabs, it is less than slabs if memory backend ran out of memory.<br>
+    /// Doesn't request more slabs than [MemoryBackend::available_slabs_hint()].
+    ///
+    /// # Safety
+    /// Calls memory backend
+    pub unsafe fn reserve_contiguous(&mut self, slabs: usize) -> usize {
+        let slabs = match self
+            .memory_backend
+            .available_slabs_hint(self.slab_size, self.page_size)
+        {
+            Some(available_slabs) => slabs.min(available_slabs),
+            None => slabs,
+        };
+        if slabs == 0 {
+            return 0;
+        }
+        let region_ptr = if slabs.checked_mul(self.slab_size).is_some() {
+            self.memory_backend
+                .alloc_slabs(slabs, self.slab_size, self.page_size)
+                .ok()
+        } else {
+            None
+        };
+        let Some(region_ptr) = region_ptr else {
+            let mut reserved_slabs_number = 0;
+            while reserved_slabs_number < slabs && self.grow().is_ok() {
+                reserved_slabs_number += 1;
+            }
+            return reserved_slabs_number;
+        };
+        assert!(
+            !region_ptr.is_null(),
+            "Memory backend returns null slabs region as Ok"
+        );
+        assert!(
+            (region_ptr as usize).is_multiple_of(self.page_size),
+            "Memory backend returned not page aligned slabs region"
+        );
+        self.backend_statistics.slab_allocs += slabs;
+
+        for i in 0..slabs {
+            let slab_ptr = region_ptr.add(i * self.slab_size);
+            if self.config.zero_objects {
+                slab_ptr.write_bytes(0, self.slab_size);
+            }
+            if self.add_slab(slab_ptr).is_err() {
+                // Return remaining slabs
+                for j in i + 1..slabs {
+                    self.free_unused_slab_memory(region_ptr.add(j * self.slab_size));
+                }
+                return i;
+            }
+        }
+        slabs
+    }
+
+    /// Moves all slabs of another cache with the same geometry into this cache
+    ///
+    /// Objects allocated from other cache must be freed to this cache after that.<br>
+    /// Saved SlabInfo ptrs are deleted from other memory backend and saved to this one, then other memory backend is dropped.<br>
+    /// Slabs and SlabInfo's allocated by other memory backend will be freed by this memory backend,
+    /// so both backends must use the same memory source (e.g. per-CPU caches on top of one page allocator).<br>
+    /// Emergency reserve slabs and reserve size of other are added to this cache reserve.
+    ///
+    /// # Safety
+    /// Memory backends must be able to free memory allocated by each other
+    pub unsafe fn absorb(&mut self, mut other: Cache<T, M>) {
+        assert_eq!(
+            self.slab_size, other.slab_size,
+            "Caches have different slab size"
+        );
+        assert_eq!(
+            self.page_size, other.page_size,
+            "Caches have different page size"
+        );
+        assert_eq!(
+            self.object_size_type, other.object_size_type,
+            "Caches have different object size type"
+        );
+        assert_eq!(
+            self.object_size, other.object_size,
+            "Caches have different object size (object alignment)"
+        );
+        assert_eq!(
+            self.objects_offset, other.objects_offset,
+            "Caches have different SlabInfo placement"
+        );
+        assert_eq!(
+            self.config.zero_objects, other.config.zero_objects,
+            "Caches have different zero_objects mode"
+        );
+        assert_eq!(
+            self.config.offset_free_links, other.config.offset_free_links,
+            "Caches have different free objects links"
+        );
+        assert_eq!(
+            self.occupancy_threshold_percent, other.occupancy_threshold_percent,
+            "Caches have different occupancy threshold"
+        );
+        check!(
+            self,
+            AssertLevel::Normal,
+            self.objects_per_slab == other.objects_per_slab
+        );
+
+        while let Some(slab_info_ref) = other.full_slabs_list.pop_front() {
+            self.adopt_slab(&mut other.memory_backend, &slab_info_ref);
+            self.push_full_slab(slab_info_ref);
+        }
+        while let Some(slab_info_ref) = other.free_slabs_list_occupacy_more_75.pop_front() {
+            self.adopt_slab(&mut other.memory_backend, &slab_info_ref);
+            self.free_slabs_list_occupacy_more_75
+                .push_back(slab_info_ref);
+        }
+        while let Some(slab_info_ref) = other.free_slabs_list_occupacy_less_75.pop_front() {
+            self.adopt_slab(&mut other.memory_backend, &slab_info_ref);
+            let slab_info_ptr = UnsafeRef::into_raw(slab_info_ref.clone());
+            self.free_slabs_list_occupacy_less_75
+                .push_front(slab_info_ref);
+            if self.config.distribution_policy == DistributionPolicy::Concentrate {
+                self.sort_slab_in_free_less_75_list(slab_info_ptr);
+            }
+        }
+
+        // Reserve of other is added to the reserve of this cache
+        while let Some(slab_info_ref) = other.emergency_slabs_list.pop_front() {
+            self.adopt_slab(&mut other.memory_backend, &slab_info_ref);
+            self.emergency_slabs_list.push_back(slab_info_ref);
+            self.emergency_slabs_number += 1;
+        }
+        self.emergency_reserve_slabs += other.emergency_reserve_slabs;
+        other.emergency_slabs_number = 0;
+        other.emergency_reserve_slabs = 0;
+
+        statistics_add!(
+            self.statistics,
+            free_slabs_number,
+            other.statistics.free_slabs_number
+        );
+        statistics_add!(
+            self.statistics,
+            full_slabs_number,
+            other.statistics.full_slabs_number
+        );
+        statistics_add!(
+            self.statistics,
+            free_objects_number,
+            other.statistics.free_objects_number
+        );
+        statistics_add!(
+            self.statistics,
+            allocated_objects_number,
+            other.statistics.allocated_objects_number
+        );
+    }
+
+    /// Makes slab of another cache belong to this cache
+    unsafe fn adopt_slab(&mut self, other_memory_backend: &mut M, slab_info: &SlabInfo) {
+        let slab_info_data = &mut *slab_info.data.get();
+        slab_info_data.cache_id = self.id;
+        // Stamp of another cache means nothing here
+        slab_info_data.last_used_operation = self.operations_number;
+
+        if !(self.object_size_type == ObjectSizeType::Small && self.slab_size == self.page_size) {
+            let slab_info_ptr = slab_info as *const SlabInfo as *mut SlabInfo;
+            let slab_addr = slab_info_data.slab_ptr as usize;
+            for i in 0..(self.slab_size / self.page_size) {
+                let page_addr = slab_addr + (i * self.page_size);
+                // Delete first, backends may share the storage
+                other_memory_backend.delete_slab_info_ptr(page_addr);
+                self.memory_backend
+                    .save_slab_info_ptr(page_addr, slab_info_ptr);
+                self.backend_statistics.save_calls += 1;
+            }
+        }
+    }
+
+    /// Returns object to cache
+    ///
+    /// # Safety
+    /// Pointer must be a previously allocated pointer from the same cache
+    pub unsafe fn free(&mut self, object_ptr: *mut T) {
+        self.free_reporting(object_ptr);
+    }
+
+    /// Same as [Cache::free()], also reports whether the object's slab was released and given back to memory backend
+    ///
+    /// Allows to act on slab memory which is no longer used by the cache (e.g. flush TLB for its pages).
+    ///
+    /// # Safety
+    /// Pointer must be a previously allocated pointer from the same cache
+    pub unsafe fn free_reporting(&mut self, object_ptr: *mut T) -> FreeOutcome {
+        assert!(!object_ptr.is_null(), "Try to free null ptr");
+        assert!(
+            object_ptr.is_aligned(),
+            "Try to free null ptr (aligned pointer has been allocated)"
+        );
+
+        // Calculate/Get slab_addr and slab_info_addr
+        let (slab_addr, slab_info_addr) = self.find_slab(object_ptr as usize);
+        let returned_to_backend =
+            self.free_in_slab(object_ptr, slab_addr, slab_info_addr, self.config.reclaim);
+        FreeOutcome {
+            slab_released: returned_to_backend.is_some(),
+            returned_to_backend: returned_to_backend.unwrap_or(false),
+            released_slab_ptr: returned_to_backend.map(|_| slab_addr as *const u8),
+        }
+    }
+
+    /// Same as [Cache::free()] for pointer given by [Cache::alloc_uninit()]
+    ///
+    /// Object is not dropped, even if it was initialized.
+    ///
+    /// # Safety
+    /// Pointer must be a previously allocated pointer from the same cache
+    pub unsafe fn free_uninit(&mut self, object_ptr: *mut MaybeUninit<T>) {
+        self.free(object_ptr.cast());
+    }
+
+    /// Returns object allocated by [Cache::alloc_tagged()] to cache
+    ///
+    /// Objects don't store their site, so it must be the same site as in [Cache::alloc_tagged()].
+    ///
+    /// # Safety
+    /// Pointer must be a previously allocated pointer from the same cache
+    #[cfg(feature = "alloc_sites")]
+    pub unsafe fn free_tagged(&mut self, object_ptr: *mut T, site: u32) {
+        self.free(object_ptr);
+        self.alloc_sites.decrement(site);
+    }
+
+    /// Returns objects to cache
+    ///
+    /// Consecutive objects of the same slab use the SlabInfo found for the first of them, without calculating/getting it again.<br>
+    /// With [CacheConfig::sorted_batch_reclaim] slabs emptied by the batch are freed after it in address order.
+    ///
+    /// # Safety
+    /// Pointers must be previously allocated pointers from the same cache
+    pub unsafe fn free_iter(&mut self, object_ptrs: impl IntoIterator<Item = *mut T>) {
+        let batch_reclaim = self.config.reclaim && self.config.sorted_batch_reclaim;
+        let batch_start_operation = self.operations_number;
+        let mut last_slab: Option<(usize, usize)> = None;
+        for object_ptr in object_ptrs {
+            assert!(!object_ptr.is_null(), "Try to free null ptr");
+            assert!(
+                object_ptr.is_aligned(),
+                "Try to free null ptr (aligned pointer has been allocated)"
+            );
+
+            let object_addr = object_ptr as usize;
+            let (slab_addr, slab_info_addr) = match last_slab {
+                Some((slab_addr, slab_info_addr))
+                    if object_addr >= slab_addr && object_addr < slab_addr + self.slab_size =>
+                {
+                    (slab_addr, slab_info_addr)
+                }
+                _ => self.find_slab(object_addr),
+            };
+            last_slab = if self
+                .free_in_slab(
+                    object_ptr,
+                    slab_addr,
+                    slab_info_addr,
+                    self.config.reclaim && !batch_reclaim,
+                )
+                .is_some()
+            {
+                None
+            } else {
+                Some((slab_addr, slab_info_addr))
+            };
+        }
+        if batch_reclaim {
+            // Slabs emptied by the batch have been used after its start
+            self.free_empty_slabs_where(
+                |slab_info_data| slab_info_data.last_used_operation > batch_start_operation,
+                true,
+            );
+        }
+    }
+
+    /// Frees all objects of the slab and returns the slab to the memory backend
+    ///
+    /// Much faster than freeing objects of the slab one by one, objects memory is not touched.<br>
+    /// Slab is searched in the slab lists, memory backend is not asked for its SlabInfo.<br>
+    /// Slab is freed even if [CacheConfig::reclaim] is not set.<br>
+    /// Emergency reserve slab can be freed too, the reserve is refilled on the next slow path allocation.
+    ///
+    /// # Safety
+    /// slab_ptr must be a slab of this cache (see [Cache::slab_base_addresses()]) and all its allocated objects must be dead
+    pub unsafe fn free_whole_slab(&mut self, slab_ptr: *const u8) {
+        assert!(!slab_ptr.is_null(), "Try to free null slab");
+        // SlabInfo ptr is saved only for pages objects were allocated from, so the slab is searched in the lists
+        // None is the emergency reserve
+        let mut found_slab = None;
+        for (list_kind, slabs_list) in [
+            (Some(SlabListKind::Full), &self.full_slabs_list),
+            (
+                Some(SlabListKind::FreeMore75),
+                &self.free_slabs_list_occupacy_more_75,
+            ),
+            (
+                Some(SlabListKind::FreeLess75),
+                &self.free_slabs_list_occupacy_less_75,
+            ),
+            (None, &self.emergency_slabs_list),
+        ] {
+            if let Some(slab_info) = slabs_list
+                .iter()
+                .find(|slab_info| (*slab_info.data.get()).slab_ptr.cast_const() == slab_ptr)
+            {
+                found_slab = Some((list_kind, slab_info as *const SlabInfo));
+                break;
+            }
+        }
+        let (list_kind, slab_info_ptr) =
+            found_slab.expect("Attempting to free a pointer which is not the start of a slab.");
+        Self::assert_slab_info_canary(slab_info_ptr);
+        let slab_info_data = &*(*slab_info_ptr).data.get();
+        assert_eq!(
+            slab_info_data.cache_id, self.id,
+            "It was not possible to verify that the slab belongs to the cache."
+        );
+        let free_objects_number = slab_info_data.free_objects_number;
+        let allocated_objects_number = self.objects_per_slab - free_objects_number;
+
+        // Remove SlabInfo from its list
+        let slabs_list = match list_kind {
+            Some(SlabListKind::Full) => &mut self.full_slabs_list,
+            Some(SlabListKind::FreeMore75) => &mut self.free_slabs_list_occupacy_more_75,
+            Some(SlabListKind::FreeLess75) => &mut self.free_slabs_list_occupacy_less_75,
+            None => &mut self.emergency_slabs_list,
+        };
+        assert!(slabs_list
+            .cursor_mut_from_ptr(slab_info_ptr)
+            .remove()
+            .is_some());
+        let slab_info_ptr = slab_info_ptr.cast_mut();
+        match list_kind {
+            Some(SlabListKind::Full) => {
+                statistics_sub!(self.statistics, full_slabs_number, 1);
+            }
+            Some(_) => {
+                statistics_sub!(self.statistics, free_slabs_number, 1);
+            }
+            None => {
+                // Reserve slab is empty and not counted in statistics, the reserve is refilled later
+                self.emergency_slabs_number -= 1;
+                self.release_slab(slab_info_ptr);
+                return;
+            }
+        }
+        statistics_sub!(self.statistics, free_objects_number, free_objects_number);
+        statistics_sub!(
+            self.statistics,
+            allocated_objects_number,
+            allocated_objects_number
+        );
+
+        self.release_slab(slab_info_ptr);
+        self.record_statistics();
+    }
+
+    /// Returns object to its slab
+    ///
+    /// Empty slab is released if reclaim is set, returns Some if the slab was released.<br>
+    /// It contains whether the slab memory was returned to the memory backend, see [Cache::release_slab()].
+    unsafe fn free_in_slab(
+        &mut self,
+        object_ptr: *mut T,
+        slab_addr: usize,
+        slab_info_addr: usize,
+        reclaim: bool,
+    ) -> Option<bool> {
+        // Return object to slab
+        let slab_info_ptr = slab_info_addr as *mut SlabInfo;
+        let slab_info_ref = UnsafeRef::from_raw(slab_info_ptr);
+
+        // Check cache
+        Self::assert_slab_info_canary(slab_info_ptr);
+        assert_eq!((*slab_info_ref.data.get()).cache_id, self.id, "It was not possible to verify that the object belongs to the cache. It looks like you try free an invalid address or an object of another cache.");
+        assert!(
+            (object_ptr as usize)
+                .checked_sub(slab_addr + self.objects_offset)
+                .is_some_and(
+                    |object_offset| object_offset.is_multiple_of(self.object_size)
+                        && object_offset / self.object_size < self.objects_per_slab
+                ),
+            "Attempting to free a pointer which is not the start of an object."
+        );
+        assert_ne!((*slab_info_ref.data.get()).free_objects_number, self.objects_per_slab, "Attempting to free an unallocated object! There are no allocated objects in this slab. It looks like invalid address or double free.");
+        self.check_object_not_free(&*slab_info_ref.data.get(), object_ptr.cast());
+
+        // Object is zeroed only after it is checked, invalid free must not touch memory
+        if self.config.zero_objects {
+            object_ptr.cast::<u8>().write_bytes(0, self.object_size);
+        }
+
+        // Add object to free list
+        self.push_free_object(&mut *slab_info_ref.data.get(), object_ptr.cast());
+        (*slab_info_ref.data.get()).free_objects_number += 1;
+        statistics_add!(self.statistics, free_objects_number, 1);
+        statistics_sub!(self.statistics, allocated_objects_number, 1);
+        self.operations_number += 1;
+        (*slab_info_ref.data.get()).last_used_operation = self.operations_number;
+
+        // Slab become free? (full -> free (>75))
+        if (*slab_info_ref.data.get()).free_objects_number == 1 {
+            // Move slab info from full list to free
+            self.check_slab_info_linked(slab_info_ptr);
+            let mut slab_info_full_list_cursor =
+                self.full_slabs_list.cursor_mut_from_ptr(slab_info_ptr);
+            statistics_sub!(self.statistics, full_slabs_number, 1);
+            assert!(slab_info_full_list_cursor.remove().is_some());
+
+            // Add slab to free list
+            let (free_slabs_list, slab_list_kind) = if !self.has_free_more_75_list() {
+                (
+                    &mut self.free_slabs_list_occupacy_less_75,
+                    SlabListKind::FreeLess75,
+                )
+            } else {
+                (
+                    &mut self.free_slabs_list_occupacy_more_75,
+                    SlabListKind::FreeMore75,
+                )
+            };
+            free_slabs_list.push_front(slab_info_ref.clone());
+            statistics_add!(self.statistics, free_slabs_number, 1);
+            self.notify_transition(
+                (*slab_info_ptr).data.get_mut().slab_ptr,
+                SlabListKind::Full,
+                slab_list_kind,
+            );
+        }
+
+        // Slab occupacy become less than 75? (free (>75) -> free (<75))
+        let allocated_objects_number =
+            self.objects_per_slab - (*slab_info_ref.data.get()).free_objects_number;
+        let previously_was_in_more_75_list = self.has_free_more_75_list()
+            && allocated_objects_number + 1
+                >= self.occupacy_more_75_minimum_allocated_objects_number;
+        let now_in_less_75_list =
+            allocated_objects_number < self.occupacy_more_75_minimum_allocated_objects_number;
+        if previously_was_in_more_75_list && now_in_less_75_list {
+            // Move slab info from free (>75) to free (<75)
+            self.check_slab_info_linked(slab_info_ptr);
+            let mut slab_info_free_more_75_list_cursor = self
+                .free_slabs_list_occupacy_more_75
+                .cursor_mut_from_ptr(slab_info_ptr);
+            assert!(slab_info_free_more_75_list_cursor.remove().is_some());
+
+            // Add to free (<75)
+            // It has the maximum occupancy in the list, so the list stays sorted
+            self.free_slabs_list_occupacy_less_75
+                .push_front(UnsafeRef::from_raw(slab_info_ptr));
+            self.notify_transition(
+                (*slab_info_ptr).data.get_mut().slab_ptr,
+                SlabListKind::FreeMore75,
+                SlabListKind::FreeLess75,
+            );
+        } else if now_in_less_75_list
+            && (allocated_objects_number != 0 || !reclaim)
+            && self.config.distribution_policy == DistributionPolicy::Concentrate
+        {
+            // Slab occupancy decreased inside free (<75), keep it sorted
+            self.sort_slab_in_free_less_75_list(slab_info_ptr);
+        }
+
+        // List becomes empty?
+        if reclaim && (*slab_info_ptr).data.get_mut().free_objects_number == self.objects_per_slab {
+            // All objects in slab is free - free slab
+            // Remove SlabInfo from free list
+            self.check_slab_info_linked(slab_info_ptr);
+            let mut slab_info_free_list_cursor = self
+                .free_slabs_list_occupacy_less_75
+                .cursor_mut_from_ptr(slab_info_ptr);
+            assert!(slab_info_free_list_cursor.remove().is_some());
+            statistics_sub!(self.statistics, free_slabs_number, 1);
+            statistics_sub!(self.statistics, free_objects_number, self.objects_per_slab);
+
+            check!(
+                self,
+                AssertLevel::Normal,
+                (*slab_info_ptr).data.get_mut().slab_ptr as usize == slab_addr
+            );
+            let returned_to_backend = self.release_slab(slab_info_ptr);
+            self.record_statistics();
+            self.check_paranoid();
+            return Some(returned_to_backend);
+        }
+        self.record_statistics();
+        self.check_paranoid();
+        None
+    }
+
+    /// Adds current statistics to the history, see [Cache::stats_history()]
+    fn record_statistics(&mut self) {
+        #[cfg(feature = "stats_history")]
+        self.stats_history.push(self.statistics);
+    }
+
+    /// Checks that slab info is linked before it is looked up by cursor in some list
+    ///
+    /// Turns lists accounting bugs into panic, [AssertLevel::Normal] check.
+    unsafe fn check_slab_info_linked(&self, slab_info_ptr: *const SlabInfo) {
+        check!(
+            self,
+            AssertLevel::Normal,
+            (*slab_info_ptr).slab_link.is_linked(),
+            "SlabInfo is not linked into any list, lists accounting is broken"
+        );
+    }
+
+    /// Checks the whole cache and memory backend, [AssertLevel::Paranoid] check done after every alloc/free
+    ///
+    /// Calls [MemoryBackend::get_slab_info_ptr()] for every page with allocated objects, see [Cache::self_check_backend()].
+    unsafe fn check_paranoid(&mut self) {
+        if !self.config.assert_level.enables(AssertLevel::Paranoid) {
+            return;
+        }
+        if let Err(error) = self.check_invariants() {
+            panic!("Cache check failed: {error}");
+        }
+        if let Err(error) = self.self_check_backend() {
+            panic!("Memory backend check failed: {error}");
+        }
+    }
+
+    /// Checks that SlabInfo was not overwritten
+    ///
+    /// Panics instead of following corrupted free objects list or slab links.
+    unsafe fn assert_slab_info_canary(slab_info_ptr: *const SlabInfo) {
+        assert_eq!(
+            (*(*slab_info_ptr).data.get()).canary,
+            SLAB_INFO_CANARY,
+            "SlabInfo canary is corrupted, SlabInfo at {slab_info_ptr:p} was overwritten. It looks like buffer overrun of an object."
+        );
+    }
+
+    /// Calls [CacheConfig::on_transition] if it is set
+    fn notify_transition(&self, slab_ptr: *mut u8, from: SlabListKind, to: SlabListKind) {
+        if let Some(on_transition) = self.config.on_transition {
+            on_transition(slab_ptr, from, to);
+        }
+    }
+
+    /// Adds slab to full list, in slab address order if [CacheConfig::sort_full_slabs] is set
+    unsafe fn push_full_slab(&mut self, slab_info_ref: UnsafeRef<SlabInfo>) {
+        if !self.config.sort_full_slabs {
+            self.full_slabs_list.push_back(slab_info_ref);
+            return;
+        }
+        let slab_ptr = (*slab_info_ref.data.get()).slab_ptr;
+        let mut cursor = self.full_slabs_list.front_mut();
+        while cursor
+            .get()
+            .is_some_and(|slab_info| (*slab_info.data.get()).slab_ptr < slab_ptr)
+        {
+            cursor.move_next();
+        }
+        // Inserts at the back if the cursor is null
+        cursor.insert_before(slab_info_ref);
+    }
+
+    /// Moves slab back in free (<75) list after its occupancy has decreased
+    ///
+    /// Slab is moved behind all slabs which are more occupied than it, so the list stays sorted by occupancy (most occupied at front).<br>
+    /// Only slabs with the same previous occupancy need to be skipped, usually it is a few steps.
+    unsafe fn sort_slab_in_free_less_75_list(&mut self, slab_info_ptr: *mut SlabInfo) {
+        let allocated_objects_number =
+            self.objects_per_slab - (*(*slab_info_ptr).data.get()).free_objects_number;
+        let is_more_occupied = |slab_info: &SlabInfo| {
+            self.objects_per_slab - (*slab_info.data.get()).free_objects_number
+                > allocated_objects_number
+        };
+
+        self.check_slab_info_linked(slab_info_ptr);
+        let mut cursor = self
+            .free_slabs_list_occupacy_less_75
+            .cursor_mut_from_ptr(slab_info_ptr);
+        if !cursor.peek_next().get().is_some_and(is_more_occupied) {
+            // Already in place
+            return;
+        }
+        let slab_info_ref = cursor.remove().unwrap();
+        while cursor.get().is_some_and(is_more_occupied) {
+            cursor.move_next();
+        }
+        // Inserts at the back if the cursor is null
+        cursor.insert_before(slab_info_ref);
+    }
+
+    /// Calls f for every free object in the cache
+    ///
+    /// Can be used to scrub free objects memory, e.g. before slabs are freed.
+    ///
+    /// # Safety
+    /// The first two pointers (8/16 bytes) of a free object are used by the cache as free list links, f must not modify them
+    /// (the first 4 bytes with [CacheConfig::offset_free_links]).<br>
+    /// The rest of the object memory may be modified freely. f must not call the cache.
+    pub unsafe fn for_each_free_slot(&self, mut f: impl FnMut(*mut T)) {
+        for slab_info in self
+            .free_slabs_list_occupacy_less_75
+            .iter()
+            .chain(self.free_slabs_list_occupacy_more_75.iter())
+        {
+            let slab_info_data = &*slab_info.data.get();
+            let mut free_object_ptr = self.next_free_object(slab_info_data, null());
+            while !free_object_ptr.is_null() {
+                f(free_object_ptr as *mut T);
+                free_object_ptr = self.next_free_object(slab_info_data, free_object_ptr);
+            }
+            for uncarved_object_index in 0..slab_info_data.uncarved_objects_number {
+                f(self
+                    .object_ptr(slab_info_data.slab_ptr, uncarved_object_index)
+                    .cast());
+            }
+        }
+    }
+
+    /// Iterates over all slabs of the cache, yields (slab addr, slab size)
+    ///
+    /// Only SlabInfo's are read, objects memory is not touched.
+    pub fn slab_base_addresses(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.free_slabs_list_occupacy_less_75
+            .iter()
+            .chain(self.free_slabs_list_occupacy_more_75.iter())
+            .chain(self.full_slabs_list.iter())
+            .chain(self.emergency_slabs_list.iter())
+            .map(|slab_info| {
+                let slab_addr = unsafe { (*slab_info.data.get()).slab_ptr as usize };
+                (slab_addr, self.slab_size)
+            })
+    }
+
+    /// Iterates over slabs with both free and allocated objects, the most free first
+    ///
+    /// Allows to relocate live objects out of the sparse slabs, so they become empty and can be freed.<br>
+    /// Only SlabInfo's are read. Free lists are walked once for each number of free objects, so it is O(objects_per_slab * slabs).
+    pub fn partial_slabs_by_emptiness(&self) -> impl Iterator<Item = SlabStats> + '_ {
+        (1..self.objects_per_slab)
+            .rev()
+            .flat_map(move |free_objects_number| {
+                self.free_slabs_list_occupacy_less_75
+                    .iter()
+                    .chain(self.free_slabs_list_occupacy_more_75.iter())
+                    .map(|slab_info| unsafe { &*slab_info.data.get() })
+                    .filter(move |slab_info_data| {
+                        slab_info_data.free_objects_number == free_objects_number
+                    })
+                    .map(|slab_info_data| SlabStats {
+                        slab_addr: slab_info_data.slab_ptr as usize,
+                        free_objects_number: slab_info_data.free_objects_number,
+                        allocated_objects_number: self.objects_per_slab
+                            - slab_info_data.free_objects_number,
+                    })
+            })
+    }
+
+    /// Writes slabinfo-like report: one line per slab and summary line
+    ///
+    /// Slab line: slab addr, allocated/total objects and list of the slab.<br>
+    /// Summary line: statistics and fragmentation (percent of free objects in allocated slabs).
+    pub fn dump(&self, w: &mut impl core::fmt::Write) -> core::fmt::Result {
+        for (slab_list_kind, slabs_list) in [
+            (SlabListKind::Full, &self.full_slabs_list),
+            (
+                SlabListKind::FreeMore75,
+                &self.free_slabs_list_occupacy_more_75,
+            ),
+            (
+                SlabListKind::FreeLess75,
+                &self.free_slabs_list_occupacy_less_75,
+            ),
+        ] {
+            for slab_info in slabs_list.iter() {
+                let slab_info_data = unsafe { &*slab_info.data.get() };
+                writeln!(
+                    w,
+                    "slab {:#x}: {}/{} objects ({:?})",
+                    slab_info_data.slab_ptr as usize,
+                    self.objects_per_slab - slab_info_data.free_objects_number,
+                    self.objects_per_slab,
+                    slab_list_kind
+                )?;
+            }
+        }
+        let capacity = self.capacity();
+        let fragmentation_percent = (self.statistics.free_objects_number * 100)
+            .checked_div(capacity)
+            .unwrap_or(0);
+        writeln!(
+            w,
+            "total: object size {}, slab size {}, slabs {} free {} full, objects {}/{}, fragmentation {}%",
+            self.object_size,
+            self.slab_size,
+            self.statistics.free_slabs_number,
+            self.statistics.full_slabs_number,
+            self.statistics.allocated_objects_number,
+            capacity,
+            fragmentation_percent
+        )
+    }
+
+    /// Gets number of free objects in the slab of the object
+    ///
+    /// # Safety
+    /// Pointer must be a previously allocated pointer from the same cache
+    pub unsafe fn slab_free_count(&mut self, object_ptr: *const T) -> usize {
+        assert!(!object_ptr.is_null(), "Try to get slab of null ptr");
+        let (_, slab_info_addr) = self.find_slab(object_ptr as usize);
+        let slab_info_data = &*(*(slab_info_addr as *const SlabInfo)).data.get();
+        assert_eq!(
+            slab_info_data.cache_id, self.id,
+            "It was not possible to verify that the object belongs to the cache."
+        );
+        slab_info_data.free_objects_number
+    }
+
+    /// Checks free objects lists of all slabs
+    ///
+    /// For each slab walks its free objects list and checks that the number of nodes is equal to the number of free objects
+    /// and that all nodes are objects of the slab.<br>
+    /// Returns error instead of looping or dereferencing dangling pointer if the list is corrupted.
+    ///
+    /// # Safety
+    /// Cache must not be used concurrently
+    pub unsafe fn verify_free_lists(&self) -> Result<(), &'static str> {
+        for slab_info in self
+            .free_slabs_list_occupacy_less_75
+            .iter()
+            .chain(self.free_slabs_list_occupacy_more_75.iter())
+            .chain(self.full_slabs_list.iter())
+            .chain(self.emergency_slabs_list.iter())
+        {
+            let slab_info_data = &*slab_info.data.get();
+            if slab_info_data.canary != SLAB_INFO_CANARY {
+                return Err("SlabInfo canary is corrupted");
+            }
+            if slab_info_data.cache_id != self.id {
+                return Err("Slab belongs to another cache");
+            }
+            let objects_addr = self.object_ptr(slab_info_data.slab_ptr, 0) as usize;
+            // Not carved objects are not in the list
+            let carved_objects_addr = self.object_ptr(
+                slab_info_data.slab_ptr,
+                slab_info_data.uncarved_objects_number,
+            ) as usize;
+            let carved_objects_number =
+                slab_info_data.free_objects_number - slab_info_data.uncarved_objects_number;
+            let objects_end_addr =
+                self.object_ptr(slab_info_data.slab_ptr, self.objects_per_slab) as usize;
+
+            let mut nodes_number = 0;
+            let mut free_object_ptr = self.next_free_object(slab_info_data, null());
+            while !free_object_ptr.is_null() {
+                let free_object_addr = free_object_ptr as usize;
+                if free_object_addr < carved_objects_addr || free_object_addr >= objects_end_addr {
+                    return Err("Free object is outside of the slab");
+                }
+                if !(free_object_addr - objects_addr).is_multiple_of(self.object_size) {
+                    return Err("Free object is not aligned to object size");
+                }
+                nodes_number += 1;
+                if nodes_number > carved_objects_number {
+                    // Also stops walking a looped list
+                    return Err("Free objects list is longer than free objects number");
+                }
+                free_object_ptr = self.next_free_object(slab_info_data, free_object_ptr);
+            }
+            if nodes_number != carved_objects_number {
+                return Err("Free objects list is shorter than free objects number");
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks that memory backend gives back the saved SlabInfo ptrs
+    ///
+    /// For every page with allocated objects of every slab, gets SlabInfo ptr by [MemoryBackend::get_slab_info_ptr()] and compares it with the slab's SlabInfo.<br>
+    /// Pages without allocated objects may have no saved ptr, they are skipped. [CacheConfig::slab_info_lookup_cache] is bypassed.<br>
+    /// Intended for bring-up of a new memory backend, walks free objects lists for every object. Does nothing if SlabInfo ptrs are not saved.
+    ///
+    /// # Safety
+    /// Calls memory backend
+    pub unsafe fn self_check_backend(&mut self) -> Result<(), &'static str> {
+        if self.object_size_type == ObjectSizeType::Small && self.slab_size == self.page_size {
+            return Ok(());
+        }
+        for slab_info in self
+            .free_slabs_list_occupacy_less_75
+            .iter()
+            .chain(self.free_slabs_list_occupacy_more_75.iter())
+            .chain(self.full_slabs_list.iter())
+        {
+            let slab_info_data = &*slab_info.data.get();
+            for page_index in 0..self.slab_size / self.page_size {
+                let page_addr = slab_info_data.slab_ptr as usize + page_index * self.page_size;
+                if !self.page_has_allocated_objects(slab_info_data, page_addr) {
+                    continue;
+                }
+                let slab_info_ptr = self.memory_backend.get_slab_info_ptr(page_addr);
+                self.backend_statistics.get_calls += 1;
+                if slab_info_ptr.is_null() {
+                    return Err(
+                        "Memory backend returns null SlabInfo ptr for page with allocated objects",
+                    );
+                }
+                if !core::ptr::eq(slab_info_ptr, slab_info) {
+                    return Err("Memory backend returns SlabInfo ptr of another slab");
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks whether some allocated object of the slab starts in the page
+    ///
+    /// SlabInfo ptrs are saved for such pages. Walks free objects list for every object of the page.
+    unsafe fn page_has_allocated_objects(
+        &self,
+        slab_info_data: &SlabInfoData,
+        page_addr: usize,
+    ) -> bool {
+        // Objects with index < uncarved_objects_number have never been allocated
+        (slab_info_data.uncarved_objects_number..self.objects_per_slab)
+            .map(|object_index| self.object_ptr(slab_info_data.slab_ptr, object_index))
+            .filter(|&object_ptr| align_down(object_ptr as usize, self.page_size) == page_addr)
+            .any(|object_ptr| {
+                let mut free_object_ptr = self.next_free_object(slab_info_data, null());
+                while !free_object_ptr.is_null() && free_object_ptr != object_ptr.cast_const() {
+                    free_object_ptr = self.next_free_object(slab_info_data, free_object_ptr);
+                }
+                free_object_ptr.is_null()
+            })
+    }
+
+    /// Checks free objects lists, slab lists and statistics
+    unsafe fn check_invariants(&self) -> Result<(), &'static str> {
+        self.verify_free_lists()?;
+
+        let mut free_objects_number = 0;
+        let mut free_slabs_number = 0;
+        for slab_info in self.free_slabs_list_occupacy_less_75.iter() {
+            let slab_info_data = &*slab_info.data.get();
+            if slab_info_data.free_objects_number == 0
+                || self.objects_per_slab - slab_info_data.free_objects_number
+                    >= self.occupacy_more_75_minimum_allocated_objects_number
+            {
+                return Err("Slab in free (<75) list has wrong occupancy");
+            }
+            free_objects_number += slab_info_data.free_objects_number;
+            free_slabs_number += 1;
+        }
+        for slab_info in self.free_slabs_list_occupacy_more_75.iter() {
+            let slab_info_data = &*slab_info.data.get();
+            if slab_info_data.free_objects_number == 0
+                || self.objects_per_slab - slab_info_data.free_objects_number
+                    < self.occupacy_more_75_minimum_allocated_objects_number
+            {
+                return Err("Slab in free (>75) list has wrong occupancy");
+            }
+            free_objects_number += slab_info_data.free_objects_number;
+            free_slabs_number += 1;
+        }
+        let mut full_slabs_number = 0;
+        for slab_info in self.full_slabs_list.iter() {
+            if (*slab_info.data.get()).free_objects_number != 0 {
+                return Err("Slab in full list has free objects");
+            }
+            full_slabs_number += 1;
+        }
+
+        if free_slabs_number != self.statistics.free_slabs_number
+            || full_slabs_number != self.statistics.full_slabs_number
+        {
+            return Err("Slabs number doesn't match statistics");
+        }
+        if free_objects_number != self.statistics.free_objects_number {
+            return Err("Free objects number doesn't match statistics");
+        }
+        if (free_slabs_number + full_slabs_number) * self.objects_per_slab
+            != free_objects_number + self.statistics.allocated_objects_number
+        {
+            return Err("Allocated objects number doesn't match statistics");
+        }
+        Ok(())
+    }
+
+    /// Allocs and frees objects in a mixed pseudo-random pattern and checks the cache after each step
+    ///
+    /// Deterministic soak test which can be run on a real device, without rand or std.<br>
+    /// Allocated objects are filled with a pattern which is checked before free, this catches objects given twice.<br>
+    /// All objects allocated by the test are freed before return. Returns error on the first inconsistency or failed allocation.
+    ///
+    /// # Safety
+    /// Calls memory backend, object memory is overwritten
+    pub unsafe fn self_test(&mut self, iterations: usize) -> Result<(), &'static str> {
+        /// Maximum number of objects allocated by the test at the same time
+        const SELF_TEST_OBJECTS: usize = 64;
+
+        let mut allocated_ptrs = [null_mut::<u8>(); SELF_TEST_OBJECTS];
+        let mut allocated_ptrs_number = 0;
+        // Knuth's MMIX LCG
+        let mut lcg_state: u64 = 0x853c_49e6_748f_ea9b;
+        let mut result = self.check_invariants();
+        for _ in 0..iterations {
+            if result.is_err() {
+                break;
+            }
+            lcg_state = lcg_state
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            let random = (lcg_state >> 33) as usize;
+
+            // Alloc more often than free while there are few allocated objects
+            let alloc = allocated_ptrs_number == 0
+                || (allocated_ptrs_number < SELF_TEST_OBJECTS
+                    && random % SELF_TEST_OBJECTS >= allocated_ptrs_number / 2);
+            if alloc {
+                let allocated_ptr = self.alloc().cast::<u8>();
+                if allocated_ptr.is_null() {
+                    result = Err("Failed to allocate object");
+                    break;
+                }
+                allocated_ptr.write_bytes(allocated_ptr as usize as u8, self.object_size);
+                allocated_ptrs[allocated_ptrs_number] = allocated_ptr;
+                allocated_ptrs_number += 1;
+            } else {
+                let index = (random >> 8) % allocated_ptrs_number;
+                let allocated_ptr = allocated_ptrs[index];
+                let pattern = allocated_ptr as usize as u8;
+                if (0..self.object_size).any(|i| allocated_ptr.add(i).read() != pattern) {
+                    result = Err("Object memory was overwritten, object was given twice");
+                    break;
+                }
+                allocated_ptrs_number -= 1;
+                allocated_ptrs[index] = allocated_ptrs[allocated_ptrs_number];
+                self.free(allocated_ptr.cast());
+            }
+            result = self.check_invariants();
+        }
+
+        for allocated_ptr in &allocated_ptrs[..allocated_ptrs_number] {
+            self.free(allocated_ptr.cast());
+        }
+        result?;
+        self.check_invariants()
+    }
+
+    /// Calculates/Gets slab addr and SlabInfo addr of the object
+    unsafe fn find_slab(&mut self, object_addr: usize) -> (usize, usize) {
+        if self.object_size_type == ObjectSizeType::Small && self.slab_size == self.page_size {
+            // In this case we may calculate slab info addr
+            let slab_addr = align_down(object_addr, self.page_size);
+            let slab_info_addr = calculate_slab_info_addr_in_small_object_cache(
+                slab_addr as *mut u8,
+                self.slab_size,
+                self.config.small_info_placement,
+            );
+            assert_ne!(slab_addr, 0);
+            assert_ne!(slab_info_addr, 0);
+            check!(self, AssertLevel::Normal, slab_info_addr >= slab_addr);
+            check!(
+                self,
+                AssertLevel::Normal,
+                slab_info_addr <= slab_addr + self.slab_size - size_of::<SlabInfo>()
+            );
+            assert_eq!(slab_info_addr % align_of::<SlabInfo>(), 0);
+            (slab_addr, slab_info_addr)
+        } else {
+            let object_page_addr = align_down(object_addr, self.page_size);
+            let slab_info_ptr = match self.slab_info_lookup_cache.get(object_page_addr) {
+                Some(slab_info_ptr) => slab_info_ptr,
+                None => {
+                    // Get slab info addr from memory backend
+                    let slab_info_ptr = self.memory_backend.get_slab_info_ptr(object_page_addr);
+                    self.backend_statistics.get_calls += 1;
+                    if self.config.slab_info_lookup_cache {
+                        self.slab_info_lookup_cache
+                            .insert(object_page_addr, slab_info_ptr);
+                    }
+                    slab_info_ptr
+                }
+            };
+            assert!(!slab_info_ptr.is_null());
+            assert!(slab_info_ptr.is_aligned());
+            let slab_ptr = (*(*slab_info_ptr).data.get()).slab_ptr;
+            assert!(!slab_ptr.is_null());
+            (slab_ptr as usize, slab_info_ptr as usize)
+        }
+    }
+
+    /// Frees every slab in which all objects are free
+    ///
+    /// Returns the number of freed pages (freed slabs * pages in slab), so it can be reported directly by memory pressure callbacks.<br>
+    /// Only slabs returned to the memory backend are counted, donated slabs are released but not counted (see [Cache::donate_slab()]).
+    ///
+    /// Frees slabs even if [CacheConfig::reclaim] is not set.
+    ///
+    /// # Safety
+    /// Calls memory backend to free slabs
+    pub unsafe fn drain_free_slabs(&mut self) -> usize {
+        let freed_slabs_number = self.free_empty_slabs_where(|_| true, false);
+        freed_slabs_number * (self.slab_size / self.page_size)
+    }
+
+    /// Frees every slab in which all objects are free and which had no alloc/free for at least min_idle_operations cache operations
+    ///
+    /// Age is counted in alloc/free operations of the cache, so a slab which has just become empty and is likely to be reused is kept.<br>
+    /// Intended for a periodic reclaimer with [CacheConfig::reclaim] unset, otherwise slabs are freed as soon as they become empty.<br>
+    /// Returns the number of slabs returned to the memory backend, donated slabs are released but not counted.
+    ///
+    /// # Safety
+    /// Calls memory backend to free slabs
+    pub unsafe fn reap_idle(&mut self, min_idle_operations: u64) -> usize {
+        let operations_number = self.operations_number;
+        self.free_empty_slabs_where(
+            |slab_info_data| {
+                operations_number - slab_info_data.last_used_operation >= min_idle_operations
+            },
+            false,
+        )
+    }
+
+    /// Frees every slab in which all objects are free and whose address satisfies the predicate
+    ///
+    /// Slabs with allocated objects are skipped, see [Cache::slab_base_addresses()] to find them.<br>
+    /// E.g. frees slabs in the physical address range being removed.<br>
+    /// Returns the number of slabs returned to the memory backend, donated slabs are released but not counted.
+    ///
+    /// # Safety
+    /// Calls memory backend to free slabs
+    pub unsafe fn free_slabs_where(&mut self, predicate: impl Fn(*const u8) -> bool) -> usize {
+        self.free_empty_slabs_where(|slab_info_data| predicate(slab_info_data.slab_ptr), false)
+    }
+
+    /// Frees every slab in which all objects are free and which satisfies the predicate
+    ///
+    /// If in_address_order is set, slabs are freed in address order, so memory backend (e.g. buddy allocator) can coalesce adjacent slabs.
+    /// Sorting takes O(n^2) for n freed slabs.<br>
+    /// Returns the number of slabs returned to the memory backend, see [Cache::release_slab()]
+    unsafe fn free_empty_slabs_where(
+        &mut self,
+        mut predicate: impl FnMut(&SlabInfoData) -> bool,
+        in_address_order: bool,
+    ) -> usize {
+        // Move empty slabs to a temporary list first, the memory backend can't be called while the free lists are borrowed
+        let mut empty_slabs_list = LinkedList::new(SlabInfoAdapter::new());
+        for free_slabs_list in [
+            &mut self.free_slabs_list_occupacy_less_75,
+            &mut self.free_slabs_list_occupacy_more_75,
+        ] {
+            let mut cursor = free_slabs_list.front_mut();
+            while let Some(slab_info) = cursor.get() {
+                let slab_info_data = &*slab_info.data.get();
+                if slab_info_data.free_objects_number == self.objects_per_slab
+                    && predicate(slab_info_data)
+                {
+                    let slab_info_ref = cursor.remove().unwrap();
+                    if in_address_order {
+                        let slab_ptr = slab_info_data.slab_ptr;
+                        let mut empty_slabs_cursor = empty_slabs_list.front_mut();
+                        while empty_slabs_cursor
+                            .get()
+                            .is_some_and(|slab_info| (*slab_info.data.get()).slab_ptr < slab_ptr)
+                        {
+                            empty_slabs_cursor.move_next();
+                        }
+                        // Inserts at the back if the cursor is null
+                        empty_slabs_cursor.insert_before(slab_info_ref);
+                    } else {
+                        empty_slabs_list.push_back(slab_info_ref);
+                    }
+                } else {
+                    cursor.move_next();
+                }
+            }
+        }
+
+        let mut freed_slabs_number = 0;
+        while let Some(slab_info_ref) = empty_slabs_list.pop_front() {
+            statistics_sub!(self.statistics, free_slabs_number, 1);
+            statistics_sub!(self.statistics, free_objects_number, self.objects_per_slab);
+            if self.release_slab(UnsafeRef::into_raw(slab_info_ref)) {
+                freed_slabs_number += 1;
+            }
+        }
+        freed_slabs_number
+    }
+
+    /// Moves SlabInfos of [ObjectSizeType::Large] cache to the memory given by [MemoryBackend::relocate_slab_info()]
+    ///
+    /// Allows the memory backend to reduce fragmentation of its SlabInfo memory in long-running systems.<br>
+    /// Lists order is kept and saved SlabInfo ptrs are updated, allocated objects stay valid.<br>
+    /// Does nothing for [ObjectSizeType::Small] cache or if the memory backend doesn't override [MemoryBackend::relocate_slab_info()].
+    ///
+    /// Returns the number of moved SlabInfos
+    ///
+    /// # Safety
+    /// Calls memory backend to move SlabInfos
+    pub unsafe fn compact_slab_infos(&mut self) -> usize {
+        if self.object_size_type != ObjectSizeType::Large {
+            return 0;
+        }
+
+        let mut slabs_lists = [
+            core::mem::replace(
+                &mut self.full_slabs_list,
+                LinkedList::new(SlabInfoAdapter::new()),
+            ),
+            core::mem::replace(
+                &mut self.free_slabs_list_occupacy_more_75,
+                LinkedList::new(SlabInfoAdapter::new()),
+            ),
+            core::mem::replace(
+                &mut self.free_slabs_list_occupacy_less_75,
+                LinkedList::new(SlabInfoAdapter::new()),
+            ),
+            core::mem::replace(
+                &mut self.emergency_slabs_list,
+                LinkedList::new(SlabInfoAdapter::new()),
+            ),
+        ];
+        let mut moved_slab_infos_number = 0;
+        for slabs_list in &mut slabs_lists {
+            let mut relocated_slabs_list = LinkedList::new(SlabInfoAdapter::new());
+            while let Some(slab_info_ref) = slabs_list.pop_front() {
+                let slab_info_ptr = UnsafeRef::into_raw(slab_info_ref);
+                let new_slab_info_ptr = self.relocate_slab_info(slab_info_ptr);
+                if new_slab_info_ptr != slab_info_ptr {
+                    moved_slab_infos_number += 1;
+                }
+                relocated_slabs_list.push_back(UnsafeRef::from_raw(new_slab_info_ptr));
+            }
+            *slabs_list = relocated_slabs_list;
+        }
+        let [full_slabs_list, free_slabs_list_occupacy_more_75, free_slabs_list_occupacy_less_75, emergency_slabs_list] =
+            slabs_lists;
+        self.full_slabs_list = full_slabs_list;
+        self.free_slabs_list_occupacy_more_75 = free_slabs_list_occupacy_more_75;
+        self.free_slabs_list_occupacy_less_75 = free_slabs_list_occupacy_less_75;
+        self.emergency_slabs_list = emergency_slabs_list;
+        moved_slab_infos_number
+    }
+
+    /// Moves unlinked SlabInfo to the memory given by memory backend
+    ///
+    /// Returns the new SlabInfo ptr, or the same ptr if memory backend keeps it in place
+    unsafe fn relocate_slab_info(&mut self, slab_info_ptr: *mut SlabInfo) -> *mut SlabInfo {
+        check!(
+            self,
+            AssertLevel::Normal,
+            !(*slab_info_ptr).slab_link.is_linked()
+        );
+        let new_slab_info_ptr = self.memory_backend.relocate_slab_info(slab_info_ptr);
+        if new_slab_info_ptr.is_null() || new_slab_info_ptr == slab_info_ptr {
+            return slab_info_ptr;
+        }
+        self.backend_statistics.slab_info_allocs += 1;
+        assert!(
+            new_slab_info_ptr.is_aligned(),
+            "Memory backend allocates not aligned SlabInfo"
+        );
+
+        // SlabInfo is not linked, so only its data must be moved
+        // Free objects are linked with each other, not with free objects list itself, so the list can be moved
+        let slab_info_data = core::ptr::read((*slab_info_ptr).data.get());
+        let slab_addr = slab_info_data.slab_ptr as usize;
+        new_slab_info_ptr.write(SlabInfo {
+            slab_link: LinkedListLink::new(),
+            data: UnsafeCell::new(slab_info_data),
+        });
+        self.memory_backend.free_slab_info(slab_info_ptr);
+        self.backend_statistics.slab_info_frees += 1;
+
+        for i in 0..(self.slab_size / self.page_size) {
+            let page_addr = slab_addr + (i * self.page_size);
+            self.slab_info_lookup_cache.invalidate(page_addr);
+            self.memory_backend.delete_slab_info_ptr(page_addr);
+            self.backend_statistics.delete_calls += 1;
+            self.memory_backend
+                .save_slab_info_ptr(page_addr, new_slab_info_ptr);
+            self.backend_statistics.save_calls += 1;
+        }
+        new_slab_info_ptr
+    }
+
+    /// Returns slab memory to the memory backend by [MemoryBackend::try_free_slab()]
+    ///
+    /// Returns false if memory backend failed to free it, the failure is counted in [BackendStatistics::slab_free_failures]
+    unsafe fn free_slab_memory(&mut self, slab_ptr: *mut u8) -> bool {
+        if self
+            .memory_backend
+            .try_free_slab(slab_ptr, self.slab_size, self.page_size)
+            .is_ok()
+        {
+            self.backend_statistics.slab_frees += 1;
+            true
+        } else {
+            self.backend_statistics.slab_free_failures += 1;
+            false
+        }
+    }
+
+    /// Returns slab memory, SlabInfo and saved SlabInfo ptrs to the memory backend
+    ///
+    /// SlabInfo must be already removed from the lists and statistics.<br>
+    /// Returns whether slab memory was returned to the memory backend, the slab is not used by the cache anyway.<br>
+    /// It is false if memory backend failed to free it or the slab is donated, memory of donated slab is not freed, see [Cache::donate_slab()].
+    unsafe fn release_slab(&mut self, slab_info_ptr: *mut SlabInfo) -> bool {
+        // SlabInfo may be stored inside slab, read slab addr before freeing
+        let slab_addr = (*(*slab_info_ptr).data.get()).slab_ptr as usize;
+        let donated = (*(*slab_info_ptr).data.get()).donated;
+        assert_ne!(slab_addr, 0);
+
+        // Free slab memory
+        let slab_freed = !donated && self.free_slab_memory(slab_addr as *mut u8);
+
+        if !(self.object_size_type == ObjectSizeType::Small && self.slab_size == self.page_size) {
+            if self.object_size_type == ObjectSizeType::Large {
+                // Free SlabInfo
+                self.memory_backend.free_slab_info(slab_info_ptr);
+                self.backend_statistics.slab_info_frees += 1;
+            }
+            for i in 0..(self.slab_size / self.page_size) {
+                let page_addr = slab_addr + (i * self.page_size);
+                self.slab_info_lookup_cache.invalidate(page_addr);
+                self.memory_backend.delete_slab_info_ptr(page_addr);
+                self.backend_statistics.delete_calls += 1;
+            }
+        }
+        slab_freed
+    }
+
+    /// Calculates address of the object with index in slab
+    ///
+    /// Objects are placed one after another from the slab start (after SlabInfo for [SmallInfoPlacement::Head]).<br>
+    /// New slab gives objects starting from the last one: index objects_per_slab - 1, then objects_per_slab - 2 and so on.
+    pub fn predicted_slot_addr(&self, slab_ptr: *const u8, index: usize) -> usize {
+        assert!(index < self.objects_per_slab, "Object index out of slab");
+        self.object_ptr(slab_ptr as *mut u8, index) as usize
+    }
+
+    /// Gets placement of objects in slabs, e.g. to encode objects as compact handles
+    pub fn geometry(&self) -> CacheGeometry {
+        CacheGeometry {
+            objects_offset: self.objects_offset,
+            object_stride: self.object_size,
+            objects_per_slab: self.objects_per_slab,
+        }
+    }
+
+    /// Encodes object of the slab with slab_index in caller's slab table as handle, see [CacheGeometry::encode_handle()]
+    ///
+    /// Panics if object_ptr is not an object of the slab at slab_ptr.
+    pub fn encode_handle(&self, slab_index: u64, slab_ptr: *const u8, object_ptr: *const T) -> u64 {
+        let geometry = self.geometry();
+        let object_index = geometry
+            .object_index(slab_ptr, object_ptr.cast())
+            .expect("Object is not in the slab");
+        geometry.encode_handle(slab_index, object_index)
+    }
+
+    /// Decodes handle made by [Cache::encode_handle()] into object ptr
+    ///
+    /// slab_ptrs is caller's slab table, it is indexed by the slab index of the handle.
+    pub fn decode_handle(&self, handle: u64, slab_ptrs: &[*const u8]) -> *mut T {
+        let geometry = self.geometry();
+        let (slab_index, object_index) = geometry.decode_handle(handle);
+        let slab_ptr =
+            slab_ptrs[usize::try_from(slab_index).expect("Slab index doesn't fit in usize")];
+        geometry
+            .object_ptr(slab_ptr, object_index)
+            .cast_mut()
+            .cast()
+    }
+
+    /// Gets minimum object size in bytes (two pointers)
+    ///
+    /// Free objects store links of the free objects list in their memory, so T must be at least this size.
+    pub const fn min_object_size() -> usize {
+        size_of::<FreeObject>()
+    }
+
+    /// Gets minimum object size in bytes with [CacheConfig::offset_free_links] (one u32)
+    pub const fn min_object_size_with_offset_links() -> usize {
+        size_of::<u32>()
+    }
+
+    /// Gets object size in bytes
+    ///
+    /// It is the distance between objects in slab, rounded up to [CacheConfig::object_alignment] if it is set.
+    pub fn object_size(&self) -> usize {
+        self.object_size
+    }
+
+    /// Gets slab size in bytes
+    pub fn slab_size(&self) -> usize {
+        self.slab_size
+    }
+
+    /// Gets page size in bytes
+    pub fn page_size(&self) -> usize {
+        self.page_size
+    }
+
+    /// Gets ObjectSizeType
+    pub fn object_size_type(&self) -> ObjectSizeType {
+        self.object_size_type
+    }
+
+    /// Gets objects per slab in bytes
+    pub fn objects_per_slab(&self) -> usize {
+        self.objects_per_slab
+    }
+
+    /// Gets occupancy threshold percent, see [Cache::set_occupancy_threshold_percent()]
+    pub fn occupancy_threshold_percent(&self) -> u8 {
+        self.occupancy_threshold_percent
+    }
+
+    /// Gets number of bytes in slab which can't be used for objects
+    ///
+    /// Includes SlabInfo for [ObjectSizeType::Small] and the tail which is smaller than object.
+    pub fn overhead_bytes(&self) -> usize {
+        self.slab_size - self.objects_per_slab * self.object_size
+    }
+
+    /// Gets cache statistics
+    pub fn cache_statistics(&self) -> CacheStatistics {
+        self.statistics
+    }
+
+    /// Same as [CacheStatistics::utilization_permille()] for current statistics
+    pub fn utilization_permille(&self) -> u32 {
+        self.statistics.utilization_permille()
+    }
+
+    /// Gets statistics recorded after each of the last [STATS_HISTORY_LENGTH] alloc/free calls, from the oldest to the newest
+    ///
+    /// Recorded by the cache itself, so it works where an external sampler can't run (e.g. allocations in interrupts).
+    #[cfg(feature = "stats_history")]
+    pub fn stats_history(&self) -> impl Iterator<Item = CacheStatistics> + '_ {
+        self.stats_history.iter()
+    }
+
+    /// Gets number of live objects of each allocation site of [Cache::alloc_tagged()]
+    ///
+    /// Only sites with live objects are yielded, in no particular order.<br>
+    /// At most [ALLOC_SITES_NUMBER] sites are counted at once, objects of other sites are not counted until some site has no live objects.
+    #[cfg(feature = "alloc_sites")]
+    pub fn live_by_site(&self) -> impl Iterator<Item = (u32, usize)> + '_ {
+        self.alloc_sites.iter()
+    }
+
+    /// Gets memory backend calls statistics
+    pub fn backend_statistics(&self) -> BackendStatistics {
+        self.backend_statistics
+    }
+
+    /// Gets memory backend
+    pub fn memory_backend(&self) -> &M {
+        &self.memory_backend
+    }
+
+    /// Gets mutable memory backend
+    ///
+    /// Memory backend must keep slabs, SlabInfo's and saved SlabInfo ptrs of the cache valid.
+    pub fn memory_backend_mut(&mut self) -> &mut M {
+        &mut self.memory_backend
+    }
+
+    /// Gets unique cache id
+    ///
+    /// Assigned at construction and doesn't change when the cache is moved
+    pub fn cache_id(&self) -> u64 {
+        self.id
+    }
+
+    /// Gets cache config
+    pub fn config(&self) -> CacheConfig {
+        self.config
+    }
+
+    /// Gets number of object slots in slabs currently allocated by the cache (allocated and free)
+    ///
+    /// This is not a limit, the cache allocates new slabs when it runs out of free objects.
+    pub fn capacity(&self) -> usize {
+        (self.statistics.free_slabs_number + self.statistics.full_slabs_number)
+            * self.objects_per_slab
+    }
+
+    /// Gets number of objects which can be allocated without slab allocation
+    ///
+    /// If it is 0, the next [Cache::alloc()] calls memory backend.
+    pub fn remaining_capacity(&self) -> usize {
+        self.statistics.free_objects_number
+    }
+
+    /// Checks that the cache has no slabs
+    ///
+    /// Then dropping the cache doesn't leak memory.
+    pub fn is_empty(&self) -> bool {
+        self.free_slabs_list_occupacy_less_75.is_empty()
+            && self.free_slabs_list_occupacy_more_75.is_empty()
+            && self.full_slabs_list.is_empty()
+            && self.emergency_slabs_list.is_empty()
+    }
+
+    /// Checks that the cache has a free object
+    ///
+    /// If false, the next [Cache::alloc()] calls memory backend.
+    pub fn has_free_object(&self) -> bool {
+        !self.free_slabs_list_occupacy_less_75.is_empty()
+            || !self.free_slabs_list_occupacy_more_75.is_empty()
+    }
+}
+
+/// Caches are equal only to themselves, compared by [Cache::cache_id()]
+impl<T, M: MemoryBackend + Sized> PartialEq for Cache<T, M> {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl<T, M: MemoryBackend + Sized> Eq for Cache<T, M> {}
+
+fn calculate_slab_info_addr_in_small_object_cache(
+    slab_ptr: *mut u8,
+    slab_size: usize,
+    small_info_placement: SmallInfoPlacement,
+) -> usize {
+    match small_info_placement {
+        // SlabInfo inside slab, at start, slab is page aligned
+        SmallInfoPlacement::Head => slab_ptr as usize,
+        // SlabInfo inside slab, at end
+        SmallInfoPlacement::Tail => {
+            let slab_info_addr = (slab_ptr as usize + slab_size) - size_of::<SlabInfo>();
+            align_down(slab_info_addr, align_of::<SlabInfo>())
+        }
+    }
+}
+
+/// Calculates minimum number of allocated objects of slab in free (>75) list
+///
+/// It is at least one object, empty slabs must stay in free (<75) list.<br>
+/// Slabs with 1 object are never in free (>75) list, they go free (<75) -> full on alloc and back on free.
+fn occupacy_more_75_minimum_allocated_objects_number(
+    objects_per_slab: usize,
+    occupancy_threshold_percent: u8,
+) -> usize {
+    ((occupancy_threshold_percent as usize * objects_per_slab) / 100).max(1)
+}
+
+/// Number of entries in [SlabInfoLookupCache]
+const SLAB_INFO_LOOKUP_CACHE_SIZE: usize = 4;
+
+/// Small cache of (page addr, SlabInfo addr) pairs got from memory backend
+///
+/// Frees are often clustered on the same pages, a hit avoids [MemoryBackend::get_slab_info_ptr()] call.<br>
+/// Entries must be invalidated when the slab is freed.
+struct SlabInfoLookupCache {
+    /// (page addr, SlabInfo addr), page addr 0 is an empty entry
+    entries: [(usize, usize); SLAB_INFO_LOOKUP_CACHE_SIZE],
+    /// Index of the entry to replace next
+    next_replaced_index: usize,
+}
 
-            // Add to free (<75)
-            self.free_slabs_list_occupacy_less_75
-                .push_front(UnsafeRef::from_raw(slab_info_ptr));
+impl SlabInfoLookupCache {
+    fn new() -> Self {
+        Self {
+            entries: [(0, 0); SLAB_INFO_LOOKUP_CACHE_SIZE],
+            next_replaced_index: 0,
         }
+    }
 
-        // List becomes empty?
-        if (*slab_info_ptr).data.get_mut().free_objects_number == self.objects_per_slab {
-            // All objects in slab is free - free slab
-            // Remove SlabInfo from free list
-            let mut slab_info_free_list_cursor = self
-                .free_slabs_list_occupacy_less_75
-                .cursor_mut_from_ptr(slab_info_ptr);
-            assert!(slab_info_free_list_cursor.remove().is_some());
-            self.statistics.free_slabs_number -= 1;
-            self.statistics.free_objects_number -= self.objects_per_slab;
+    fn get(&self, page_addr: usize) -> Option<*mut SlabInfo> {
+        debug_assert_ne!(page_addr, 0);
+        self.entries
+            .iter()
+            .find(|(entry_page_addr, _)| *entry_page_addr == page_addr)
+            .map(|(_, slab_info_addr)| *slab_info_addr as *mut SlabInfo)
+    }
 
-            // Free slab memory
-            self.memory_backend
-                .free_slab(slab_addr as *mut u8, self.slab_size, self.page_size);
+    fn insert(&mut self, page_addr: usize, slab_info_ptr: *mut SlabInfo) {
+        debug_assert_ne!(page_addr, 0);
+        self.entries[self.next_replaced_index] = (page_addr, slab_info_ptr as usize);
+        self.next_replaced_index = (self.next_replaced_index + 1) % SLAB_INFO_LOOKUP_CACHE_SIZE;
+    }
 
-            if !(self.object_size_type == ObjectSizeType::Small && self.slab_size == self.page_size)
-            {
-                if self.object_size_type == ObjectSizeType::Large {
-                    // Free SlabInfo
-                    self.memory_backend.free_slab_info(slab_info_ptr);
-                }
-                for i in 0..(self.slab_size / self.page_size) {
-                    let page_addr = slab_addr + (i * self.page_size);
-                    self.memory_backend.delete_slab_info_ptr(page_addr);
-                }
+    fn invalidate(&mut self, page_addr: usize) {
+        for entry in self.entries.iter_mut() {
+            if entry.0 == page_addr {
+                *entry = (0, 0);
             }
         }
     }
+}
 
-    /// Gets object size in bytes
-    pub fn object_size(&self) -> usize {
-        self.object_size
+/// Number of samples in [Cache::stats_history()]
+#[cfg(feature = "stats_history")]
+pub const STATS_HISTORY_LENGTH: usize = 64;
+
+/// Ring buffer of the last [STATS_HISTORY_LENGTH] statistics samples
+#[cfg(feature = "stats_history")]
+struct StatsHistory {
+    samples: [CacheStatistics; STATS_HISTORY_LENGTH],
+    /// Index of the sample to replace next
+    next_replaced_index: usize,
+    samples_number: usize,
+}
+
+#[cfg(feature = "stats_history")]
+impl StatsHistory {
+    fn new() -> Self {
+        Self {
+            samples: [CacheStatistics::zeroed(); STATS_HISTORY_LENGTH],
+            next_replaced_index: 0,
+            samples_number: 0,
+        }
     }
 
-    /// Gets slab size in bytes
-    pub fn slab_size(&self) -> usize {
-        self.slab_size
+    fn push(&mut self, sample: CacheStatistics) {
+        self.samples[self.next_replaced_index] = sample;
+        self.next_replaced_index = (self.next_replaced_index + 1) % STATS_HISTORY_LENGTH;
+        self.samples_number = (self.samples_number + 1).min(STATS_HISTORY_LENGTH);
     }
 
-    /// Gets page size in bytes
-    pub fn page_size(&self) -> usize {
-        self.page_size
+    /// Iterates samples from the oldest to the newest
+    fn iter(&self) -> impl Iterator<Item = CacheStatistics> + '_ {
+        let oldest_sample_index = (self.next_replaced_index + STATS_HISTORY_LENGTH
+            - self.samples_number)
+            % STATS_HISTORY_LENGTH;
+        (0..self.samples_number)
+            .map(move |i| self.samples[(oldest_sample_index + i) % STATS_HISTORY_LENGTH])
     }
+}
 
-    /// Gets ObjectSizeType
-    pub fn object_size_type(&self) -> ObjectSizeType {
-        self.object_size_type
+/// Number of allocation sites counted by [Cache::live_by_site()] at once
+#[cfg(feature = "alloc_sites")]
+pub const ALLOC_SITES_NUMBER: usize = 32;
+
+/// Live objects counters of allocation sites
+///
+/// Counter with zero live objects is free and may be taken by another site.
+#[cfg(feature = "alloc_sites")]
+struct AllocSites {
+    /// Site and its live objects number
+    counters: [(u32, usize); ALLOC_SITES_NUMBER],
+}
+
+#[cfg(feature = "alloc_sites")]
+impl AllocSites {
+    fn new() -> Self {
+        Self {
+            counters: [(0, 0); ALLOC_SITES_NUMBER],
+        }
     }
 
-    /// Gets objects per slab in bytes
-    pub fn objects_per_slab(&self) -> usize {
-        self.objects_per_slab
+    fn increment(&mut self, site: u32) {
+        let counter_index = self
+            .counters
+            .iter()
+            .position(|&(counter_site, live)| counter_site == site && live != 0)
+            .or_else(|| self.counters.iter().position(|&(_, live)| live == 0));
+        // All counters are taken, the object is not counted
+        if let Some(counter_index) = counter_index {
+            self.counters[counter_index].0 = site;
+            self.counters[counter_index].1 += 1;
+        }
     }
 
-    /// Gets cache statistics
-    pub fn cache_statistics(&self) -> CacheStatistics {
-        self.statistics
+    fn decrement(&mut self, site: u32) {
+        if let Some(counter) = self
+            .counters
+            .iter_mut()
+            .find(|(counter_site, live)| *counter_site == site && *live != 0)
+        {
+            counter.1 -= 1;
+        }
     }
-}
 
-fn calculate_slab_info_addr_in_small_object_cache(slab_ptr: *mut u8, slab_size: usize) -> usize {
-    // SlabInfo inside slab, at end
-    let slab_info_addr = (slab_ptr as usize + slab_size) - size_of::<SlabInfo>();
-    align_down(slab_info_addr, align_of::<SlabInfo>())
+    fn iter(&self) -> impl Iterator<Item = (u32, usize)> + '_ {
+        self.counters.iter().copied().filter(|&(_, live)| live != 0)
+    }
 }
 
 fn align_down(addr: usize, align: usize) -> usize {
     addr & !(align - 1)
 }
 
+/// Teardown of the cache
+///
+/// Empty slabs and the emergency reserve are returned to memory backend, then memory backend is dropped.<br>
+/// Slabs with allocated objects are leaked: objects may still be used, so their memory is not given back.<br>
+/// If [CacheConfig::reclaim] is not set, all slabs are leaked, memory backend free methods are not called.
+impl<T, M: MemoryBackend + Sized> Drop for Cache<T, M> {
+    fn drop(&mut self) {
+        if !self.config.reclaim {
+            return;
+        }
+        unsafe {
+            self.drain_free_slabs();
+            self.set_emergency_reserve(0).unwrap();
+        }
+    }
+}
+
 /// See README.md, [ObjectSizeType::Small] and [ObjectSizeType::Large]
 #[derive(Debug, Copy, Clone, PartialEq)]
 pub enum ObjectSizeType {
@@ -489,16 +3303,105 @@ unsafe impl Send for SlabInfo {}
 unsafe impl Sync for SlabInfo {}
 
 struct SlabInfoData {
+    /// Always [SLAB_INFO_CANARY], other value means that SlabInfo was overwritten (e.g. by object buffer overrun)
+    canary: u64,
     /// Free objects in slab list
     free_objects_list: LinkedList<FreeObjectAdapter>,
-    /// Slab cache to which slab belongs
-    cache_ptr: *mut u8,
+    /// Free objects in slab list with offsets from the slab start, used instead of free_objects_list if [CacheConfig::offset_free_links] is set
+    free_objects_offset_list: OffsetFreeList,
+    /// NUMA node of the slab memory, [NO_NODE] if slab was not allocated by [MemoryBackend::alloc_slab_on_node()]
+    node: u32,
+    /// Id of the slab cache to which slab belongs
+    ///
+    /// Unlike a pointer to the cache, it stays valid when the cache is moved
+    cache_id: u64,
     /// Number of free objects in slab
     free_objects_number: usize,
+    /// Number of objects at the slab start which were never allocated and are not in free objects list
+    ///
+    /// Used if [CacheConfig::lazy_carve] is set, included in free_objects_number
+    uncarved_objects_number: usize,
     /// Slab ptr
     slab_ptr: *mut u8,
+    /// Cache operations number at the last alloc/free in slab, see [Cache::reap_idle()]
+    last_used_operation: u64,
+    /// Slab memory is owned by the caller, see [Cache::donate_slab()]
+    donated: bool,
+}
+
+/// Written to every SlabInfo, checked before SlabInfo is used
+const SLAB_INFO_CANARY: u64 = 0x5AB1_2F0C_A2A2_7E11;
+
+/// Ends free objects list with offset links
+const NO_FREE_OBJECT_OFFSET: u32 = u32::MAX;
+
+/// Free objects list with offset links, used by [CacheConfig::offset_free_links] slabs and [FixedCache]
+///
+/// Each free object stores u32 offset of the next one from the base (slab or buffer start), [NO_FREE_OBJECT_OFFSET] ends the list.<br>
+/// Objects must be aligned for u32.
+#[repr(transparent)]
+struct OffsetFreeList {
+    /// Offset of the first free object
+    head: u32,
+}
+
+impl OffsetFreeList {
+    const fn new() -> Self {
+        Self {
+            head: NO_FREE_OBJECT_OFFSET,
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.head == NO_FREE_OBJECT_OFFSET
+    }
+
+    /// Adds free object to the list start
+    unsafe fn push(&mut self, base_ptr: *const u8, free_object_ptr: *mut u8) {
+        assert!(
+            free_object_ptr.cast::<u32>().is_aligned(),
+            "Free object link addr not aligned!"
+        );
+        free_object_ptr.cast::<u32>().write(self.head);
+        self.head = (free_object_ptr as usize - base_ptr as usize) as u32;
+    }
+
+    /// Takes the first free object, list must not be empty
+    unsafe fn pop(&mut self, base_ptr: *mut u8) -> *mut u8 {
+        assert!(!self.is_empty());
+        let free_object_ptr = base_ptr.add(self.head as usize);
+        self.head = free_object_ptr.cast::<u32>().read();
+        free_object_ptr
+    }
+
+    /// Removes free object, previous_free_object_ptr is the previous object in list (null for the first)
+    unsafe fn remove(&mut self, previous_free_object_ptr: *const u8, free_object_ptr: *const u8) {
+        let next_free_object_offset = free_object_ptr.cast::<u32>().read();
+        if previous_free_object_ptr.is_null() {
+            self.head = next_free_object_offset;
+        } else {
+            (previous_free_object_ptr as *mut u32).write(next_free_object_offset);
+        }
+    }
+
+    /// Gets next object in list, see [Cache::next_free_object()]
+    unsafe fn next(&self, base_ptr: *const u8, free_object_ptr: *const u8) -> *const u8 {
+        let next_free_object_offset = if free_object_ptr.is_null() {
+            self.head
+        } else {
+            free_object_ptr.cast::<u32>().read()
+        };
+        if next_free_object_offset == NO_FREE_OBJECT_OFFSET {
+            null()
+        } else {
+            base_ptr.add(next_free_object_offset as usize)
+        }
+    }
 }
 
+/// Node of slab which was allocated without node hint
+const NO_NODE: u32 = u32::MAX;
+
 #[derive(Debug)]
 #[repr(transparent)]
 /// Metadata stored inside a free object and pointing to the previous and next free object
@@ -515,21 +3418,133 @@ intrusive_adapter!(FreeObjectAdapter = UnsafeRef<FreeObject>: FreeObject { free_
 ///
 /// See [Cache::new()] for memory backend requirements
 pub trait MemoryBackend {
+    /// Error of memory backend allocations, e.g. [MemoryBackend::alloc_slab()]
+    ///
+    /// Fallible cache methods return it as [CacheError::Backend], so callers can tell failures apart (e.g. out of memory and region not mapped yet).<br>
+    /// Memory backends which don't distinguish failures can use [OutOfMemory] (or &'static str) as the error.
+    type Error: From<OutOfMemory> + Debug;
+
     /// Allocates slab for cache
     ///
+    /// Returns error if slab can't be allocated, it is returned by [Cache::try_alloc()].
+    ///
+    /// # Safety
+    /// Must be page aligned
+    unsafe fn alloc_slab(
+        &mut self,
+        slab_size: usize,
+        page_size: usize,
+    ) -> Result<*mut u8, Self::Error>;
+
+    /// Allocates zeroed slab for cache
+    ///
+    /// Used instead of [MemoryBackend::alloc_slab()] by caches with [CacheConfig::zero_objects].<br>
+    /// By default allocates slab with [MemoryBackend::alloc_slab()] and zeroes it, override it if zeroed pages can be obtained cheaper.
+    ///
+    /// # Safety
+    /// Must be page aligned
+    unsafe fn alloc_slab_zeroed(
+        &mut self,
+        slab_size: usize,
+        page_size: usize,
+    ) -> Result<*mut u8, Self::Error> {
+        let slab_ptr = self.alloc_slab(slab_size, page_size)?;
+        slab_ptr.write_bytes(0, slab_size);
+        Ok(slab_ptr)
+    }
+
+    /// Allocates slab on the NUMA node, used by [Cache::alloc_on_node()]
+    ///
+    /// By default ignores the node and calls [MemoryBackend::alloc_slab()].<br>
+    /// The cache zeroes the slab itself if [CacheConfig::zero_objects] is set.
+    ///
+    /// # Safety
+    /// Must be page aligned
+    unsafe fn alloc_slab_on_node(
+        &mut self,
+        slab_size: usize,
+        page_size: usize,
+        node: u32,
+    ) -> Result<*mut u8, Self::Error> {
+        let _ = node;
+        self.alloc_slab(slab_size, page_size)
+    }
+
+    /// Allocates one contiguous region of count slabs
+    ///
+    /// Returns the region start or error if there is no such region, [Cache::reserve_contiguous()] allocates slabs one by one then.<br>
+    /// The cache carves count slabs of slab_size from the region, each of them is freed separately by [MemoryBackend::free_slab()].<br>
+    /// Returns [OutOfMemory] by default, so slabs are allocated one by one.
+    ///
     /// # Safety
     /// Must be page aligned
-    unsafe fn alloc_slab(&mut self, slab_size: usize, page_size: usize) -> *mut u8;
+    unsafe fn alloc_slabs(
+        &mut self,
+        _count: usize,
+        _slab_size: usize,
+        _page_size: usize,
+    ) -> Result<*mut u8, Self::Error> {
+        Err(OutOfMemory.into())
+    }
+
+    /// Gets number of slabs which can be allocated now, if it is known
+    ///
+    /// Used by [Cache::reserve_contiguous()] to limit the number of requested slabs,
+    /// [Cache::reserve_slabs()] and [Cache::reserve_objects()] fail early if it is not enough.<br>
+    /// It is a hint, allocation may fail anyway. Returns None by default.
+    fn available_slabs_hint(&self, _slab_size: usize, _page_size: usize) -> Option<usize> {
+        None
+    }
 
     /// Frees slab
+    ///
+    /// # Safety
+    /// slab_ptr is a slab previously allocated by [MemoryBackend::alloc_slab()] with the same sizes
     unsafe fn free_slab(&mut self, slab_ptr: *mut u8, slab_size: usize, page_size: usize);
 
+    /// Frees slab and reports failure
+    ///
+    /// The cache frees slabs only by this method. By default calls [MemoryBackend::free_slab()] and never fails.<br>
+    /// Memory backends which can detect failures (e.g. double free) should override it.
+    /// On failure the cache counts it in [BackendStatistics::slab_free_failures] and forgets the slab anyway.
+    ///
+    /// # Safety
+    /// slab_ptr is a slab previously allocated by [MemoryBackend::alloc_slab()] with the same sizes
+    unsafe fn try_free_slab(
+        &mut self,
+        slab_ptr: *mut u8,
+        slab_size: usize,
+        page_size: usize,
+    ) -> Result<(), Self::Error> {
+        self.free_slab(slab_ptr, slab_size, page_size);
+        Ok(())
+    }
+
     /// Allocs SlabInfo
-    unsafe fn alloc_slab_info(&mut self) -> *mut SlabInfo;
+    ///
+    /// Returns error if SlabInfo can't be allocated, it is returned by [Cache::try_alloc()].
+    ///
+    /// # Safety
+    /// Must be aligned for SlabInfo
+    unsafe fn alloc_slab_info(&mut self) -> Result<*mut SlabInfo, Self::Error>;
 
     /// Frees SlabInfo
+    ///
+    /// # Safety
+    /// slab_info_ptr is a SlabInfo previously allocated by [MemoryBackend::alloc_slab_info()]
     unsafe fn free_slab_info(&mut self, slab_info_ptr: *mut SlabInfo);
 
+    /// Gives memory in a denser region to move SlabInfo into
+    ///
+    /// Used by [Cache::compact_slab_infos()], the cache moves SlabInfo to the returned memory and frees the old one by [MemoryBackend::free_slab_info()].<br>
+    /// Returns null to keep SlabInfo in place. By default returns null, so SlabInfos are never moved.
+    ///
+    /// # Safety
+    /// Returned memory must be aligned for SlabInfo, it is freed by [MemoryBackend::free_slab_info()] like allocated one
+    unsafe fn relocate_slab_info(&mut self, _slab_info_ptr: *mut SlabInfo) -> *mut SlabInfo {
+        null_mut()
+    }
+
     /// It is required to save slab_info_ptr to the corresponding object page addr
     ///
     /// This function cannot be called just for the cache which: [ObjectSizeType::Small] and slab_size == page_size.<br>
@@ -553,9 +3568,15 @@ pub trait MemoryBackend {
     ///  |o0;o1|o2;o3| <-- 2 pages (2 pages in slab)<br>
     /// If you align the address of the object to the page, you can unambiguously refer it to the correct slab (slab page) and calculate SlabInfo by the slab page as well.<br>
     /// Not only is it incredibly wasteful to save SlabInfo for each object, but it doesn't make sense. But this trick works only when the beginning of the slab is aligned to the beginning of the page and when its size is the sum of page sizes.
+    ///
+    /// # Safety
+    /// Called only by the cache, object_page_addr is page aligned
     unsafe fn save_slab_info_ptr(&mut self, object_page_addr: usize, slab_info_ptr: *mut SlabInfo);
 
     /// It is required to get slab_info_ptr to the corresponding object page addr
+    ///
+    /// # Safety
+    /// Must return the slab_info_ptr previously saved for object_page_addr
     unsafe fn get_slab_info_ptr(&mut self, object_page_addr: usize) -> *mut SlabInfo;
 
     /// Notify that the SlabInfo for the page can be deleted(if exist)
@@ -571,10 +3592,13 @@ pub trait MemoryBackend {
     ///     saved_slab_infos_ht.remove(page_addr);
     /// }
     /// ```
+    ///
+    /// # Safety
+    /// Called only by the cache, page_addr is page aligned
     unsafe fn delete_slab_info_ptr(&mut self, page_addr: usize);
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Default, Clone, Copy)]
 pub struct CacheStatistics {
     /// Number of slabs with free objects
     pub free_slabs_number: usize,
@@ -585,3 +3609,418 @@ pub struct CacheStatistics {
     /// Number of objects in cache allocated from Cache
     pub allocated_objects_number: usize,
 }
+
+impl CacheStatistics {
+    /// Gets statistics with all fields zero, same as [Default::default()]
+    ///
+    /// Statistics of the cache without slabs, can be used as start value for aggregation of several caches.
+    pub const fn zeroed() -> Self {
+        Self {
+            free_slabs_number: 0,
+            full_slabs_number: 0,
+            free_objects_number: 0,
+            allocated_objects_number: 0,
+        }
+    }
+
+    /// Gets permille of objects in slabs which are allocated
+    ///
+    /// allocated * 1000 / (allocated + free), 0 if the cache has no slabs.
+    pub fn utilization_permille(&self) -> u32 {
+        (self.allocated_objects_number * 1000)
+            .checked_div(self.allocated_objects_number + self.free_objects_number)
+            .unwrap_or(0) as u32
+    }
+
+    /// Gets change of statistics since earlier sample
+    ///
+    /// Fields are signed, numbers may decrease between samples.
+    pub fn delta(&self, earlier: &CacheStatistics) -> CacheStatisticsDelta {
+        CacheStatisticsDelta {
+            free_slabs_number: self.free_slabs_number as isize - earlier.free_slabs_number as isize,
+            full_slabs_number: self.full_slabs_number as isize - earlier.full_slabs_number as isize,
+            free_objects_number: self.free_objects_number as isize
+                - earlier.free_objects_number as isize,
+            allocated_objects_number: self.allocated_objects_number as isize
+                - earlier.allocated_objects_number as isize,
+        }
+    }
+}
+
+/// Change of [CacheStatistics] between two samples, see [CacheStatistics::delta()]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CacheStatisticsDelta {
+    /// Change of [CacheStatistics::free_slabs_number]
+    pub free_slabs_number: isize,
+    /// Change of [CacheStatistics::full_slabs_number]
+    pub full_slabs_number: isize,
+    /// Change of [CacheStatistics::free_objects_number]
+    pub free_objects_number: isize,
+    /// Change of [CacheStatistics::allocated_objects_number]
+    pub allocated_objects_number: isize,
+}
+
+/// Number of memory backend calls made by the cache
+///
+/// Slab and SlabInfo counters count allocated/freed memory blocks, failed allocations are not counted.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct BackendStatistics {
+    /// Slabs allocated by [MemoryBackend::alloc_slab()]/[MemoryBackend::alloc_slabs()]
+    pub slab_allocs: usize,
+    /// Slabs freed by [MemoryBackend::try_free_slab()]
+    pub slab_frees: usize,
+    /// Failed [MemoryBackend::try_free_slab()] calls
+    pub slab_free_failures: usize,
+    /// SlabInfo's allocated by [MemoryBackend::alloc_slab_info()]
+    pub slab_info_allocs: usize,
+    /// SlabInfo's freed by [MemoryBackend::free_slab_info()]
+    pub slab_info_frees: usize,
+    /// [MemoryBackend::save_slab_info_ptr()] calls
+    pub save_calls: usize,
+    /// [MemoryBackend::get_slab_info_ptr()] calls
+    pub get_calls: usize,
+    /// [MemoryBackend::delete_slab_info_ptr()] calls
+    pub delete_calls: usize,
+    /// Total time of alloc slow path (slab allocation and setup), in [CacheConfig::clock] units
+    pub slow_path_time_total: u64,
+    /// Time of the last alloc slow path, in [CacheConfig::clock] units
+    pub slow_path_time_last: u64,
+    /// Maximum time of alloc slow path, in [CacheConfig::clock] units
+    pub slow_path_time_max: u64,
+}
+
+/// Placement of objects in slabs of the cache, see [Cache::geometry()]
+///
+/// Object with index i starts at slab_ptr + objects_offset + i * object_stride, it doesn't change while the cache exists.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CacheGeometry {
+    /// Offset of the first object from the slab start
+    pub objects_offset: usize,
+    /// Distance between objects in slab, same as [Cache::object_size()]
+    pub object_stride: usize,
+    /// Number of objects in slab
+    pub objects_per_slab: usize,
+}
+
+impl CacheGeometry {
+    /// Calculates ptr of the object with index in slab
+    pub fn object_ptr(&self, slab_ptr: *const u8, object_index: usize) -> *const u8 {
+        assert!(
+            object_index < self.objects_per_slab,
+            "Object index out of slab"
+        );
+        slab_ptr.wrapping_add(self.objects_offset + object_index * self.object_stride)
+    }
+
+    /// Calculates index of the object in slab, inverse of [CacheGeometry::object_ptr()]
+    ///
+    /// Returns None if object_ptr is not the start of an object of the slab.
+    pub fn object_index(&self, slab_ptr: *const u8, object_ptr: *const u8) -> Option<usize> {
+        let object_offset =
+            (object_ptr as usize).checked_sub(slab_ptr as usize + self.objects_offset)?;
+        if !object_offset.is_multiple_of(self.object_stride) {
+            return None;
+        }
+        let object_index = object_offset / self.object_stride;
+        (object_index < self.objects_per_slab).then_some(object_index)
+    }
+
+    /// Gets number of low handle bits which hold object index
+    pub fn object_index_bits(&self) -> u32 {
+        usize::BITS - (self.objects_per_slab - 1).leading_zeros()
+    }
+
+    /// Encodes slab index and object index as handle
+    ///
+    /// Object index is in the low [CacheGeometry::object_index_bits()] bits, slab index is above it.<br>
+    /// Bits above slab index may be used by the caller, e.g. for cache index.
+    pub fn encode_handle(&self, slab_index: u64, object_index: usize) -> u64 {
+        assert!(
+            object_index < self.objects_per_slab,
+            "Object index out of slab"
+        );
+        let object_index_bits = self.object_index_bits();
+        assert!(
+            slab_index.leading_zeros() >= object_index_bits,
+            "Slab index doesn't fit in handle"
+        );
+        (slab_index << object_index_bits) | object_index as u64
+    }
+
+    /// Decodes handle made by [CacheGeometry::encode_handle()] into slab index and object index
+    pub fn decode_handle(&self, handle: u64) -> (u64, usize) {
+        let object_index_bits = self.object_index_bits();
+        let object_index = (handle & ((1 << object_index_bits) - 1)) as usize;
+        (handle >> object_index_bits, object_index)
+    }
+}
+
+/// Result of [Cache::free_reporting()]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FreeOutcome {
+    /// The object was the last allocated object of its slab and the cache stopped using the slab (only with [CacheConfig::reclaim])
+    pub slab_released: bool,
+    /// The released slab memory was given back to memory backend
+    ///
+    /// False for donated slab (see [Cache::donate_slab()]) and if [MemoryBackend::try_free_slab()] failed.
+    pub returned_to_backend: bool,
+    /// Address of the released slab, Some if slab_released is set
+    pub released_slab_ptr: Option<*const u8>,
+}
+
+/// Error of fallible [Cache] methods which call memory backend, e.g. [Cache::try_alloc()]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CacheError<E> {
+    /// Memory backend failed, see [MemoryBackend::Error]
+    Backend(E),
+    /// Cache refused the operation, e.g. [CacheConfig::max_allocated_objects] is reached
+    Cache(&'static str),
+}
+
+impl<E> From<E> for CacheError<E> {
+    fn from(error: E) -> Self {
+        CacheError::Backend(error)
+    }
+}
+
+/// Memory backend has no memory, error of memory backends which don't distinguish failures (see [MemoryBackend::Error])
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OutOfMemory;
+
+/// Allows memory backends to report errors as strings, [OutOfMemory] is "Out of memory"
+impl From<OutOfMemory> for &'static str {
+    fn from(_: OutOfMemory) -> Self {
+        "Out of memory"
+    }
+}
+
+/// Optional cache behaviors
+///
+/// Default config gives the behavior of [Cache::new()]
+#[derive(Debug, Clone, Copy)]
+pub struct CacheConfig {
+    /// Allocated objects are zeroed
+    ///
+    /// Slabs are allocated with [MemoryBackend::alloc_slab_zeroed()] and objects are zeroed on free,
+    /// so alloc only needs to zero the free object link (two pointers) instead of the whole object.
+    pub zero_objects: bool,
+    /// Remember the last few SlabInfo ptrs got from [MemoryBackend::get_slab_info_ptr()]
+    ///
+    /// When frees are clustered on the same pages, most of them don't call memory backend.<br>
+    /// Has no effect for [ObjectSizeType::Small] && slab_size == page_size caches, they don't use get_slab_info_ptr.
+    pub slab_info_lookup_cache: bool,
+    /// How allocations are distributed between slabs with free objects
+    pub distribution_policy: DistributionPolicy,
+    /// How much validation runs on cache operations, see [AssertLevel]
+    ///
+    /// Checks which always guard against freeing invalid pointers (e.g. null ptr, object of another cache) are not affected.
+    pub assert_level: AssertLevel,
+    /// Free slabs when all their objects are freed, true by default
+    ///
+    /// When false, empty slabs stay in the cache and memory backend free methods are never called by [Cache::free()] and drop of the cache.<br>
+    /// Allows to use memory backend which can't free memory (e.g. early boot bump allocator).<br>
+    /// Slab which can't be added to the cache because SlabInfo allocation failed is leaked.<br>
+    /// Empty slabs can be freed explicitly by [Cache::drain_free_slabs()].
+    pub reclaim: bool,
+    /// [Cache::free_iter()] frees slabs emptied by the batch after all objects are freed, sorted by address
+    ///
+    /// Adjacent slabs are freed one after another, which helps memory backend (e.g. buddy allocator) to coalesce them.<br>
+    /// Has no effect if [CacheConfig::reclaim] is not set.
+    pub sorted_batch_reclaim: bool,
+    /// Maximum percent of slab which can't be used for objects, see [Cache::overhead_bytes()]
+    ///
+    /// If set, cache creation fails when slab overhead exceeds it, this catches misconfigured caches which waste most of their memory.
+    pub max_overhead_percent: Option<u8>,
+    /// Called when [Cache::alloc()] or [Cache::free()] moves a slab between lists
+    ///
+    /// Arguments are slab ptr, list from which slab is moved and list to which slab is moved.<br>
+    /// Allows to observe how slabs move around the occupancy threshold.
+    pub on_transition: Option<fn(*mut u8, SlabListKind, SlabListKind)>,
+    /// Clock (e.g. cycle counter) used to time alloc slow path, see [BackendStatistics::slow_path_time_total]
+    ///
+    /// The slow path allocates slab by memory backend and sets it up, it is timed even if it fails. Not called if None.
+    pub clock: Option<fn() -> u64>,
+    /// Don't fill free objects list of the new slab
+    ///
+    /// Objects never allocated before are taken directly from the slab memory, before objects from the free objects list.<br>
+    /// Slab setup doesn't touch objects memory, this is faster for big slabs when only a few objects are used before the slab is freed.
+    pub lazy_carve: bool,
+    /// Link free objects by u32 offsets from the slab start instead of pointers
+    ///
+    /// Free object stores only the offset of the next free object, so minimum object size is 4 bytes instead of two pointers,
+    /// see [Cache::min_object_size_with_offset_links()].<br>
+    /// Slab size must be <= u32::MAX.
+    pub offset_free_links: bool,
+    /// Maximum number of allocated objects
+    ///
+    /// When it is reached, alloc returns null until objects are freed, even if the cache has free objects.
+    pub max_allocated_objects: Option<usize>,
+    /// Called when memory backend fails to allocate a slab for alloc
+    ///
+    /// Should try to free memory (e.g. shrink other caches) and return true if something was freed, then slab allocation is retried once.<br>
+    /// Allows to balance memory between caches without the cache knowing about them.
+    pub on_oom: Option<fn() -> bool>,
+    /// Alignment of every object, overrides the type alignment if it is bigger
+    ///
+    /// Object size is rounded up to it (e.g. 48 bytes objects are 64 bytes aligned for DMA), so objects_per_slab is recalculated.<br>
+    /// Must be power of two and page_size must be multiple of it, slabs are only page aligned.
+    pub object_alignment: Option<usize>,
+    /// Where SlabInfo is placed in slab of [ObjectSizeType::Small] cache, at the end by default
+    pub small_info_placement: SmallInfoPlacement,
+    /// Keep full slabs list sorted by slab address
+    ///
+    /// Full slabs are iterated in the same order (e.g. by [Cache::dump()]), so successive dumps are comparable.<br>
+    /// Slab becoming full is inserted in O(full slabs number) instead of O(1).
+    pub sort_full_slabs: bool,
+    /// Split slabs with free objects between free (<75) and free (>75) lists, true by default
+    ///
+    /// If not set, all slabs with free objects are in one list and go between it and full list directly,
+    /// with fewer list moves per alloc/free. Allocations are less concentrated in the most occupied slabs, so slabs are freed less often.<br>
+    /// Occupancy threshold is 100 and can't be changed, see [Cache::set_occupancy_threshold_percent()].<br>
+    /// It pays off with [DistributionPolicy::Spread]. With [DistributionPolicy::Concentrate] the one list keeps all these slabs sorted, so churn gets slower.
+    pub split_free_lists: bool,
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        Self {
+            zero_objects: false,
+            slab_info_lookup_cache: false,
+            distribution_policy: DistributionPolicy::default(),
+            assert_level: AssertLevel::default(),
+            reclaim: true,
+            sorted_batch_reclaim: false,
+            max_overhead_percent: None,
+            on_transition: None,
+            clock: None,
+            lazy_carve: false,
+            offset_free_links: false,
+            max_allocated_objects: None,
+            on_oom: None,
+            object_alignment: None,
+            small_info_placement: SmallInfoPlacement::default(),
+            sort_full_slabs: false,
+            split_free_lists: true,
+        }
+    }
+}
+
+/// Objects of one slab, see [Cache::partial_slabs_by_emptiness()]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SlabStats {
+    /// Slab start address
+    pub slab_addr: usize,
+    /// Number of free objects in slab
+    pub free_objects_number: usize,
+    /// Number of allocated objects in slab
+    pub allocated_objects_number: usize,
+}
+
+/// List of the cache in which slab is stored
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum SlabListKind {
+    /// Slab has free objects, occupacy less than 75%
+    FreeLess75,
+    /// Slab has free objects, occupacy more than 75%
+    ///
+    /// Slabs with one object and slabs of caches without [CacheConfig::split_free_lists] are never in this list,
+    /// they go between [SlabListKind::FreeLess75] and [SlabListKind::Full] directly.
+    FreeMore75,
+    /// Slab has no free objects
+    Full,
+}
+
+/// Place of SlabInfo inside slab of [ObjectSizeType::Small] cache, see [CacheConfig::small_info_placement]
+#[derive(Debug, Default, Copy, Clone, PartialEq)]
+pub enum SmallInfoPlacement {
+    /// SlabInfo is at the slab start, objects follow it
+    ///
+    /// The first page of the slab holds metadata, objects are aligned only to the object alignment.
+    Head,
+    /// SlabInfo is at the slab end, objects start at the slab start
+    #[default]
+    Tail,
+}
+
+/// How [Cache::alloc()] chooses the slab from which the object is allocated
+#[derive(Debug, Default, Copy, Clone, PartialEq)]
+pub enum DistributionPolicy {
+    /// Allocate from the most occupied slab
+    ///
+    /// Allocations are concentrated in a few slabs, while other slabs are more likely to become empty and be freed.
+    #[default]
+    Concentrate,
+    /// Rotate through slabs with free objects, starting with the least occupied
+    ///
+    /// Consecutive allocations land in different slabs, which helps access parallelism (e.g. NUMA striping).<br>
+    /// Hurts reclaimability: objects are spread across all slabs, so slabs rarely become empty.
+    Spread,
+}
+
+/// Amount of internal validation, see [CacheConfig::assert_level]
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum AssertLevel {
+    /// No internal checks, for maximum performance
+    Off,
+    /// Cheap internal checks (lists accounting, double free), only in debug builds like debug_assert
+    #[default]
+    Normal,
+    /// Normal checks in any build, also the whole cache and memory backend are checked after every alloc/free
+    ///
+    /// Walks all slabs on every operation, intended for CI and soak tests.
+    Paranoid,
+}
+
+impl AssertLevel {
+    /// Checks whether checks of check_level run with this level
+    const fn enables(self, check_level: AssertLevel) -> bool {
+        match self {
+            AssertLevel::Off => false,
+            AssertLevel::Normal => {
+                cfg!(debug_assertions) && matches!(check_level, AssertLevel::Normal)
+            }
+            AssertLevel::Paranoid => true,
+        }
+    }
+}
+
+/// Set of allocation options for [Cache::alloc_flags()]
+///
+/// Flags are combined with `|`, e.g. `AllocFlags::ZERO | AllocFlags::NO_GROW`.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct AllocFlags(u32);
+
+impl AllocFlags {
+    /// No options, same as [Cache::alloc()]
+    pub const EMPTY: Self = Self(0);
+    /// Allocated object is zeroed
+    ///
+    /// Only the free object link is zeroed if [CacheConfig::zero_objects] is set, the rest is zero already.
+    pub const ZERO: Self = Self(1 << 0);
+    /// Memory backend is not called, null is returned if there are no free objects, same as [Cache::alloc_no_grow()]
+    pub const NO_GROW: Self = Self(1 << 1);
+
+    /// Gets raw flags bits
+    pub const fn bits(self) -> u32 {
+        self.0
+    }
+
+    /// Checks whether all flags of other are set
+    pub const fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+}
+
+impl core::ops::BitOr for AllocFlags {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        Self(self.0 | rhs.0)
+    }
+}
+
+impl core::ops::BitOrAssign for AllocFlags {
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.0 |= rhs.0;
+    }
+}
@@ -0,0 +1,107 @@
+use crate::{Cache, CacheConfig, CacheStatistics, MemoryBackend, ObjectSizeType};
+
+/// Slab cache of objects with size and alignment known only at runtime
+///
+/// Same as [Cache], but objects are untyped memory blocks of object_size bytes.
+pub struct RawCache<M: MemoryBackend + Sized> {
+    pub(crate) cache: Cache<u8, M>,
+}
+
+impl<M: MemoryBackend + Sized> RawCache<M> {
+    /// Creates raw cache
+    ///
+    /// object_size must be >= 8/16 (two pointers) and must be multiple of object_align.<br>
+    /// object_align must be power of two and page_size must be multiple of it.<br>
+    /// Other requirements are the same as [Cache::new()]
+    pub fn new(
+        object_size: usize,
+        object_align: usize,
+        slab_size: usize,
+        page_size: usize,
+        object_size_type: ObjectSizeType,
+        memory_backend: M,
+    ) -> Result<Self, &'static str> {
+        Self::with_config(
+            object_size,
+            object_align,
+            slab_size,
+            page_size,
+            object_size_type,
+            memory_backend,
+            CacheConfig::default(),
+        )
+    }
+
+    /// Same as [RawCache::new()], with optional behaviors set by [CacheConfig]
+    pub fn with_config(
+        object_size: usize,
+        object_align: usize,
+        slab_size: usize,
+        page_size: usize,
+        object_size_type: ObjectSizeType,
+        memory_backend: M,
+        config: CacheConfig,
+    ) -> Result<Self, &'static str> {
+        Ok(Self {
+            cache: Cache::with_layout(
+                object_size,
+                object_align,
+                slab_size,
+                page_size,
+                object_size_type,
+                memory_backend,
+                config,
+            )?,
+        })
+    }
+
+    /// Allocs object from cache
+    ///
+    /// # Safety
+    /// May return null pointer<br>
+    /// Allocated memory is not initialized
+    #[must_use = "the allocated pointer must be freed with RawCache::free"]
+    pub unsafe fn alloc(&mut self) -> *mut u8 {
+        self.cache.alloc()
+    }
+
+    /// Returns object to cache
+    ///
+    /// # Safety
+    /// Pointer must be a previously allocated pointer from the same cache
+    pub unsafe fn free(&mut self, object_ptr: *mut u8) {
+        assert!(
+            (object_ptr as usize).is_multiple_of(self.cache.object_align),
+            "Try to free not aligned ptr"
+        );
+        self.cache.free(object_ptr);
+    }
+
+    /// Same as [Cache::drain_free_slabs()]
+    ///
+    /// # Safety
+    /// Calls memory backend to free slabs
+    pub unsafe fn drain_free_slabs(&mut self) -> usize {
+        self.cache.drain_free_slabs()
+    }
+
+    /// Gets object size in bytes
+    pub fn object_size(&self) -> usize {
+        self.cache.object_size
+    }
+
+    /// Gets object alignment in bytes
+    pub fn object_align(&self) -> usize {
+        self.cache.object_align
+    }
+
+    /// Gets total objects in slab
+    pub fn objects_per_slab(&self) -> usize {
+        self.cache.objects_per_slab
+    }
+
+    /// Gets cache statistics
+    pub fn cache_statistics(&self) -> CacheStatistics {
+        self.cache.cache_statistics()
+    }
+}
@@ -0,0 +1,63 @@
+use crate::{Cache, MemoryBackend};
+use core::ops::{Deref, DerefMut};
+use core::ptr::NonNull;
+
+/// Owning pointer to an object allocated from [Cache]
+///
+/// Created by [Cache::alloc_box()]. When dropped, drops the value and returns object to cache.
+///
+/// SlabBox mutably borrows the cache, so the cache can't be used while the box is alive.<br>
+/// Use [SlabBox::into_raw()] to get the raw pointer and free it manually with [Cache::free()].
+pub struct SlabBox<'c, T, M: MemoryBackend + Sized> {
+    object_ptr: NonNull<T>,
+    cache: &'c mut Cache<T, M>,
+}
+
+impl<'c, T, M: MemoryBackend + Sized> SlabBox<'c, T, M> {
+    /// # Safety
+    /// object_ptr must be allocated from cache and contain initialized value
+    pub(crate) unsafe fn from_raw(object_ptr: *mut T, cache: &'c mut Cache<T, M>) -> Self {
+        Self {
+            object_ptr: NonNull::new(object_ptr).expect("SlabBox from null ptr"),
+            cache,
+        }
+    }
+
+    /// Consumes box without dropping the value and returning object to cache
+    ///
+    /// Pointer must be freed with [Cache::free()] of the same cache
+    #[must_use = "the pointer must be freed with Cache::free"]
+    pub fn into_raw(self) -> *mut T {
+        let object_ptr = self.object_ptr.as_ptr();
+        core::mem::forget(self);
+        object_ptr
+    }
+
+    /// Gets object ptr
+    pub fn as_ptr(&self) -> *mut T {
+        self.object_ptr.as_ptr()
+    }
+}
+
+impl<T, M: MemoryBackend + Sized> Deref for SlabBox<'_, T, M> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        unsafe { self.object_ptr.as_ref() }
+    }
+}
+
+impl<T, M: MemoryBackend + Sized> DerefMut for SlabBox<'_, T, M> {
+    fn deref_mut(&mut self) -> &mut T {
+        unsafe { self.object_ptr.as_mut() }
+    }
+}
+
+impl<T, M: MemoryBackend + Sized> Drop for SlabBox<'_, T, M> {
+    fn drop(&mut self) {
+        unsafe {
+            core::ptr::drop_in_place(self.object_ptr.as_ptr());
+            self.cache.free(self.object_ptr.as_ptr());
+        }
+    }
+}
@@ -1,9 +1,12 @@
 #[cfg(test)]
+#[allow(clippy::module_inception)]
 mod tests {
     use crate::*;
     extern crate alloc;
     extern crate std;
     use alloc::alloc::{alloc, dealloc, Layout};
+    use alloc::format;
+    use alloc::string::String;
     use alloc::vec;
     use alloc::vec::Vec;
     use rand::prelude::SliceRandom;
@@ -11,6 +14,90 @@ mod tests {
     use spin::{Mutex, Once};
     use std::collections::{HashMap, HashSet};
 
+    /// Memory backend for tests which don't check backend calls one by one
+    ///
+    /// Supports all cache configurations
+    #[derive(Default)]
+    struct StdMemoryBackend {
+        allocated_slab_addrs: Vec<usize>,
+        allocated_slab_info_addrs: Vec<usize>,
+        ht_saved_slab_infos: HashMap<usize, *mut SlabInfo>,
+        get_slab_info_ptr_calls: usize,
+        relocate_slab_infos: bool,
+    }
+
+    impl MemoryBackend for StdMemoryBackend {
+        type Error = OutOfMemory;
+
+        unsafe fn alloc_slab(
+            &mut self,
+            slab_size: usize,
+            page_size: usize,
+        ) -> Result<*mut u8, Self::Error> {
+            let layout = Layout::from_size_align(slab_size, page_size).unwrap();
+            let allocated_slab_ptr = alloc(layout);
+            assert!(!allocated_slab_ptr.is_null());
+            self.allocated_slab_addrs.push(allocated_slab_ptr as usize);
+            Ok(allocated_slab_ptr)
+        }
+
+        unsafe fn free_slab(&mut self, slab_ptr: *mut u8, slab_size: usize, page_size: usize) {
+            let position = self
+                .allocated_slab_addrs
+                .iter()
+                .position(|addr| *addr == slab_ptr as usize)
+                .unwrap();
+            self.allocated_slab_addrs.remove(position);
+            let layout = Layout::from_size_align(slab_size, page_size).unwrap();
+            dealloc(slab_ptr, layout);
+        }
+
+        unsafe fn alloc_slab_info(&mut self) -> Result<*mut SlabInfo, Self::Error> {
+            let layout = Layout::new::<SlabInfo>();
+            let allocated_ptr: *mut SlabInfo = alloc(layout).cast();
+            assert!(!allocated_ptr.is_null());
+            self.allocated_slab_info_addrs.push(allocated_ptr as usize);
+            Ok(allocated_ptr)
+        }
+
+        unsafe fn free_slab_info(&mut self, slab_info_ptr: *mut SlabInfo) {
+            let position = self
+                .allocated_slab_info_addrs
+                .iter()
+                .position(|addr| *addr == slab_info_ptr as usize)
+                .unwrap();
+            self.allocated_slab_info_addrs.remove(position);
+            let layout = Layout::new::<SlabInfo>();
+            dealloc(slab_info_ptr.cast(), layout);
+        }
+
+        unsafe fn relocate_slab_info(&mut self, _slab_info_ptr: *mut SlabInfo) -> *mut SlabInfo {
+            if self.relocate_slab_infos {
+                self.alloc_slab_info().unwrap()
+            } else {
+                null_mut()
+            }
+        }
+
+        unsafe fn save_slab_info_ptr(
+            &mut self,
+            object_page_addr: usize,
+            slab_info_ptr: *mut SlabInfo,
+        ) {
+            self.ht_saved_slab_infos
+                .insert(object_page_addr, slab_info_ptr);
+        }
+
+        unsafe fn get_slab_info_ptr(&mut self, object_page_addr: usize) -> *mut SlabInfo {
+            self.get_slab_info_ptr_calls += 1;
+            *self.ht_saved_slab_infos.get(&object_page_addr).unwrap()
+        }
+
+        unsafe fn delete_slab_info_ptr(&mut self, page_addr: usize) {
+            self.ht_saved_slab_infos.remove(&page_addr);
+        }
+    }
+
     #[test]
     fn can_be_used_as_static() {
         let test_memory_backend: TestMemoryBackend = TestMemoryBackend;
@@ -24,7 +111,13 @@ mod tests {
         struct TestMemoryBackend;
 
         impl MemoryBackend for TestMemoryBackend {
-            unsafe fn alloc_slab(&mut self, _slab_size: usize, _page_size: usize) -> *mut u8 {
+            type Error = OutOfMemory;
+
+            unsafe fn alloc_slab(
+                &mut self,
+                _slab_size: usize,
+                _page_size: usize,
+            ) -> Result<*mut u8, Self::Error> {
                 unreachable!();
             }
 
@@ -37,7 +130,7 @@ mod tests {
                 unreachable!();
             }
 
-            unsafe fn alloc_slab_info(&mut self) -> *mut SlabInfo {
+            unsafe fn alloc_slab_info(&mut self) -> Result<*mut SlabInfo, Self::Error> {
                 unreachable!();
             }
 
@@ -85,14 +178,20 @@ mod tests {
             }
 
             impl MemoryBackend for TestMemoryBackend {
-                unsafe fn alloc_slab(&mut self, slab_size: usize, page_size: usize) -> *mut u8 {
+                type Error = OutOfMemory;
+
+                unsafe fn alloc_slab(
+                    &mut self,
+                    slab_size: usize,
+                    page_size: usize,
+                ) -> Result<*mut u8, Self::Error> {
                     assert_eq!(slab_size, SLAB_SIZE);
                     assert_eq!(page_size, PAGE_SIZE);
                     let layout = Layout::from_size_align(slab_size, page_size).unwrap();
                     let allocated_slab_ptr = alloc(layout);
                     assert!(!allocated_slab_ptr.is_null());
                     self.allocated_slab_addrs.push(allocated_slab_ptr as usize);
-                    allocated_slab_ptr
+                    Ok(allocated_slab_ptr)
                 }
 
                 unsafe fn free_slab(
@@ -104,7 +203,7 @@ mod tests {
                     unreachable!();
                 }
 
-                unsafe fn alloc_slab_info(&mut self) -> *mut SlabInfo {
+                unsafe fn alloc_slab_info(&mut self) -> Result<*mut SlabInfo, Self::Error> {
                     unreachable!();
                 }
 
@@ -149,19 +248,16 @@ mod tests {
             }
             // slab 0            slab 1            slab2
             // [obj2, obj1, obj0][obj2, obj1, obj0][obj2]
-            let mut obj_index_in_slab = cache.objects_per_slab - 1;
             for (i, v) in allocated_ptrs.iter().enumerate() {
                 // 0 0 0 1 1 1 2
                 let slab_index = i / cache.objects_per_slab;
                 // 0 1 2 3 4 5 6
                 // 2 1 0 2 1 0 2
-                let object_addr = cache.memory_backend.allocated_slab_addrs[slab_index]
-                    + obj_index_in_slab * cache.object_size;
-                if obj_index_in_slab == 0 {
-                    obj_index_in_slab = cache.objects_per_slab - 1;
-                } else {
-                    obj_index_in_slab -= 1;
-                }
+                let obj_index_in_slab = cache.objects_per_slab - 1 - i % cache.objects_per_slab;
+                let object_addr = cache.predicted_slot_addr(
+                    cache.memory_backend.allocated_slab_addrs[slab_index] as *const u8,
+                    obj_index_in_slab,
+                );
                 assert_eq!(*v as usize, object_addr);
             }
 
@@ -243,13 +339,19 @@ mod tests {
             }
 
             impl MemoryBackend for TestMemoryBackend {
-                unsafe fn alloc_slab(&mut self, slab_size: usize, page_size: usize) -> *mut u8 {
+                type Error = OutOfMemory;
+
+                unsafe fn alloc_slab(
+                    &mut self,
+                    slab_size: usize,
+                    page_size: usize,
+                ) -> Result<*mut u8, Self::Error> {
                     assert_eq!(slab_size, SLAB_SIZE);
                     assert_eq!(page_size, PAGE_SIZE);
                     let layout = Layout::from_size_align(slab_size, page_size).unwrap();
                     let allocated_slab_ptr = alloc(layout);
                     self.allocated_slab_addrs.push(allocated_slab_ptr as usize);
-                    allocated_slab_ptr
+                    Ok(allocated_slab_ptr)
                 }
 
                 unsafe fn free_slab(
@@ -261,7 +363,7 @@ mod tests {
                     unreachable!();
                 }
 
-                unsafe fn alloc_slab_info(&mut self) -> *mut SlabInfo {
+                unsafe fn alloc_slab_info(&mut self) -> Result<*mut SlabInfo, Self::Error> {
                     unreachable!();
                 }
 
@@ -403,13 +505,19 @@ mod tests {
             }
 
             impl MemoryBackend for TestMemoryBackend {
-                unsafe fn alloc_slab(&mut self, slab_size: usize, page_size: usize) -> *mut u8 {
+                type Error = OutOfMemory;
+
+                unsafe fn alloc_slab(
+                    &mut self,
+                    slab_size: usize,
+                    page_size: usize,
+                ) -> Result<*mut u8, Self::Error> {
                     assert_eq!(slab_size, SLAB_SIZE);
                     assert_eq!(page_size, PAGE_SIZE);
                     let layout = Layout::from_size_align(slab_size, page_size).unwrap();
                     let allocated_slab_ptr = alloc(layout);
                     self.allocated_slab_addrs.push(allocated_slab_ptr as usize);
-                    allocated_slab_ptr
+                    Ok(allocated_slab_ptr)
                 }
 
                 unsafe fn free_slab(
@@ -421,14 +529,14 @@ mod tests {
                     unreachable!();
                 }
 
-                unsafe fn alloc_slab_info(&mut self) -> *mut SlabInfo {
+                unsafe fn alloc_slab_info(&mut self) -> Result<*mut SlabInfo, Self::Error> {
                     let layout =
                         Layout::from_size_align(size_of::<SlabInfo>(), align_of::<SlabInfo>())
                             .unwrap();
                     let allocated_slab_info_ptr = alloc(layout);
                     self.allocated_slab_infos_addrs
                         .push(allocated_slab_info_ptr as usize);
-                    allocated_slab_info_ptr.cast()
+                    Ok(allocated_slab_info_ptr.cast())
                 }
 
                 unsafe fn free_slab_info(&mut self, _slab_info_ptr: *mut SlabInfo) {
@@ -569,13 +677,19 @@ mod tests {
             }
 
             impl MemoryBackend for TestMemoryBackend {
-                unsafe fn alloc_slab(&mut self, slab_size: usize, page_size: usize) -> *mut u8 {
+                type Error = OutOfMemory;
+
+                unsafe fn alloc_slab(
+                    &mut self,
+                    slab_size: usize,
+                    page_size: usize,
+                ) -> Result<*mut u8, Self::Error> {
                     assert_eq!(slab_size, SLAB_SIZE);
                     assert_eq!(page_size, PAGE_SIZE);
                     let layout = Layout::from_size_align(slab_size, page_size).unwrap();
                     let allocated_slab_ptr = alloc(layout);
                     self.allocated_slab_addrs.push(allocated_slab_ptr as usize);
-                    allocated_slab_ptr
+                    Ok(allocated_slab_ptr)
                 }
 
                 unsafe fn free_slab(
@@ -587,14 +701,14 @@ mod tests {
                     unreachable!();
                 }
 
-                unsafe fn alloc_slab_info(&mut self) -> *mut SlabInfo {
+                unsafe fn alloc_slab_info(&mut self) -> Result<*mut SlabInfo, Self::Error> {
                     let layout =
                         Layout::from_size_align(size_of::<SlabInfo>(), align_of::<SlabInfo>())
                             .unwrap();
                     let allocated_slab_info_ptr = alloc(layout);
                     self.allocated_slab_infos_addrs
                         .push(allocated_slab_info_ptr as usize);
-                    allocated_slab_info_ptr.cast()
+                    Ok(allocated_slab_info_ptr.cast())
                 }
 
                 unsafe fn free_slab_info(&mut self, _slab_info_ptr: *mut SlabInfo) {
@@ -735,14 +849,20 @@ mod tests {
             }
 
             impl MemoryBackend for TestMemoryBackend {
-                unsafe fn alloc_slab(&mut self, slab_size: usize, page_size: usize) -> *mut u8 {
+                type Error = OutOfMemory;
+
+                unsafe fn alloc_slab(
+                    &mut self,
+                    slab_size: usize,
+                    page_size: usize,
+                ) -> Result<*mut u8, Self::Error> {
                     assert_eq!(slab_size, SLAB_SIZE);
                     assert_eq!(page_size, PAGE_SIZE);
                     let layout = Layout::from_size_align(slab_size, page_size).unwrap();
                     let allocated_slab_ptr = alloc(layout);
                     assert!(!allocated_slab_ptr.is_null());
                     self.allocated_slab_addrs.push(allocated_slab_ptr as usize);
-                    allocated_slab_ptr
+                    Ok(allocated_slab_ptr)
                 }
 
                 unsafe fn free_slab(
@@ -763,7 +883,7 @@ mod tests {
                     dealloc(slab_ptr, layout);
                 }
 
-                unsafe fn alloc_slab_info(&mut self) -> *mut SlabInfo {
+                unsafe fn alloc_slab_info(&mut self) -> Result<*mut SlabInfo, Self::Error> {
                     unreachable!();
                 }
 
@@ -935,7 +1055,7 @@ mod tests {
                 }
 
                 // All addresses are unique
-                let hs: HashSet<_> = HashSet::from_iter(allocated_ptrs.clone().into_iter());
+                let hs: HashSet<_> = HashSet::from_iter(allocated_ptrs.clone());
                 assert_eq!(hs.len(), allocated_ptrs.len());
 
                 // Check statistics
@@ -1011,14 +1131,20 @@ mod tests {
             }
 
             impl MemoryBackend for TestMemoryBackend {
-                unsafe fn alloc_slab(&mut self, slab_size: usize, page_size: usize) -> *mut u8 {
+                type Error = OutOfMemory;
+
+                unsafe fn alloc_slab(
+                    &mut self,
+                    slab_size: usize,
+                    page_size: usize,
+                ) -> Result<*mut u8, Self::Error> {
                     assert_eq!(slab_size, SLAB_SIZE);
                     assert_eq!(page_size, PAGE_SIZE);
                     let layout = Layout::from_size_align(slab_size, page_size).unwrap();
                     let allocated_slab_ptr = alloc(layout);
                     assert!(!allocated_slab_ptr.is_null());
                     self.allocated_slab_addrs.push(allocated_slab_ptr as usize);
-                    allocated_slab_ptr
+                    Ok(allocated_slab_ptr)
                 }
 
                 unsafe fn free_slab(
@@ -1039,7 +1165,7 @@ mod tests {
                     dealloc(slab_ptr, layout);
                 }
 
-                unsafe fn alloc_slab_info(&mut self) -> *mut SlabInfo {
+                unsafe fn alloc_slab_info(&mut self) -> Result<*mut SlabInfo, Self::Error> {
                     unreachable!();
                 }
 
@@ -1237,7 +1363,7 @@ mod tests {
                 }
 
                 // All addresses are unique
-                let hs: HashSet<_> = HashSet::from_iter(allocated_ptrs.clone().into_iter());
+                let hs: HashSet<_> = HashSet::from_iter(allocated_ptrs.clone());
                 assert_eq!(hs.len(), allocated_ptrs.len());
                 assert_eq!(
                     cache.statistics.allocated_objects_number,
@@ -1324,14 +1450,20 @@ mod tests {
             }
 
             impl MemoryBackend for TestMemoryBackend {
-                unsafe fn alloc_slab(&mut self, slab_size: usize, page_size: usize) -> *mut u8 {
+                type Error = OutOfMemory;
+
+                unsafe fn alloc_slab(
+                    &mut self,
+                    slab_size: usize,
+                    page_size: usize,
+                ) -> Result<*mut u8, Self::Error> {
                     assert_eq!(slab_size, SLAB_SIZE);
                     assert_eq!(page_size, PAGE_SIZE);
                     let layout = Layout::from_size_align(slab_size, page_size).unwrap();
                     let allocated_slab_ptr = alloc(layout);
                     assert!(!allocated_slab_ptr.is_null());
                     self.allocated_slab_addrs.push(allocated_slab_ptr as usize);
-                    allocated_slab_ptr
+                    Ok(allocated_slab_ptr)
                 }
 
                 unsafe fn free_slab(
@@ -1352,12 +1484,12 @@ mod tests {
                     dealloc(slab_ptr, layout);
                 }
 
-                unsafe fn alloc_slab_info(&mut self) -> *mut SlabInfo {
+                unsafe fn alloc_slab_info(&mut self) -> Result<*mut SlabInfo, Self::Error> {
                     let layout = Layout::new::<SlabInfo>();
                     let allocated_ptr: *mut SlabInfo = alloc(layout).cast();
                     assert!(!allocated_ptr.is_null());
                     self.allocated_slab_info_addrs.push(allocated_ptr as usize);
-                    allocated_ptr
+                    Ok(allocated_ptr)
                 }
 
                 unsafe fn free_slab_info(&mut self, slab_info_ptr: *mut SlabInfo) {
@@ -1553,7 +1685,7 @@ mod tests {
                 }
 
                 // All addresses are unique
-                let hs: HashSet<_> = HashSet::from_iter(allocated_ptrs.clone().into_iter());
+                let hs: HashSet<_> = HashSet::from_iter(allocated_ptrs.clone());
                 assert_eq!(hs.len(), allocated_ptrs.len());
                 assert_eq!(
                     cache.statistics.allocated_objects_number,
@@ -1634,14 +1766,20 @@ mod tests {
             }
 
             impl MemoryBackend for TestMemoryBackend {
-                unsafe fn alloc_slab(&mut self, slab_size: usize, page_size: usize) -> *mut u8 {
+                type Error = OutOfMemory;
+
+                unsafe fn alloc_slab(
+                    &mut self,
+                    slab_size: usize,
+                    page_size: usize,
+                ) -> Result<*mut u8, Self::Error> {
                     assert_eq!(slab_size, SLAB_SIZE);
                     assert_eq!(page_size, PAGE_SIZE);
                     let layout = Layout::from_size_align(slab_size, page_size).unwrap();
                     let allocated_slab_ptr = alloc(layout);
                     assert!(!allocated_slab_ptr.is_null());
                     self.allocated_slab_addrs.push(allocated_slab_ptr as usize);
-                    allocated_slab_ptr
+                    Ok(allocated_slab_ptr)
                 }
 
                 unsafe fn free_slab(
@@ -1662,12 +1800,12 @@ mod tests {
                     dealloc(slab_ptr, layout);
                 }
 
-                unsafe fn alloc_slab_info(&mut self) -> *mut SlabInfo {
+                unsafe fn alloc_slab_info(&mut self) -> Result<*mut SlabInfo, Self::Error> {
                     let layout = Layout::new::<SlabInfo>();
                     let allocated_ptr: *mut SlabInfo = alloc(layout).cast();
                     assert!(!allocated_ptr.is_null());
                     self.allocated_slab_info_addrs.push(allocated_ptr as usize);
-                    allocated_ptr
+                    Ok(allocated_ptr)
                 }
 
                 unsafe fn free_slab_info(&mut self, slab_info_ptr: *mut SlabInfo) {
@@ -1865,7 +2003,7 @@ mod tests {
                 }
 
                 // All addresses are unique
-                let hs: HashSet<_> = HashSet::from_iter(allocated_ptrs.clone().into_iter());
+                let hs: HashSet<_> = HashSet::from_iter(allocated_ptrs.clone());
                 assert_eq!(hs.len(), allocated_ptrs.len());
                 assert_eq!(
                     cache.statistics.allocated_objects_number,
@@ -1944,14 +2082,20 @@ mod tests {
             }
 
             impl MemoryBackend for TestMemoryBackend {
-                unsafe fn alloc_slab(&mut self, slab_size: usize, page_size: usize) -> *mut u8 {
+                type Error = OutOfMemory;
+
+                unsafe fn alloc_slab(
+                    &mut self,
+                    slab_size: usize,
+                    page_size: usize,
+                ) -> Result<*mut u8, Self::Error> {
                     assert_eq!(slab_size, SLAB_SIZE);
                     assert_eq!(page_size, PAGE_SIZE);
                     let layout = Layout::from_size_align(slab_size, page_size).unwrap();
                     let allocated_slab_ptr = alloc(layout);
                     assert!(!allocated_slab_ptr.is_null());
                     self.allocated_slab_addrs.push(allocated_slab_ptr as usize);
-                    allocated_slab_ptr
+                    Ok(allocated_slab_ptr)
                 }
 
                 unsafe fn free_slab(
@@ -1972,12 +2116,12 @@ mod tests {
                     dealloc(slab_ptr, layout);
                 }
 
-                unsafe fn alloc_slab_info(&mut self) -> *mut SlabInfo {
+                unsafe fn alloc_slab_info(&mut self) -> Result<*mut SlabInfo, Self::Error> {
                     let layout = Layout::new::<SlabInfo>();
                     let allocated_ptr: *mut SlabInfo = alloc(layout).cast();
                     assert!(!allocated_ptr.is_null());
                     self.allocated_slab_info_addrs.push(allocated_ptr as usize);
-                    allocated_ptr
+                    Ok(allocated_ptr)
                 }
 
                 unsafe fn free_slab_info(&mut self, slab_info_ptr: *mut SlabInfo) {
@@ -2119,9 +2263,8 @@ mod tests {
 
             // Free remain (23) objects
             assert_eq!(allocated_ptrs.len(), 23);
-            for i in 0..23 {
-                let allocated_ptr = allocated_ptrs[i];
-                cache.free(allocated_ptr);
+            for allocated_ptr in allocated_ptrs.iter() {
+                cache.free(*allocated_ptr);
             }
             assert_eq!(cache.free_slabs_list_occupacy_less_75.iter().count(), 0);
             assert_eq!(cache.free_slabs_list_occupacy_more_75.iter().count(), 0);
@@ -2137,4 +2280,5933 @@ mod tests {
             assert_eq!(cache.statistics.free_objects_number, 0);
         }
     }
+
+    #[test]
+    fn drain_free_slabs() {
+        unsafe {
+            const PAGE_SIZE: usize = 4096;
+            const SLAB_SIZE: usize = 8192;
+
+            let mut cache: Cache<[u64; 32], StdMemoryBackend> = Cache::new(
+                SLAB_SIZE,
+                PAGE_SIZE,
+                ObjectSizeType::Large,
+                StdMemoryBackend::default(),
+            )
+            .unwrap();
+            assert_eq!(cache.objects_per_slab, 32);
+
+            // Nothing to drain
+            assert_eq!(cache.drain_free_slabs(), 0);
+
+            // 2 slabs, both have allocated objects
+            let mut allocated_ptrs = Vec::new();
+            for _ in 0..cache.objects_per_slab + 1 {
+                allocated_ptrs.push(cache.alloc());
+            }
+            assert_eq!(cache.drain_free_slabs(), 0);
+            assert_eq!(cache.memory_backend.allocated_slab_addrs.len(), 2);
+            assert_eq!(cache.statistics.free_slabs_number, 1);
+            assert_eq!(cache.statistics.full_slabs_number, 1);
+
+            for allocated_ptr in allocated_ptrs {
+                cache.free(allocated_ptr);
+            }
+            // Empty slabs are freed by free
+            assert_eq!(cache.drain_free_slabs(), 0);
+            assert!(cache.memory_backend.allocated_slab_addrs.is_empty());
+            assert!(cache.memory_backend.allocated_slab_info_addrs.is_empty());
+            assert!(cache.memory_backend.ht_saved_slab_infos.is_empty());
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "object of another cache")]
+    fn free_into_cache_of_another_type() {
+        unsafe {
+            const PAGE_SIZE: usize = 4096;
+            const SLAB_SIZE: usize = 4096;
+
+            let mut cache_a: Cache<[u64; 4], StdMemoryBackend> = Cache::new(
+                SLAB_SIZE,
+                PAGE_SIZE,
+                ObjectSizeType::Small,
+                StdMemoryBackend::default(),
+            )
+            .unwrap();
+            let mut cache_b: Cache<[u32; 8], StdMemoryBackend> = Cache::new(
+                SLAB_SIZE,
+                PAGE_SIZE,
+                ObjectSizeType::Small,
+                StdMemoryBackend::default(),
+            )
+            .unwrap();
+            assert_eq!(cache_a.object_size(), cache_b.object_size());
+            assert_ne!(cache_a.id, cache_b.id);
+
+            let _allocated_ptr_a = cache_a.alloc();
+            let allocated_ptr_b = cache_b.alloc();
+            cache_a.free(allocated_ptr_b.cast());
+        }
+    }
+
+    #[test]
+    fn capacity() {
+        unsafe {
+            const PAGE_SIZE: usize = 4096;
+            const SLAB_SIZE: usize = 8192;
+
+            let mut cache: Cache<[u64; 8], StdMemoryBackend> = Cache::new(
+                SLAB_SIZE,
+                PAGE_SIZE,
+                ObjectSizeType::Small,
+                StdMemoryBackend::default(),
+            )
+            .unwrap();
+            assert_eq!(cache.capacity(), 0);
+            assert_eq!(cache.remaining_capacity(), 0);
+
+            let allocated_ptr = cache.alloc();
+            assert_eq!(cache.capacity(), cache.objects_per_slab);
+            assert_eq!(cache.remaining_capacity(), cache.objects_per_slab - 1);
+            cache.free(allocated_ptr);
+            assert_eq!(cache.capacity(), 0);
+            assert_eq!(cache.remaining_capacity(), 0);
+
+            // Random test
+            let mut allocated_ptrs = Vec::new();
+            for _ in 0..rand::thread_rng().gen_range(20..=40) {
+                if rand::thread_rng().gen_bool(0.5) {
+                    for _ in 0..rand::thread_rng().gen_range(20..200) {
+                        let allocated_ptr = cache.alloc();
+                        assert!(!allocated_ptr.is_null());
+                        allocated_ptrs.push(allocated_ptr);
+                    }
+                } else {
+                    allocated_ptrs.shuffle(&mut rand::thread_rng());
+                    for _ in 0..rand::thread_rng().gen_range(0..=allocated_ptrs.len()) {
+                        cache.free(allocated_ptrs.pop().unwrap());
+                    }
+                }
+
+                assert_eq!(
+                    cache.capacity(),
+                    cache.memory_backend.allocated_slab_addrs.len() * cache.objects_per_slab
+                );
+                assert_eq!(
+                    cache.capacity(),
+                    allocated_ptrs.len() + cache.remaining_capacity()
+                );
+            }
+
+            for allocated_ptr in allocated_ptrs {
+                cache.free(allocated_ptr);
+            }
+            assert_eq!(cache.capacity(), 0);
+            assert_eq!(cache.remaining_capacity(), 0);
+        }
+    }
+
+    #[test]
+    fn free_less_75_list_sorted_by_occupancy() {
+        unsafe {
+            const PAGE_SIZE: usize = 4096;
+            const SLAB_SIZE: usize = 8192;
+
+            // 32 objects
+            // 75% is 24
+            let mut cache: Cache<[u64; 32], StdMemoryBackend> = Cache::new(
+                SLAB_SIZE,
+                PAGE_SIZE,
+                ObjectSizeType::Large,
+                StdMemoryBackend::default(),
+            )
+            .unwrap();
+            assert_eq!(cache.objects_per_slab, 32);
+
+            let less_75_occupancy = |cache: &Cache<[u64; 32], StdMemoryBackend>| -> Vec<usize> {
+                cache
+                    .free_slabs_list_occupacy_less_75
+                    .iter()
+                    .map(|slab_info| {
+                        cache.objects_per_slab - (*slab_info.data.get()).free_objects_number
+                    })
+                    .collect()
+            };
+
+            // 2 full slabs
+            let mut first_slab_ptrs = Vec::new();
+            let mut second_slab_ptrs = Vec::new();
+            for _ in 0..cache.objects_per_slab {
+                first_slab_ptrs.push(cache.alloc());
+            }
+            for _ in 0..cache.objects_per_slab {
+                second_slab_ptrs.push(cache.alloc());
+            }
+            assert_eq!(cache.full_slabs_list.iter().count(), 2);
+
+            // Both slabs to free (<75), with 20 objects
+            for _ in 0..12 {
+                cache.free(first_slab_ptrs.pop().unwrap());
+            }
+            for _ in 0..12 {
+                cache.free(second_slab_ptrs.pop().unwrap());
+            }
+            assert_eq!(less_75_occupancy(&cache), [20, 20]);
+            let second_slab_info = cache
+                .free_slabs_list_occupacy_less_75
+                .front()
+                .get()
+                .unwrap() as *const SlabInfo;
+
+            // Front slab becomes less occupied and is moved back
+            cache.free(second_slab_ptrs.pop().unwrap());
+            assert_eq!(less_75_occupancy(&cache), [20, 19]);
+            assert_eq!(
+                cache.free_slabs_list_occupacy_less_75.back().get().unwrap() as *const SlabInfo,
+                second_slab_info
+            );
+
+            // Next allocation is taken from the most occupied slab
+            first_slab_ptrs.push(cache.alloc());
+            assert_eq!(less_75_occupancy(&cache), [21, 19]);
+
+            // Random test
+            let mut allocated_ptrs = Vec::new();
+            allocated_ptrs.append(&mut first_slab_ptrs);
+            allocated_ptrs.append(&mut second_slab_ptrs);
+            for _ in 0..rand::thread_rng().gen_range(20..=40) {
+                if rand::thread_rng().gen_bool(0.5) {
+                    for _ in 0..rand::thread_rng().gen_range(20..100) {
+                        allocated_ptrs.push(cache.alloc());
+                    }
+                } else {
+                    allocated_ptrs.shuffle(&mut rand::thread_rng());
+                    for _ in 0..rand::thread_rng().gen_range(0..=allocated_ptrs.len()) {
+                        cache.free(allocated_ptrs.pop().unwrap());
+                        let occupancy = less_75_occupancy(&cache);
+                        assert!(occupancy.windows(2).all(|w| w[0] >= w[1]));
+                    }
+                }
+            }
+
+            for allocated_ptr in allocated_ptrs {
+                cache.free(allocated_ptr);
+            }
+            assert!(cache.memory_backend.allocated_slab_addrs.is_empty());
+        }
+    }
+
+    #[test]
+    fn slab_box() {
+        use core::cell::Cell;
+
+        struct TestObjectType<'a> {
+            drops_counter: &'a Cell<usize>,
+            value: u64,
+        }
+
+        impl Drop for TestObjectType<'_> {
+            fn drop(&mut self) {
+                self.drops_counter.set(self.drops_counter.get() + 1);
+            }
+        }
+
+        const PAGE_SIZE: usize = 4096;
+        const SLAB_SIZE: usize = 4096;
+
+        let drops_counter = Cell::new(0);
+        let mut cache: Cache<TestObjectType, StdMemoryBackend> = Cache::new(
+            SLAB_SIZE,
+            PAGE_SIZE,
+            ObjectSizeType::Small,
+            StdMemoryBackend::default(),
+        )
+        .unwrap();
+
+        {
+            let mut slab_box = cache
+                .alloc_box(TestObjectType {
+                    drops_counter: &drops_counter,
+                    value: 1,
+                })
+                .unwrap();
+            assert_eq!(slab_box.value, 1);
+            slab_box.value = 2;
+            assert_eq!(slab_box.value, 2);
+            assert!(slab_box.as_ptr().is_aligned());
+        }
+        // Value dropped, object freed
+        assert_eq!(drops_counter.get(), 1);
+        assert_eq!(cache.statistics.allocated_objects_number, 0);
+        assert!(cache.memory_backend.allocated_slab_addrs.is_empty());
+
+        // Into raw
+        let object_ptr = cache
+            .alloc_box(TestObjectType {
+                drops_counter: &drops_counter,
+                value: 3,
+            })
+            .unwrap()
+            .into_raw();
+        assert_eq!(drops_counter.get(), 1);
+        assert_eq!(cache.statistics.allocated_objects_number, 1);
+        unsafe {
+            assert_eq!((*object_ptr).value, 3);
+            core::ptr::drop_in_place(object_ptr);
+            cache.free(object_ptr);
+        }
+        assert_eq!(drops_counter.get(), 2);
+        assert_eq!(cache.statistics.allocated_objects_number, 0);
+        assert!(cache.memory_backend.allocated_slab_addrs.is_empty());
+    }
+
+    #[test]
+    fn migrate_to_slab_size() {
+        unsafe {
+            const PAGE_SIZE: usize = 4096;
+
+            // Cache has allocated objects
+            let mut cache: Cache<[u64; 3], StdMemoryBackend> = Cache::new(
+                PAGE_SIZE,
+                PAGE_SIZE,
+                ObjectSizeType::Small,
+                StdMemoryBackend::default(),
+            )
+            .unwrap();
+            let allocated_ptr = cache.alloc();
+            let Err((mut cache, error)) = cache.migrate_to_slab_size(PAGE_SIZE * 2) else {
+                panic!("Migrated cache with allocated objects");
+            };
+            assert_eq!(error, "Cache has allocated objects");
+            assert_eq!(cache.slab_size(), PAGE_SIZE);
+            assert_eq!(cache.statistics.allocated_objects_number, 1);
+            cache.free(allocated_ptr);
+
+            // Invalid slab size
+            let Err((cache, error)) = cache.migrate_to_slab_size(PAGE_SIZE * 3) else {
+                panic!("Migrated cache to invalid slab size");
+            };
+            assert_eq!(error, "Slab size is not power of two");
+            assert_eq!(cache.slab_size(), PAGE_SIZE);
+
+            let Ok(mut cache) = cache.migrate_to_slab_size(PAGE_SIZE * 4) else {
+                panic!("Failed to migrate cache");
+            };
+            assert_eq!(cache.slab_size(), PAGE_SIZE * 4);
+            assert_eq!(cache.page_size(), PAGE_SIZE);
+            assert_eq!(cache.object_size_type(), ObjectSizeType::Small);
+            assert_eq!(
+                cache.objects_per_slab(),
+                (PAGE_SIZE * 4 - size_of::<SlabInfo>()) / 24
+            );
+
+            // Backend moved into new cache
+            let allocated_ptr = cache.alloc();
+            assert_eq!(cache.memory_backend.allocated_slab_addrs.len(), 1);
+            assert_eq!(cache.memory_backend.ht_saved_slab_infos.len(), 1);
+            cache.free(allocated_ptr);
+            assert!(cache.memory_backend.allocated_slab_addrs.is_empty());
+            assert!(cache.memory_backend.ht_saved_slab_infos.is_empty());
+        }
+    }
+
+    #[test]
+    fn small_slab_info_does_not_overlap_objects() {
+        unsafe {
+            const PAGE_SIZE: usize = 4096;
+            const SLAB_SIZE: usize = 4096;
+
+            #[repr(align(64))]
+            struct TestObjectType64 {
+                #[allow(unused)]
+                a: [u8; 64],
+            }
+
+            let mut cache: Cache<TestObjectType64, StdMemoryBackend> = Cache::new(
+                SLAB_SIZE,
+                PAGE_SIZE,
+                ObjectSizeType::Small,
+                StdMemoryBackend::default(),
+            )
+            .unwrap();
+
+            let mut allocated_ptrs = Vec::new();
+            for _ in 0..cache.objects_per_slab {
+                let allocated_ptr = cache.alloc();
+                assert!(allocated_ptr.is_aligned());
+                allocated_ptrs.push(allocated_ptr);
+            }
+            assert_eq!(cache.memory_backend.allocated_slab_addrs.len(), 1);
+            let slab_ptr = cache.memory_backend.allocated_slab_addrs[0] as *mut u8;
+            let slab_info_addr = calculate_slab_info_addr_in_small_object_cache(
+                slab_ptr,
+                SLAB_SIZE,
+                SmallInfoPlacement::Tail,
+            );
+            for allocated_ptr in allocated_ptrs.iter() {
+                let object_addr = *allocated_ptr as usize;
+                assert!(object_addr >= slab_ptr as usize);
+                assert!(object_addr + size_of::<TestObjectType64>() <= slab_info_addr);
+            }
+
+            for allocated_ptr in allocated_ptrs {
+                cache.free(allocated_ptr);
+            }
+            assert!(cache.memory_backend.allocated_slab_addrs.is_empty());
+        }
+    }
+
+    #[test]
+    fn zero_objects() {
+        unsafe {
+            const PAGE_SIZE: usize = 4096;
+            const SLAB_SIZE: usize = 8192;
+
+            // Fills allocated slabs with garbage, counts zeroed slab allocations
+            #[derive(Default)]
+            struct TestMemoryBackend {
+                std_memory_backend: StdMemoryBackend,
+                zeroed_slabs_number: usize,
+            }
+
+            impl MemoryBackend for TestMemoryBackend {
+                type Error = OutOfMemory;
+
+                unsafe fn alloc_slab(
+                    &mut self,
+                    slab_size: usize,
+                    page_size: usize,
+                ) -> Result<*mut u8, Self::Error> {
+                    let slab_ptr = self.std_memory_backend.alloc_slab(slab_size, page_size)?;
+                    slab_ptr.write_bytes(0xAA, slab_size);
+                    Ok(slab_ptr)
+                }
+
+                unsafe fn alloc_slab_zeroed(
+                    &mut self,
+                    slab_size: usize,
+                    page_size: usize,
+                ) -> Result<*mut u8, Self::Error> {
+                    self.zeroed_slabs_number += 1;
+                    let slab_ptr = self.alloc_slab(slab_size, page_size)?;
+                    slab_ptr.write_bytes(0, slab_size);
+                    Ok(slab_ptr)
+                }
+
+                unsafe fn free_slab(
+                    &mut self,
+                    slab_ptr: *mut u8,
+                    slab_size: usize,
+                    page_size: usize,
+                ) {
+                    self.std_memory_backend
+                        .free_slab(slab_ptr, slab_size, page_size);
+                }
+
+                unsafe fn alloc_slab_info(&mut self) -> Result<*mut SlabInfo, Self::Error> {
+                    self.std_memory_backend.alloc_slab_info()
+                }
+
+                unsafe fn free_slab_info(&mut self, slab_info_ptr: *mut SlabInfo) {
+                    self.std_memory_backend.free_slab_info(slab_info_ptr);
+                }
+
+                unsafe fn save_slab_info_ptr(
+                    &mut self,
+                    object_page_addr: usize,
+                    slab_info_ptr: *mut SlabInfo,
+                ) {
+                    self.std_memory_backend
+                        .save_slab_info_ptr(object_page_addr, slab_info_ptr);
+                }
+
+                unsafe fn get_slab_info_ptr(&mut self, object_page_addr: usize) -> *mut SlabInfo {
+                    self.std_memory_backend.get_slab_info_ptr(object_page_addr)
+                }
+
+                unsafe fn delete_slab_info_ptr(&mut self, page_addr: usize) {
+                    self.std_memory_backend.delete_slab_info_ptr(page_addr);
+                }
+            }
+
+            for object_size_type in [ObjectSizeType::Small, ObjectSizeType::Large] {
+                let mut cache: Cache<[u8; 48], TestMemoryBackend> = Cache::with_config(
+                    SLAB_SIZE,
+                    PAGE_SIZE,
+                    object_size_type,
+                    TestMemoryBackend::default(),
+                    CacheConfig {
+                        zero_objects: true,
+                        ..Default::default()
+                    },
+                )
+                .unwrap();
+                assert!(cache.config().zero_objects);
+
+                let mut allocated_ptrs = Vec::new();
+                for _ in 0..cache.objects_per_slab * 2 {
+                    let allocated_ptr = cache.alloc();
+                    assert_eq!(*allocated_ptr, [0; 48]);
+                    *allocated_ptr = [0xBB; 48];
+                    allocated_ptrs.push(allocated_ptr);
+                }
+                assert_eq!(cache.memory_backend.zeroed_slabs_number, 2);
+
+                // Random test
+                for _ in 0..rand::thread_rng().gen_range(20..=40) {
+                    if rand::thread_rng().gen_bool(0.5) {
+                        for _ in 0..rand::thread_rng().gen_range(20..200) {
+                            let allocated_ptr = cache.alloc();
+                            assert_eq!(*allocated_ptr, [0; 48]);
+                            *allocated_ptr = [0xBB; 48];
+                            allocated_ptrs.push(allocated_ptr);
+                        }
+                    } else {
+                        allocated_ptrs.shuffle(&mut rand::thread_rng());
+                        for _ in 0..rand::thread_rng().gen_range(0..=allocated_ptrs.len()) {
+                            cache.free(allocated_ptrs.pop().unwrap());
+                        }
+                    }
+                }
+
+                for allocated_ptr in allocated_ptrs {
+                    cache.free(allocated_ptr);
+                }
+                assert!(cache
+                    .memory_backend
+                    .std_memory_backend
+                    .allocated_slab_addrs
+                    .is_empty());
+            }
+        }
+    }
+
+    #[test]
+    fn slab_info_lookup_cache() {
+        unsafe {
+            const PAGE_SIZE: usize = 4096;
+            const SLAB_SIZE: usize = 8192;
+
+            for object_size_type in [ObjectSizeType::Small, ObjectSizeType::Large] {
+                let mut cache: Cache<[u64; 4], StdMemoryBackend> = Cache::with_config(
+                    SLAB_SIZE,
+                    PAGE_SIZE,
+                    object_size_type,
+                    StdMemoryBackend::default(),
+                    CacheConfig {
+                        slab_info_lookup_cache: true,
+                        ..Default::default()
+                    },
+                )
+                .unwrap();
+
+                // Objects of the first page of the first slab
+                let objects_per_page = PAGE_SIZE / cache.object_size;
+                let mut allocated_ptrs = Vec::new();
+                for _ in 0..cache.objects_per_slab + 1 {
+                    allocated_ptrs.push(cache.alloc());
+                }
+                let first_page_addr = cache.memory_backend.allocated_slab_addrs[0];
+                let mut first_page_ptrs = Vec::new();
+                allocated_ptrs.retain(|allocated_ptr| {
+                    if align_down(*allocated_ptr as usize, PAGE_SIZE) == first_page_addr {
+                        first_page_ptrs.push(*allocated_ptr);
+                        false
+                    } else {
+                        true
+                    }
+                });
+                assert_eq!(first_page_ptrs.len(), objects_per_page);
+
+                // Only the first free calls memory backend
+                for allocated_ptr in first_page_ptrs {
+                    cache.free(allocated_ptr);
+                }
+                assert_eq!(cache.memory_backend.get_slab_info_ptr_calls, 1);
+
+                // Random test, slab addresses are reused by the allocator
+                for _ in 0..rand::thread_rng().gen_range(20..=40) {
+                    if rand::thread_rng().gen_bool(0.5) {
+                        for _ in 0..rand::thread_rng().gen_range(20..400) {
+                            allocated_ptrs.push(cache.alloc());
+                        }
+                    } else {
+                        allocated_ptrs.shuffle(&mut rand::thread_rng());
+                        for _ in 0..rand::thread_rng().gen_range(0..=allocated_ptrs.len()) {
+                            cache.free(allocated_ptrs.pop().unwrap());
+                        }
+                    }
+                }
+
+                for allocated_ptr in allocated_ptrs {
+                    cache.free(allocated_ptr);
+                }
+                assert!(cache.memory_backend.allocated_slab_addrs.is_empty());
+                assert!(cache.memory_backend.ht_saved_slab_infos.is_empty());
+                assert!(cache
+                    .slab_info_lookup_cache
+                    .entries
+                    .iter()
+                    .all(|entry| *entry == (0, 0)));
+            }
+        }
+    }
+
+    #[test]
+    fn spread_distribution_policy() {
+        unsafe {
+            const PAGE_SIZE: usize = 4096;
+            const SLAB_SIZE: usize = 8192;
+
+            // 32 objects
+            let mut cache: Cache<[u64; 32], StdMemoryBackend> = Cache::with_config(
+                SLAB_SIZE,
+                PAGE_SIZE,
+                ObjectSizeType::Large,
+                StdMemoryBackend::default(),
+                CacheConfig {
+                    distribution_policy: DistributionPolicy::Spread,
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+            assert_eq!(cache.objects_per_slab, 32);
+            let slab_index_of = |cache: &Cache<[u64; 32], StdMemoryBackend>,
+                                 ptr: *mut [u64; 32]| {
+                cache
+                    .memory_backend
+                    .allocated_slab_addrs
+                    .iter()
+                    .position(|slab_addr| {
+                        (*slab_addr..*slab_addr + SLAB_SIZE).contains(&(ptr as usize))
+                    })
+                    .unwrap()
+            };
+
+            // 3 full slabs
+            let mut allocated_ptrs = Vec::new();
+            for _ in 0..cache.objects_per_slab * 3 {
+                allocated_ptrs.push(cache.alloc());
+            }
+            assert_eq!(cache.full_slabs_list.iter().count(), 3);
+
+            // Free half of each slab
+            allocated_ptrs.sort_by_key(|ptr| slab_index_of(&cache, *ptr));
+            let mut remaining_ptrs = Vec::new();
+            for (i, allocated_ptr) in allocated_ptrs.into_iter().enumerate() {
+                if i % 2 == 0 {
+                    cache.free(allocated_ptr);
+                } else {
+                    remaining_ptrs.push(allocated_ptr);
+                }
+            }
+            let mut allocated_ptrs = remaining_ptrs;
+            assert_eq!(cache.free_slabs_list_occupacy_less_75.iter().count(), 3);
+
+            // Consecutive allocations are taken from different slabs
+            let mut slab_indexes = Vec::new();
+            for _ in 0..12 {
+                let allocated_ptr = cache.alloc();
+                slab_indexes.push(slab_index_of(&cache, allocated_ptr));
+                allocated_ptrs.push(allocated_ptr);
+            }
+            for window in slab_indexes.windows(3) {
+                let hs: HashSet<usize> = HashSet::from_iter(window.iter().copied());
+                assert_eq!(hs.len(), 3);
+            }
+
+            // Random test
+            for _ in 0..rand::thread_rng().gen_range(20..=40) {
+                if rand::thread_rng().gen_bool(0.5) {
+                    for _ in 0..rand::thread_rng().gen_range(20..100) {
+                        allocated_ptrs.push(cache.alloc());
+                    }
+                } else {
+                    allocated_ptrs.shuffle(&mut rand::thread_rng());
+                    for _ in 0..rand::thread_rng().gen_range(0..=allocated_ptrs.len()) {
+                        cache.free(allocated_ptrs.pop().unwrap());
+                    }
+                }
+                let hs: HashSet<_> = HashSet::from_iter(allocated_ptrs.clone());
+                assert_eq!(hs.len(), allocated_ptrs.len());
+                assert_eq!(
+                    cache.statistics.free_slabs_number,
+                    cache.free_slabs_list_occupacy_less_75.iter().count()
+                        + cache.free_slabs_list_occupacy_more_75.iter().count()
+                );
+                assert_eq!(
+                    cache.statistics.full_slabs_number,
+                    cache.full_slabs_list.iter().count()
+                );
+            }
+
+            for allocated_ptr in allocated_ptrs {
+                cache.free(allocated_ptr);
+            }
+            assert!(cache.memory_backend.allocated_slab_addrs.is_empty());
+        }
+    }
+
+    #[test]
+    fn slab_free_count() {
+        unsafe {
+            const PAGE_SIZE: usize = 4096;
+
+            for (slab_size, object_size_type) in [
+                (PAGE_SIZE, ObjectSizeType::Small),
+                (PAGE_SIZE * 2, ObjectSizeType::Small),
+                (PAGE_SIZE * 2, ObjectSizeType::Large),
+            ] {
+                let mut cache: Cache<[u64; 16], StdMemoryBackend> = Cache::new(
+                    slab_size,
+                    PAGE_SIZE,
+                    object_size_type,
+                    StdMemoryBackend::default(),
+                )
+                .unwrap();
+
+                let first_ptr = cache.alloc();
+                assert_eq!(cache.slab_free_count(first_ptr), cache.objects_per_slab - 1);
+                let mut allocated_ptrs = vec![first_ptr];
+                for i in 1..cache.objects_per_slab {
+                    allocated_ptrs.push(cache.alloc());
+                    assert_eq!(
+                        cache.slab_free_count(first_ptr),
+                        cache.objects_per_slab - 1 - i
+                    );
+                }
+
+                // Second slab
+                let second_slab_ptr = cache.alloc();
+                assert_eq!(
+                    cache.slab_free_count(second_slab_ptr),
+                    cache.objects_per_slab - 1
+                );
+                assert_eq!(cache.slab_free_count(first_ptr), 0);
+
+                cache.free(allocated_ptrs.pop().unwrap());
+                assert_eq!(cache.slab_free_count(first_ptr), 1);
+
+                cache.free(second_slab_ptr);
+                for allocated_ptr in allocated_ptrs {
+                    cache.free(allocated_ptr);
+                }
+                assert!(cache.memory_backend.allocated_slab_addrs.is_empty());
+            }
+        }
+    }
+
+    #[test]
+    fn for_each_free_slot() {
+        unsafe {
+            const PAGE_SIZE: usize = 4096;
+            const SLAB_SIZE: usize = 8192;
+
+            let mut cache: Cache<[u8; 64], StdMemoryBackend> = Cache::new(
+                SLAB_SIZE,
+                PAGE_SIZE,
+                ObjectSizeType::Small,
+                StdMemoryBackend::default(),
+            )
+            .unwrap();
+
+            let mut free_slots_number = 0;
+            cache.for_each_free_slot(|_| free_slots_number += 1);
+            assert_eq!(free_slots_number, 0);
+
+            let mut allocated_ptrs = Vec::new();
+            for _ in 0..cache.objects_per_slab * 3 {
+                let allocated_ptr = cache.alloc();
+                *allocated_ptr = [0xAA; 64];
+                allocated_ptrs.push(allocated_ptr);
+            }
+            allocated_ptrs.shuffle(&mut rand::thread_rng());
+            for _ in 0..cache.objects_per_slab {
+                cache.free(allocated_ptrs.pop().unwrap());
+            }
+
+            // Scrub free objects, except free list links
+            let link_size = size_of::<usize>() * 2;
+            let mut free_slots = HashSet::new();
+            cache.for_each_free_slot(|free_slot_ptr| {
+                assert!(free_slot_ptr.is_aligned());
+                assert!(!allocated_ptrs.contains(&free_slot_ptr));
+                assert!(free_slots.insert(free_slot_ptr));
+                free_slot_ptr
+                    .cast::<u8>()
+                    .add(link_size)
+                    .write_bytes(0, 64 - link_size);
+            });
+            assert_eq!(free_slots.len(), cache.statistics.free_objects_number);
+
+            // Free objects are still usable
+            for _ in 0..free_slots.len() {
+                let allocated_ptr = cache.alloc();
+                assert!(free_slots.contains(&allocated_ptr));
+                assert_eq!((&*allocated_ptr)[link_size..], [0; 64][link_size..]);
+                allocated_ptrs.push(allocated_ptr);
+            }
+            for allocated_ptr in allocated_ptrs {
+                cache.free(allocated_ptr);
+            }
+            assert!(cache.memory_backend.allocated_slab_addrs.is_empty());
+        }
+    }
+
+    #[test]
+    fn no_reclaim() {
+        unsafe {
+            const PAGE_SIZE: usize = 4096;
+            const SLAB_SIZE: usize = 8192;
+
+            for object_size_type in [ObjectSizeType::Small, ObjectSizeType::Large] {
+                let mut cache: Cache<[u64; 32], StdMemoryBackend> = Cache::with_config(
+                    SLAB_SIZE,
+                    PAGE_SIZE,
+                    object_size_type,
+                    StdMemoryBackend::default(),
+                    CacheConfig {
+                        reclaim: false,
+                        ..Default::default()
+                    },
+                )
+                .unwrap();
+                assert!(CacheConfig::default().reclaim);
+
+                // Random test
+                let mut allocated_ptrs = Vec::new();
+                let mut max_slabs_number = 0;
+                for _ in 0..rand::thread_rng().gen_range(20..=40) {
+                    if rand::thread_rng().gen_bool(0.5) {
+                        for _ in 0..rand::thread_rng().gen_range(20..100) {
+                            allocated_ptrs.push(cache.alloc());
+                        }
+                    } else {
+                        allocated_ptrs.shuffle(&mut rand::thread_rng());
+                        for _ in 0..rand::thread_rng().gen_range(0..=allocated_ptrs.len()) {
+                            cache.free(allocated_ptrs.pop().unwrap());
+                        }
+                    }
+                    // Slabs are never freed
+                    let slabs_number = cache.memory_backend.allocated_slab_addrs.len();
+                    assert!(slabs_number >= max_slabs_number);
+                    max_slabs_number = slabs_number;
+                    assert_eq!(
+                        cache.capacity(),
+                        allocated_ptrs.len() + cache.remaining_capacity()
+                    );
+                    assert_eq!(cache.capacity(), slabs_number * cache.objects_per_slab);
+                }
+
+                for allocated_ptr in allocated_ptrs {
+                    cache.free(allocated_ptr);
+                }
+                let slabs_number = cache.memory_backend.allocated_slab_addrs.len();
+                assert_eq!(slabs_number, max_slabs_number);
+                assert_eq!(cache.statistics.free_slabs_number, slabs_number);
+                assert_eq!(
+                    cache.statistics.free_objects_number,
+                    slabs_number * cache.objects_per_slab
+                );
+
+                // Empty slabs are reused
+                let allocated_ptr = cache.alloc();
+                assert_eq!(
+                    cache.memory_backend.allocated_slab_addrs.len(),
+                    slabs_number
+                );
+                cache.free(allocated_ptr);
+
+                // Explicit drain
+                assert_eq!(
+                    cache.drain_free_slabs(),
+                    slabs_number * (SLAB_SIZE / PAGE_SIZE)
+                );
+                assert!(cache.memory_backend.allocated_slab_addrs.is_empty());
+                assert!(cache.memory_backend.allocated_slab_info_addrs.is_empty());
+                assert!(cache.memory_backend.ht_saved_slab_infos.is_empty());
+                assert_eq!(cache.statistics.free_slabs_number, 0);
+                assert_eq!(cache.statistics.free_objects_number, 0);
+            }
+        }
+    }
+
+    #[test]
+    fn alloc_tracked() {
+        unsafe {
+            const PAGE_SIZE: usize = 4096;
+            const SLAB_SIZE: usize = 4096;
+
+            let mut cache: Cache<[u64; 32], StdMemoryBackend> = Cache::new(
+                SLAB_SIZE,
+                PAGE_SIZE,
+                ObjectSizeType::Small,
+                StdMemoryBackend::default(),
+            )
+            .unwrap();
+
+            let mut allocated_ptrs = Vec::new();
+            for i in 0..cache.objects_per_slab * 3 {
+                let (allocated_ptr, slab_allocated) = cache.alloc_tracked();
+                assert!(!allocated_ptr.is_null());
+                assert_eq!(slab_allocated, i % cache.objects_per_slab == 0);
+                allocated_ptrs.push(allocated_ptr);
+            }
+            assert_eq!(cache.memory_backend.allocated_slab_addrs.len(), 3);
+
+            cache.free(allocated_ptrs.pop().unwrap());
+            let (allocated_ptr, slab_allocated) = cache.alloc_tracked();
+            assert!(!slab_allocated);
+            allocated_ptrs.push(allocated_ptr);
+
+            for allocated_ptr in allocated_ptrs {
+                cache.free(allocated_ptr);
+            }
+            assert!(cache.memory_backend.allocated_slab_addrs.is_empty());
+        }
+    }
+
+    #[test]
+    fn cache_id() {
+        unsafe {
+            const PAGE_SIZE: usize = 4096;
+
+            let new_cache = || -> Cache<[u64; 4], StdMemoryBackend> {
+                Cache::new(
+                    PAGE_SIZE,
+                    PAGE_SIZE,
+                    ObjectSizeType::Small,
+                    StdMemoryBackend::default(),
+                )
+                .unwrap()
+            };
+            let mut cache_a = new_cache();
+            let cache_b = new_cache();
+            assert_ne!(cache_a.cache_id(), cache_b.cache_id());
+            assert!(cache_a == cache_a);
+            assert!(cache_a != cache_b);
+
+            // Id doesn't change when cache is moved after allocation
+            let cache_a_id = cache_a.cache_id();
+            let allocated_ptr = cache_a.alloc();
+            let mut caches = [cache_b, cache_a];
+            assert_eq!(caches[1].cache_id(), cache_a_id);
+            caches[1].free(allocated_ptr);
+            assert!(caches[1].memory_backend.allocated_slab_addrs.is_empty());
+        }
+    }
+
+    #[test]
+    fn cache_moved_after_allocation() {
+        unsafe {
+            const PAGE_SIZE: usize = 4096;
+
+            struct Subsystem {
+                cache: Cache<[u64; 8], StdMemoryBackend>,
+                allocated_ptrs: Vec<*mut [u64; 8]>,
+            }
+
+            // Warm cache in a function and move it out
+            fn warm_cache(
+                slab_size: usize,
+                object_size_type: ObjectSizeType,
+            ) -> (Cache<[u64; 8], StdMemoryBackend>, Vec<*mut [u64; 8]>) {
+                let mut cache = Cache::new(
+                    slab_size,
+                    PAGE_SIZE,
+                    object_size_type,
+                    StdMemoryBackend::default(),
+                )
+                .unwrap();
+                let mut allocated_ptrs = Vec::new();
+                for _ in 0..1000 {
+                    allocated_ptrs.push(unsafe { cache.alloc() });
+                }
+                (cache, allocated_ptrs)
+            }
+
+            for (slab_size, object_size_type) in [
+                (PAGE_SIZE, ObjectSizeType::Small),
+                (PAGE_SIZE * 2, ObjectSizeType::Small),
+                (PAGE_SIZE * 2, ObjectSizeType::Large),
+            ] {
+                let (cache, allocated_ptrs) = warm_cache(slab_size, object_size_type);
+                let mut subsystem = Subsystem {
+                    cache,
+                    allocated_ptrs,
+                };
+
+                // Random test
+                for _ in 0..rand::thread_rng().gen_range(20..=40) {
+                    if rand::thread_rng().gen_bool(0.5) {
+                        for _ in 0..rand::thread_rng().gen_range(20..200) {
+                            let allocated_ptr = subsystem.cache.alloc();
+                            subsystem.allocated_ptrs.push(allocated_ptr);
+                        }
+                    } else {
+                        subsystem.allocated_ptrs.shuffle(&mut rand::thread_rng());
+                        for _ in 0..rand::thread_rng().gen_range(0..=subsystem.allocated_ptrs.len())
+                        {
+                            let allocated_ptr = subsystem.allocated_ptrs.pop().unwrap();
+                            subsystem.cache.free(allocated_ptr);
+                        }
+                    }
+                }
+
+                // Move again
+                let Subsystem {
+                    mut cache,
+                    allocated_ptrs,
+                } = subsystem;
+                for allocated_ptr in allocated_ptrs {
+                    cache.free(allocated_ptr);
+                }
+                assert!(cache.memory_backend.allocated_slab_addrs.is_empty());
+                assert!(cache.memory_backend.allocated_slab_info_addrs.is_empty());
+                assert!(cache.memory_backend.ht_saved_slab_infos.is_empty());
+            }
+        }
+    }
+
+    #[test]
+    fn reserve_contiguous() {
+        unsafe {
+            const PAGE_SIZE: usize = 4096;
+            const SLAB_SIZE: usize = 4096;
+            const ARENA_SLABS_NUMBER: usize = 40;
+
+            // Carves slabs from one contiguous arena
+            struct TestMemoryBackend {
+                arena_addr: usize,
+                allocated_slabs_number: usize,
+                freed_slab_addrs: Vec<usize>,
+                alloc_slabs_calls: usize,
+            }
+
+            impl MemoryBackend for TestMemoryBackend {
+                type Error = OutOfMemory;
+
+                unsafe fn alloc_slab(
+                    &mut self,
+                    slab_size: usize,
+                    page_size: usize,
+                ) -> Result<*mut u8, Self::Error> {
+                    self.alloc_slabs(1, slab_size, page_size)
+                }
+
+                unsafe fn alloc_slabs(
+                    &mut self,
+                    count: usize,
+                    slab_size: usize,
+                    _page_size: usize,
+                ) -> Result<*mut u8, Self::Error> {
+                    self.alloc_slabs_calls += 1;
+                    if count > ARENA_SLABS_NUMBER - self.allocated_slabs_number {
+                        return Err(OutOfMemory);
+                    }
+                    let region_addr = self.arena_addr + self.allocated_slabs_number * slab_size;
+                    self.allocated_slabs_number += count;
+                    Ok(region_addr as *mut u8)
+                }
+
+                unsafe fn free_slab(
+                    &mut self,
+                    slab_ptr: *mut u8,
+                    _slab_size: usize,
+                    _page_size: usize,
+                ) {
+                    self.freed_slab_addrs.push(slab_ptr as usize);
+                }
+
+                unsafe fn alloc_slab_info(&mut self) -> Result<*mut SlabInfo, Self::Error> {
+                    unreachable!();
+                }
+
+                unsafe fn free_slab_info(&mut self, _slab_info_ptr: *mut SlabInfo) {
+                    unreachable!();
+                }
+
+                unsafe fn save_slab_info_ptr(
+                    &mut self,
+                    _object_page_addr: usize,
+                    _slab_info_ptr: *mut SlabInfo,
+                ) {
+                    unreachable!();
+                }
+
+                unsafe fn get_slab_info_ptr(&mut self, _object_page_addr: usize) -> *mut SlabInfo {
+                    unreachable!();
+                }
+
+                unsafe fn delete_slab_info_ptr(&mut self, _page_addr: usize) {
+                    unreachable!();
+                }
+            }
+
+            let arena_layout =
+                Layout::from_size_align(SLAB_SIZE * ARENA_SLABS_NUMBER, PAGE_SIZE).unwrap();
+            let arena_ptr = alloc(arena_layout);
+            assert!(!arena_ptr.is_null());
+
+            let mut cache: Cache<[u64; 16], TestMemoryBackend> = Cache::new(
+                SLAB_SIZE,
+                PAGE_SIZE,
+                ObjectSizeType::Small,
+                TestMemoryBackend {
+                    arena_addr: arena_ptr as usize,
+                    allocated_slabs_number: 0,
+                    freed_slab_addrs: Vec::new(),
+                    alloc_slabs_calls: 0,
+                },
+            )
+            .unwrap();
+
+            // One backend call
+            assert_eq!(cache.reserve_contiguous(8), 8);
+            assert_eq!(cache.memory_backend.alloc_slabs_calls, 1);
+            assert_eq!(cache.statistics.free_slabs_number, 8);
+            assert_eq!(
+                cache.statistics.free_objects_number,
+                8 * cache.objects_per_slab
+            );
+            assert_eq!(cache.capacity(), 8 * cache.objects_per_slab);
+
+            // Allocations don't call backend
+            let mut allocated_ptrs = Vec::new();
+            for _ in 0..8 * cache.objects_per_slab {
+                let allocated_ptr = cache.alloc();
+                assert!((arena_ptr as usize..arena_ptr as usize + 8 * SLAB_SIZE)
+                    .contains(&(allocated_ptr as usize)));
+                allocated_ptrs.push(allocated_ptr);
+            }
+            assert_eq!(cache.memory_backend.alloc_slabs_calls, 1);
+            assert_eq!(cache.statistics.full_slabs_number, 8);
+            let hs: HashSet<_> = HashSet::from_iter(allocated_ptrs.clone());
+            assert_eq!(hs.len(), allocated_ptrs.len());
+
+            // Reserve more than the backend has, the region can't be allocated and slabs are allocated one by one
+            assert_eq!(cache.reserve_contiguous(100), ARENA_SLABS_NUMBER - 8);
+            assert_eq!(
+                cache.memory_backend.alloc_slabs_calls,
+                1 + 1 + (ARENA_SLABS_NUMBER - 8) + 1
+            );
+            assert_eq!(cache.statistics.free_slabs_number, ARENA_SLABS_NUMBER - 8);
+            assert_eq!(cache.reserve_contiguous(1), 0);
+
+            for allocated_ptr in allocated_ptrs {
+                cache.free(allocated_ptr);
+            }
+            assert_eq!(cache.memory_backend.freed_slab_addrs.len(), 8);
+            assert_eq!(cache.drain_free_slabs(), ARENA_SLABS_NUMBER - 8);
+            assert_eq!(
+                cache.memory_backend.freed_slab_addrs.len(),
+                ARENA_SLABS_NUMBER
+            );
+
+            dealloc(arena_ptr, arena_layout);
+        }
+    }
+
+    #[test]
+    fn overhead_bytes() {
+        const PAGE_SIZE: usize = 4096;
+
+        // 1 object, the rest is lost
+        let cache: Cache<[u8; 2048], StdMemoryBackend> = Cache::new(
+            PAGE_SIZE,
+            PAGE_SIZE,
+            ObjectSizeType::Small,
+            StdMemoryBackend::default(),
+        )
+        .unwrap();
+        assert_eq!(cache.objects_per_slab(), 1);
+        assert_eq!(cache.overhead_bytes(), 2048);
+
+        // SlabInfo and tail
+        let cache: Cache<[u8; 32], StdMemoryBackend> = Cache::new(
+            PAGE_SIZE,
+            PAGE_SIZE,
+            ObjectSizeType::Small,
+            StdMemoryBackend::default(),
+        )
+        .unwrap();
+        assert_eq!(
+            cache.overhead_bytes(),
+            PAGE_SIZE - (PAGE_SIZE - size_of::<SlabInfo>()) / 32 * 32
+        );
+
+        // No overhead
+        let cache: Cache<[u8; 2048], StdMemoryBackend> = Cache::new(
+            PAGE_SIZE,
+            PAGE_SIZE,
+            ObjectSizeType::Large,
+            StdMemoryBackend::default(),
+        )
+        .unwrap();
+        assert_eq!(cache.overhead_bytes(), 0);
+
+        // Max overhead
+        let config = CacheConfig {
+            max_overhead_percent: Some(25),
+            ..Default::default()
+        };
+        let result: Result<Cache<[u8; 2048], StdMemoryBackend>, _> = Cache::with_config(
+            PAGE_SIZE,
+            PAGE_SIZE,
+            ObjectSizeType::Small,
+            StdMemoryBackend::default(),
+            config,
+        );
+        assert_eq!(
+            result.err(),
+            Some("Slab overhead exceeds max_overhead_percent")
+        );
+        let result: Result<Cache<[u8; 2048], StdMemoryBackend>, _> = Cache::with_config(
+            PAGE_SIZE * 4,
+            PAGE_SIZE,
+            ObjectSizeType::Small,
+            StdMemoryBackend::default(),
+            config,
+        );
+        assert_eq!(result.ok().unwrap().overhead_bytes(), 2048);
+    }
+
+    #[test]
+    fn absorb() {
+        unsafe {
+            use alloc::rc::Rc;
+            use core::cell::RefCell;
+
+            const PAGE_SIZE: usize = 4096;
+
+            // Memory backends of both caches share the same storage
+            #[derive(Clone)]
+            struct TestMemoryBackend(Rc<RefCell<StdMemoryBackend>>);
+
+            impl MemoryBackend for TestMemoryBackend {
+                type Error = OutOfMemory;
+
+                unsafe fn alloc_slab(
+                    &mut self,
+                    slab_size: usize,
+                    page_size: usize,
+                ) -> Result<*mut u8, Self::Error> {
+                    self.0.borrow_mut().alloc_slab(slab_size, page_size)
+                }
+
+                unsafe fn free_slab(
+                    &mut self,
+                    slab_ptr: *mut u8,
+                    slab_size: usize,
+                    page_size: usize,
+                ) {
+                    self.0
+                        .borrow_mut()
+                        .free_slab(slab_ptr, slab_size, page_size);
+                }
+
+                unsafe fn alloc_slab_info(&mut self) -> Result<*mut SlabInfo, Self::Error> {
+                    self.0.borrow_mut().alloc_slab_info()
+                }
+
+                unsafe fn free_slab_info(&mut self, slab_info_ptr: *mut SlabInfo) {
+                    self.0.borrow_mut().free_slab_info(slab_info_ptr);
+                }
+
+                unsafe fn save_slab_info_ptr(
+                    &mut self,
+                    object_page_addr: usize,
+                    slab_info_ptr: *mut SlabInfo,
+                ) {
+                    self.0
+                        .borrow_mut()
+                        .save_slab_info_ptr(object_page_addr, slab_info_ptr);
+                }
+
+                unsafe fn get_slab_info_ptr(&mut self, object_page_addr: usize) -> *mut SlabInfo {
+                    self.0.borrow_mut().get_slab_info_ptr(object_page_addr)
+                }
+
+                unsafe fn delete_slab_info_ptr(&mut self, page_addr: usize) {
+                    self.0.borrow_mut().delete_slab_info_ptr(page_addr);
+                }
+            }
+
+            for (slab_size, object_size_type) in [
+                (PAGE_SIZE, ObjectSizeType::Small),
+                (PAGE_SIZE * 2, ObjectSizeType::Small),
+                (PAGE_SIZE * 2, ObjectSizeType::Large),
+            ] {
+                let memory_backend =
+                    TestMemoryBackend(Rc::new(RefCell::new(StdMemoryBackend::default())));
+                let new_cache = || -> Cache<[u64; 32], TestMemoryBackend> {
+                    Cache::new(
+                        slab_size,
+                        PAGE_SIZE,
+                        object_size_type,
+                        memory_backend.clone(),
+                    )
+                    .unwrap()
+                };
+                let mut cache = new_cache();
+                let mut other_cache = new_cache();
+
+                // Full, free (>75) and free (<75) slabs in both caches
+                let mut allocated_ptrs = Vec::new();
+                for c in [&mut cache, &mut other_cache] {
+                    let objects_per_slab = c.objects_per_slab;
+                    let mut ptrs = Vec::new();
+                    for _ in 0..objects_per_slab * 3 {
+                        ptrs.push(c.alloc());
+                    }
+                    for _ in 0..objects_per_slab / 8 {
+                        c.free(ptrs.pop().unwrap());
+                    }
+                    for _ in 0..objects_per_slab / 2 {
+                        c.free(ptrs.pop().unwrap());
+                    }
+                    allocated_ptrs.append(&mut ptrs);
+                }
+                let statistics = cache.cache_statistics();
+                let other_statistics = other_cache.cache_statistics();
+
+                cache.absorb(other_cache);
+                assert_eq!(
+                    cache.statistics.free_slabs_number,
+                    statistics.free_slabs_number + other_statistics.free_slabs_number
+                );
+                assert_eq!(
+                    cache.statistics.full_slabs_number,
+                    statistics.full_slabs_number + other_statistics.full_slabs_number
+                );
+                assert_eq!(
+                    cache.statistics.free_objects_number,
+                    statistics.free_objects_number + other_statistics.free_objects_number
+                );
+                assert_eq!(
+                    cache.statistics.allocated_objects_number,
+                    allocated_ptrs.len()
+                );
+                assert_eq!(
+                    cache.statistics.full_slabs_number,
+                    cache.full_slabs_list.iter().count()
+                );
+                assert_eq!(
+                    cache.statistics.free_slabs_number,
+                    cache.free_slabs_list_occupacy_less_75.iter().count()
+                        + cache.free_slabs_list_occupacy_more_75.iter().count()
+                );
+
+                // Random test
+                for _ in 0..rand::thread_rng().gen_range(20..=40) {
+                    if rand::thread_rng().gen_bool(0.5) {
+                        for _ in 0..rand::thread_rng().gen_range(20..200) {
+                            allocated_ptrs.push(cache.alloc());
+                        }
+                    } else {
+                        allocated_ptrs.shuffle(&mut rand::thread_rng());
+                        for _ in 0..rand::thread_rng().gen_range(0..=allocated_ptrs.len()) {
+                            cache.free(allocated_ptrs.pop().unwrap());
+                        }
+                    }
+                }
+
+                for allocated_ptr in allocated_ptrs {
+                    cache.free(allocated_ptr);
+                }
+                let memory_backend = memory_backend.0.borrow();
+                assert!(memory_backend.allocated_slab_addrs.is_empty());
+                assert!(memory_backend.allocated_slab_info_addrs.is_empty());
+                assert!(memory_backend.ht_saved_slab_infos.is_empty());
+            }
+        }
+    }
+
+    #[test]
+    fn verify_free_lists() {
+        unsafe {
+            const PAGE_SIZE: usize = 4096;
+            for (slab_size, object_size_type) in [
+                (PAGE_SIZE, ObjectSizeType::Small),
+                (PAGE_SIZE * 2, ObjectSizeType::Large),
+            ] {
+                let mut cache: Cache<[u64; 16], StdMemoryBackend> = Cache::new(
+                    slab_size,
+                    PAGE_SIZE,
+                    object_size_type,
+                    StdMemoryBackend::default(),
+                )
+                .unwrap();
+                assert!(cache.verify_free_lists().is_ok());
+
+                let mut allocated_ptrs = Vec::new();
+                for _ in 0..cache.objects_per_slab * 3 + 1 {
+                    allocated_ptrs.push(cache.alloc());
+                }
+                allocated_ptrs.shuffle(&mut rand::thread_rng());
+                for _ in 0..allocated_ptrs.len() / 2 {
+                    cache.free(allocated_ptrs.pop().unwrap());
+                }
+                assert!(cache.verify_free_lists().is_ok());
+
+                // Stray write into a free object
+                let mut free_object_ptr = null_mut();
+                cache.for_each_free_slot(|ptr| free_object_ptr = ptr);
+                free_object_ptr.cast::<usize>().write(8);
+                free_object_ptr.cast::<usize>().add(1).write(8);
+                assert_eq!(
+                    cache.verify_free_lists(),
+                    Err("Free object is outside of the slab")
+                );
+                // Cache is corrupted, its memory is leaked
+            }
+        }
+    }
+
+    #[test]
+    fn min_object_size() {
+        const MIN_OBJECT_SIZE: usize = Cache::<[u8; 64], StdMemoryBackend>::min_object_size();
+        const _: () = assert!(size_of::<[u8; 64]>() >= MIN_OBJECT_SIZE);
+        assert_eq!(MIN_OBJECT_SIZE, size_of::<*const u8>() * 2);
+
+        let too_small_cache: Result<Cache<[u8; MIN_OBJECT_SIZE - 1], StdMemoryBackend>, _> =
+            Cache::new(
+                4096,
+                4096,
+                ObjectSizeType::Small,
+                StdMemoryBackend::default(),
+            );
+        assert!(too_small_cache.is_err());
+        let cache: Result<Cache<[u8; MIN_OBJECT_SIZE], StdMemoryBackend>, _> = Cache::new(
+            4096,
+            4096,
+            ObjectSizeType::Small,
+            StdMemoryBackend::default(),
+        );
+        assert!(cache.is_ok());
+    }
+
+    #[test]
+    fn on_transition() {
+        unsafe {
+            use std::cell::RefCell;
+
+            std::thread_local! {
+                static TRANSITIONS: RefCell<Vec<(usize, SlabListKind, SlabListKind)>> =
+                    const { RefCell::new(Vec::new()) };
+            }
+            fn on_transition(slab_ptr: *mut u8, from: SlabListKind, to: SlabListKind) {
+                TRANSITIONS.with(|t| t.borrow_mut().push((slab_ptr as usize, from, to)));
+            }
+
+            const PAGE_SIZE: usize = 4096;
+            let mut cache: Cache<[u64; 32], StdMemoryBackend> = Cache::with_config(
+                PAGE_SIZE,
+                PAGE_SIZE,
+                ObjectSizeType::Small,
+                StdMemoryBackend::default(),
+                CacheConfig {
+                    on_transition: Some(on_transition),
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+            let objects_per_slab = cache.objects_per_slab;
+            let mut allocated_ptrs = Vec::new();
+            for _ in 0..objects_per_slab {
+                allocated_ptrs.push(cache.alloc());
+            }
+            let slab_addr = align_down(allocated_ptrs[0] as usize, PAGE_SIZE);
+            for _ in 0..objects_per_slab {
+                cache.free(allocated_ptrs.pop().unwrap());
+            }
+            let transitions = TRANSITIONS.with(|t| t.take());
+            assert_eq!(
+                transitions,
+                [
+                    (
+                        slab_addr,
+                        SlabListKind::FreeLess75,
+                        SlabListKind::FreeMore75
+                    ),
+                    (slab_addr, SlabListKind::FreeMore75, SlabListKind::Full),
+                    (slab_addr, SlabListKind::Full, SlabListKind::FreeMore75),
+                    (
+                        slab_addr,
+                        SlabListKind::FreeMore75,
+                        SlabListKind::FreeLess75
+                    ),
+                ]
+            );
+        }
+    }
+
+    #[test]
+    fn low_alignment_object() {
+        unsafe {
+            const POINTER_SIZE: usize = size_of::<*const u8>();
+            // Objects would be placed at addresses not aligned for free objects links
+            let cache: Result<Cache<[u8; POINTER_SIZE * 2 + 1], StdMemoryBackend>, _> = Cache::new(
+                4096,
+                4096,
+                ObjectSizeType::Small,
+                StdMemoryBackend::default(),
+            );
+            assert_eq!(
+                cache.err(),
+                Some("Objects can't be aligned for free object links, object size must be multiple of pointer size")
+            );
+
+            let mut cache: Cache<[u8; POINTER_SIZE * 3], StdMemoryBackend> = Cache::new(
+                4096,
+                4096,
+                ObjectSizeType::Small,
+                StdMemoryBackend::default(),
+            )
+            .unwrap();
+            let mut allocated_ptrs = Vec::new();
+            for _ in 0..cache.objects_per_slab * 2 {
+                let allocated_ptr = cache.alloc();
+                assert_eq!(allocated_ptr as usize % align_of::<*const u8>(), 0);
+                allocated_ptrs.push(allocated_ptr);
+            }
+            for allocated_ptr in allocated_ptrs {
+                cache.free(allocated_ptr);
+            }
+            assert!(cache.memory_backend.allocated_slab_addrs.is_empty());
+        }
+    }
+
+    #[test]
+    fn is_empty_has_free_object() {
+        unsafe {
+            const PAGE_SIZE: usize = 4096;
+            for reclaim in [true, false] {
+                let mut cache: Cache<[u64; 64], StdMemoryBackend> = Cache::with_config(
+                    PAGE_SIZE,
+                    PAGE_SIZE,
+                    ObjectSizeType::Small,
+                    StdMemoryBackend::default(),
+                    CacheConfig {
+                        reclaim,
+                        ..Default::default()
+                    },
+                )
+                .unwrap();
+                assert!(cache.is_empty());
+                assert!(!cache.has_free_object());
+
+                let mut allocated_ptrs = Vec::new();
+                for _ in 0..cache.objects_per_slab * 3 {
+                    allocated_ptrs.push(cache.alloc());
+                    assert!(!cache.is_empty());
+                    assert_eq!(cache.has_free_object(), cache.remaining_capacity() != 0);
+                }
+                assert!(!cache.has_free_object());
+
+                allocated_ptrs.shuffle(&mut rand::thread_rng());
+                for allocated_ptr in allocated_ptrs {
+                    cache.free(allocated_ptr);
+                    assert_eq!(
+                        cache.is_empty(),
+                        cache.cache_statistics().free_slabs_number == 0
+                            && cache.cache_statistics().full_slabs_number == 0
+                    );
+                    assert_eq!(cache.has_free_object(), cache.remaining_capacity() != 0);
+                }
+                assert_eq!(cache.is_empty(), reclaim);
+                cache.drain_free_slabs();
+                assert!(cache.is_empty());
+                assert!(!cache.has_free_object());
+            }
+        }
+    }
+
+    #[test]
+    fn raw_cache() {
+        unsafe {
+            const PAGE_SIZE: usize = 4096;
+            const POINTER_SIZE: usize = size_of::<*const u8>();
+
+            // Invalid layouts
+            for (object_size, object_align) in [
+                (POINTER_SIZE, POINTER_SIZE),
+                (POINTER_SIZE * 3, 3),
+                (POINTER_SIZE * 3, POINTER_SIZE * 2),
+                (PAGE_SIZE * 2, PAGE_SIZE * 2),
+            ] {
+                assert!(RawCache::new(
+                    object_size,
+                    object_align,
+                    PAGE_SIZE,
+                    PAGE_SIZE,
+                    ObjectSizeType::Small,
+                    StdMemoryBackend::default()
+                )
+                .is_err());
+            }
+
+            for (object_size, object_align, slab_size, object_size_type) in [
+                (
+                    POINTER_SIZE * 3,
+                    POINTER_SIZE,
+                    PAGE_SIZE,
+                    ObjectSizeType::Small,
+                ),
+                (
+                    100 * POINTER_SIZE,
+                    POINTER_SIZE,
+                    PAGE_SIZE * 2,
+                    ObjectSizeType::Small,
+                ),
+                (512, 64, PAGE_SIZE * 4, ObjectSizeType::Large),
+            ] {
+                let mut cache = RawCache::new(
+                    object_size,
+                    object_align,
+                    slab_size,
+                    PAGE_SIZE,
+                    object_size_type,
+                    StdMemoryBackend::default(),
+                )
+                .unwrap();
+                assert_eq!(cache.object_size(), object_size);
+                assert_eq!(cache.object_align(), object_align);
+
+                let mut allocated_ptrs = Vec::new();
+                for i in 0..cache.objects_per_slab() * 3 + 1 {
+                    let allocated_ptr = cache.alloc();
+                    assert_eq!(allocated_ptr as usize % object_align, 0);
+                    allocated_ptr.write_bytes(i as u8, object_size);
+                    allocated_ptrs.push((allocated_ptr, i as u8));
+                }
+                for (allocated_ptr, value) in allocated_ptrs.iter() {
+                    for j in 0..object_size {
+                        assert_eq!(*allocated_ptr.add(j), *value);
+                    }
+                }
+                allocated_ptrs.shuffle(&mut rand::thread_rng());
+                for (allocated_ptr, _) in allocated_ptrs {
+                    cache.free(allocated_ptr);
+                }
+                assert_eq!(cache.cache_statistics().allocated_objects_number, 0);
+                assert!(cache.cache.memory_backend.allocated_slab_addrs.is_empty());
+                assert!(cache
+                    .cache
+                    .memory_backend
+                    .allocated_slab_info_addrs
+                    .is_empty());
+            }
+        }
+    }
+
+    #[test]
+    fn free_iter() {
+        unsafe {
+            const PAGE_SIZE: usize = 4096;
+            for (slab_size, object_size_type) in [
+                (PAGE_SIZE, ObjectSizeType::Small),
+                (PAGE_SIZE * 4, ObjectSizeType::Small),
+                (PAGE_SIZE * 4, ObjectSizeType::Large),
+            ] {
+                let mut cache: Cache<[u64; 32], StdMemoryBackend> = Cache::new(
+                    slab_size,
+                    PAGE_SIZE,
+                    object_size_type,
+                    StdMemoryBackend::default(),
+                )
+                .unwrap();
+                let mut allocated_ptrs = Vec::new();
+                for _ in 0..cache.objects_per_slab * 5 + 3 {
+                    allocated_ptrs.push(cache.alloc());
+                }
+
+                // Consecutive objects of the same slab, slabs are released between them
+                let get_calls = cache.memory_backend.get_slab_info_ptr_calls;
+                let objects_number = allocated_ptrs.len();
+                cache.free_iter(allocated_ptrs.drain(..objects_number / 2));
+                if !(object_size_type == ObjectSizeType::Small && slab_size == PAGE_SIZE) {
+                    assert!(cache.memory_backend.get_slab_info_ptr_calls - get_calls <= 4);
+                }
+                assert_eq!(
+                    cache.cache_statistics().allocated_objects_number,
+                    allocated_ptrs.len()
+                );
+
+                // Random order
+                allocated_ptrs.shuffle(&mut rand::thread_rng());
+                cache.free_iter(allocated_ptrs.drain(..));
+                assert_eq!(cache.cache_statistics().allocated_objects_number, 0);
+                assert!(cache.memory_backend.allocated_slab_addrs.is_empty());
+                assert!(cache.memory_backend.allocated_slab_info_addrs.is_empty());
+                assert!(cache.memory_backend.ht_saved_slab_infos.is_empty());
+            }
+        }
+    }
+
+    #[test]
+    fn backend_statistics() {
+        unsafe {
+            const PAGE_SIZE: usize = 4096;
+            for (slab_size, object_size_type) in [
+                (PAGE_SIZE, ObjectSizeType::Small),
+                (PAGE_SIZE * 2, ObjectSizeType::Small),
+                (PAGE_SIZE * 2, ObjectSizeType::Large),
+            ] {
+                let mut cache: Cache<[u64; 32], StdMemoryBackend> = Cache::new(
+                    slab_size,
+                    PAGE_SIZE,
+                    object_size_type,
+                    StdMemoryBackend::default(),
+                )
+                .unwrap();
+                assert_eq!(cache.backend_statistics(), BackendStatistics::default());
+
+                let mut allocated_ptrs = Vec::new();
+                for _ in 0..cache.objects_per_slab * 4 {
+                    allocated_ptrs.push(cache.alloc());
+                }
+                let backend_statistics = cache.backend_statistics();
+                assert_eq!(backend_statistics.slab_allocs, 4);
+                assert_eq!(backend_statistics.slab_frees, 0);
+                if object_size_type == ObjectSizeType::Large {
+                    assert_eq!(backend_statistics.slab_info_allocs, 4);
+                } else {
+                    assert_eq!(backend_statistics.slab_info_allocs, 0);
+                }
+                if slab_size == PAGE_SIZE {
+                    assert_eq!(backend_statistics.save_calls, 0);
+                } else {
+                    assert_ne!(backend_statistics.save_calls, 0);
+                }
+
+                allocated_ptrs.shuffle(&mut rand::thread_rng());
+                for allocated_ptr in allocated_ptrs {
+                    cache.free(allocated_ptr);
+                }
+                let backend_statistics = cache.backend_statistics();
+                assert_eq!(backend_statistics.slab_frees, 4);
+                assert_eq!(
+                    backend_statistics.slab_info_frees,
+                    backend_statistics.slab_info_allocs
+                );
+                assert_eq!(
+                    backend_statistics.get_calls,
+                    cache.memory_backend.get_slab_info_ptr_calls
+                );
+                if slab_size == PAGE_SIZE {
+                    assert_eq!(backend_statistics.delete_calls, 0);
+                } else {
+                    assert_eq!(backend_statistics.delete_calls, 4 * slab_size / PAGE_SIZE);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn lazy_carve() {
+        unsafe {
+            const PAGE_SIZE: usize = 4096;
+            for (slab_size, object_size_type) in [
+                (PAGE_SIZE, ObjectSizeType::Small),
+                (PAGE_SIZE * 4, ObjectSizeType::Small),
+                (PAGE_SIZE * 4, ObjectSizeType::Large),
+            ] {
+                let mut cache: Cache<[u64; 4], StdMemoryBackend> = Cache::with_config(
+                    slab_size,
+                    PAGE_SIZE,
+                    object_size_type,
+                    StdMemoryBackend::default(),
+                    CacheConfig {
+                        lazy_carve: true,
+                        ..Default::default()
+                    },
+                )
+                .unwrap();
+                let objects_per_slab = cache.objects_per_slab;
+
+                // Objects are given in the same order as with carved slab
+                let mut allocated_ptrs = Vec::new();
+                for i in 0..3 {
+                    let allocated_ptr = cache.alloc();
+                    let slab_addr = cache.memory_backend.allocated_slab_addrs[0];
+                    assert_eq!(
+                        allocated_ptr as usize,
+                        cache.predicted_slot_addr(slab_addr as *const u8, objects_per_slab - 1 - i)
+                    );
+                    allocated_ptrs.push(allocated_ptr);
+                }
+                let mut free_slots_number = 0;
+                cache.for_each_free_slot(|_| free_slots_number += 1);
+                assert_eq!(free_slots_number, objects_per_slab - 3);
+                assert!(cache.verify_free_lists().is_ok());
+
+                // Not carved objects are taken before freed objects
+                let freed_ptr = allocated_ptrs.pop().unwrap();
+                cache.free(freed_ptr);
+                assert!(cache.verify_free_lists().is_ok());
+                let allocated_ptr = cache.alloc();
+                assert_ne!(allocated_ptr, freed_ptr);
+                allocated_ptrs.push(allocated_ptr);
+
+                // Random test
+                for _ in 0..rand::thread_rng().gen_range(20..=40) {
+                    if rand::thread_rng().gen_bool(0.5) {
+                        for _ in 0..rand::thread_rng().gen_range(0..objects_per_slab * 2) {
+                            allocated_ptrs.push(cache.alloc());
+                        }
+                    } else {
+                        allocated_ptrs.shuffle(&mut rand::thread_rng());
+                        for _ in 0..rand::thread_rng().gen_range(0..=allocated_ptrs.len()) {
+                            cache.free(allocated_ptrs.pop().unwrap());
+                        }
+                    }
+                    assert!(cache.verify_free_lists().is_ok());
+                    let mut free_slots_number = 0;
+                    cache.for_each_free_slot(|_| free_slots_number += 1);
+                    assert_eq!(free_slots_number, cache.remaining_capacity());
+                    let unique_ptrs: HashSet<*mut [u64; 4]> =
+                        HashSet::from_iter(allocated_ptrs.iter().copied());
+                    assert_eq!(unique_ptrs.len(), allocated_ptrs.len());
+                }
+
+                for allocated_ptr in allocated_ptrs {
+                    cache.free(allocated_ptr);
+                }
+                assert!(cache.memory_backend.allocated_slab_addrs.is_empty());
+                assert!(cache.memory_backend.allocated_slab_info_addrs.is_empty());
+                assert!(cache.memory_backend.ht_saved_slab_infos.is_empty());
+            }
+        }
+    }
+
+    #[test]
+    fn available_slabs_hint() {
+        unsafe {
+            const PAGE_SIZE: usize = 4096;
+
+            // Knows how many slabs it can allocate
+            struct TestMemoryBackend {
+                std_memory_backend: StdMemoryBackend,
+                available_slabs: usize,
+                alloc_slab_calls: usize,
+            }
+
+            impl MemoryBackend for TestMemoryBackend {
+                type Error = OutOfMemory;
+
+                unsafe fn alloc_slab(
+                    &mut self,
+                    slab_size: usize,
+                    page_size: usize,
+                ) -> Result<*mut u8, Self::Error> {
+                    self.alloc_slab_calls += 1;
+                    if self.available_slabs == 0 {
+                        return Err(OutOfMemory);
+                    }
+                    self.available_slabs -= 1;
+                    self.std_memory_backend.alloc_slab(slab_size, page_size)
+                }
+
+                unsafe fn free_slab(
+                    &mut self,
+                    slab_ptr: *mut u8,
+                    slab_size: usize,
+                    page_size: usize,
+                ) {
+                    self.available_slabs += 1;
+                    self.std_memory_backend
+                        .free_slab(slab_ptr, slab_size, page_size);
+                }
+
+                unsafe fn alloc_slab_info(&mut self) -> Result<*mut SlabInfo, Self::Error> {
+                    unreachable!();
+                }
+
+                unsafe fn free_slab_info(&mut self, _slab_info_ptr: *mut SlabInfo) {
+                    unreachable!();
+                }
+
+                unsafe fn save_slab_info_ptr(
+                    &mut self,
+                    _object_page_addr: usize,
+                    _slab_info_ptr: *mut SlabInfo,
+                ) {
+                    unreachable!();
+                }
+
+                unsafe fn get_slab_info_ptr(&mut self, _object_page_addr: usize) -> *mut SlabInfo {
+                    unreachable!();
+                }
+
+                unsafe fn delete_slab_info_ptr(&mut self, _page_addr: usize) {
+                    unreachable!();
+                }
+
+                fn available_slabs_hint(
+                    &self,
+                    _slab_size: usize,
+                    _page_size: usize,
+                ) -> Option<usize> {
+                    Some(self.available_slabs)
+                }
+            }
+
+            let mut cache: Cache<[u64; 16], TestMemoryBackend> = Cache::new(
+                PAGE_SIZE,
+                PAGE_SIZE,
+                ObjectSizeType::Small,
+                TestMemoryBackend {
+                    std_memory_backend: StdMemoryBackend::default(),
+                    available_slabs: 5,
+                    alloc_slab_calls: 0,
+                },
+            )
+            .unwrap();
+
+            // Request is clamped, backend never fails
+            assert_eq!(cache.reserve_contiguous(8), 5);
+            assert_eq!(cache.memory_backend.alloc_slab_calls, 5);
+            assert_eq!(cache.reserve_contiguous(1), 0);
+            assert_eq!(cache.memory_backend.alloc_slab_calls, 5);
+            assert_eq!(cache.drain_free_slabs(), 5);
+
+            // Not enough slabs, nothing is allocated
+            assert_eq!(
+                cache.reserve_slabs(6),
+                Err(CacheError::Backend(OutOfMemory))
+            );
+            assert_eq!(
+                cache.reserve_objects(cache.objects_per_slab * 5 + 1),
+                Err(CacheError::Backend(OutOfMemory))
+            );
+            assert_eq!(cache.memory_backend.alloc_slab_calls, 5);
+            assert_eq!(cache.reserve_slabs(2), Ok(()));
+            assert_eq!(cache.reserve_objects(cache.objects_per_slab * 5), Ok(()));
+            assert_eq!(cache.memory_backend.alloc_slab_calls, 10);
+
+            assert_eq!(cache.drain_free_slabs(), 5);
+            assert!(cache
+                .memory_backend
+                .std_memory_backend
+                .allocated_slab_addrs
+                .is_empty());
+        }
+    }
+
+    #[test]
+    fn offset_free_links() {
+        unsafe {
+            const PAGE_SIZE: usize = 4096;
+            assert_eq!(
+                Cache::<u64, StdMemoryBackend>::min_object_size_with_offset_links(),
+                4
+            );
+
+            // Pointer sized objects can't be linked by pointers
+            assert!(Cache::<u64, StdMemoryBackend>::new(
+                PAGE_SIZE,
+                PAGE_SIZE,
+                ObjectSizeType::Small,
+                StdMemoryBackend::default()
+            )
+            .is_err());
+
+            for (slab_size, object_size_type, lazy_carve) in [
+                (PAGE_SIZE, ObjectSizeType::Small, false),
+                (PAGE_SIZE * 4, ObjectSizeType::Small, false),
+                (PAGE_SIZE * 4, ObjectSizeType::Large, false),
+                (PAGE_SIZE, ObjectSizeType::Small, true),
+            ] {
+                let mut cache: Cache<u64, StdMemoryBackend> = Cache::with_config(
+                    slab_size,
+                    PAGE_SIZE,
+                    object_size_type,
+                    StdMemoryBackend::default(),
+                    CacheConfig {
+                        offset_free_links: true,
+                        lazy_carve,
+                        ..Default::default()
+                    },
+                )
+                .unwrap();
+                let objects_per_slab = cache.objects_per_slab;
+
+                // Same order as with pointer links
+                let mut allocated_ptrs = Vec::new();
+                for i in 0..objects_per_slab {
+                    let allocated_ptr = cache.alloc();
+                    let slab_addr = cache.memory_backend.allocated_slab_addrs[0];
+                    assert_eq!(
+                        allocated_ptr as usize,
+                        cache.predicted_slot_addr(slab_addr as *const u8, objects_per_slab - 1 - i)
+                    );
+                    allocated_ptr.write(i as u64);
+                    allocated_ptrs.push(allocated_ptr);
+                }
+                // Last freed object is allocated first
+                let freed_ptr = allocated_ptrs.swap_remove(objects_per_slab / 2);
+                cache.free(freed_ptr);
+                assert!(cache.verify_free_lists().is_ok());
+                let allocated_ptr = cache.alloc();
+                assert_eq!(allocated_ptr, freed_ptr);
+                allocated_ptrs.push(allocated_ptr);
+
+                // Random test
+                for _ in 0..rand::thread_rng().gen_range(20..=40) {
+                    if rand::thread_rng().gen_bool(0.5) {
+                        for _ in 0..rand::thread_rng().gen_range(0..objects_per_slab * 2) {
+                            let allocated_ptr = cache.alloc();
+                            allocated_ptr.write(allocated_ptr as u64);
+                            allocated_ptrs.push(allocated_ptr);
+                        }
+                    } else {
+                        allocated_ptrs.shuffle(&mut rand::thread_rng());
+                        for _ in 0..rand::thread_rng().gen_range(0..=allocated_ptrs.len()) {
+                            cache.free(allocated_ptrs.pop().unwrap());
+                        }
+                    }
+                    assert!(cache.verify_free_lists().is_ok());
+                    let mut free_slots_number = 0;
+                    cache.for_each_free_slot(|_| free_slots_number += 1);
+                    assert_eq!(free_slots_number, cache.remaining_capacity());
+                    let unique_ptrs: HashSet<*mut u64> =
+                        HashSet::from_iter(allocated_ptrs.iter().copied());
+                    assert_eq!(unique_ptrs.len(), allocated_ptrs.len());
+                }
+
+                for allocated_ptr in allocated_ptrs {
+                    cache.free(allocated_ptr);
+                }
+                assert!(cache.memory_backend.allocated_slab_addrs.is_empty());
+                assert!(cache.memory_backend.allocated_slab_info_addrs.is_empty());
+                assert!(cache.memory_backend.ht_saved_slab_infos.is_empty());
+            }
+        }
+    }
+
+    #[test]
+    fn alloc_aligned() {
+        unsafe {
+            const PAGE_SIZE: usize = 4096;
+            for (slab_size, object_size_type, config) in [
+                (PAGE_SIZE, ObjectSizeType::Small, CacheConfig::default()),
+                (PAGE_SIZE * 4, ObjectSizeType::Small, CacheConfig::default()),
+                (PAGE_SIZE * 4, ObjectSizeType::Large, CacheConfig::default()),
+                (
+                    PAGE_SIZE,
+                    ObjectSizeType::Small,
+                    CacheConfig {
+                        lazy_carve: true,
+                        ..Default::default()
+                    },
+                ),
+                (
+                    PAGE_SIZE * 2,
+                    ObjectSizeType::Large,
+                    CacheConfig {
+                        offset_free_links: true,
+                        ..Default::default()
+                    },
+                ),
+                (
+                    PAGE_SIZE,
+                    ObjectSizeType::Small,
+                    CacheConfig {
+                        distribution_policy: DistributionPolicy::Spread,
+                        ..Default::default()
+                    },
+                ),
+            ] {
+                // 24 bytes objects, only some of them are 64 aligned
+                let mut cache: Cache<[u64; 3], StdMemoryBackend> = Cache::with_config(
+                    slab_size,
+                    PAGE_SIZE,
+                    object_size_type,
+                    StdMemoryBackend::default(),
+                    config,
+                )
+                .unwrap();
+
+                // Mixed alloc/alloc_aligned random test
+                let mut allocated_ptrs = Vec::new();
+                for _ in 0..rand::thread_rng().gen_range(20..=40) {
+                    if rand::thread_rng().gen_bool(0.5) {
+                        for _ in 0..rand::thread_rng().gen_range(0..cache.objects_per_slab * 2) {
+                            let allocated_ptr = if rand::thread_rng().gen_bool(0.5) {
+                                let allocated_ptr = cache.alloc_aligned(64);
+                                assert_eq!(allocated_ptr as usize % 64, 0);
+                                allocated_ptr
+                            } else {
+                                cache.alloc()
+                            };
+                            assert!(!allocated_ptr.is_null());
+                            allocated_ptr.write([allocated_ptr as u64; 3]);
+                            allocated_ptrs.push(allocated_ptr);
+                        }
+                    } else {
+                        allocated_ptrs.shuffle(&mut rand::thread_rng());
+                        for _ in 0..rand::thread_rng().gen_range(0..=allocated_ptrs.len()) {
+                            cache.free(allocated_ptrs.pop().unwrap());
+                        }
+                    }
+                    for allocated_ptr in allocated_ptrs.iter() {
+                        assert_eq!(**allocated_ptr, [*allocated_ptr as u64; 3]);
+                    }
+                    assert!(cache.verify_free_lists().is_ok());
+                    let statistics = cache.cache_statistics();
+                    assert_eq!(statistics.allocated_objects_number, allocated_ptrs.len());
+                    assert_eq!(
+                        statistics.free_slabs_number,
+                        cache.free_slabs_list_occupacy_less_75.iter().count()
+                            + cache.free_slabs_list_occupacy_more_75.iter().count()
+                    );
+                    assert_eq!(
+                        statistics.full_slabs_number,
+                        cache.full_slabs_list.iter().count()
+                    );
+                }
+
+                for allocated_ptr in allocated_ptrs {
+                    cache.free(allocated_ptr);
+                }
+                cache.drain_free_slabs();
+                assert!(cache.memory_backend.allocated_slab_addrs.is_empty());
+                assert!(cache.memory_backend.allocated_slab_info_addrs.is_empty());
+                assert!(cache.memory_backend.ht_saved_slab_infos.is_empty());
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "not the start of an object")]
+    fn free_inside_object() {
+        unsafe {
+            let mut cache: Cache<[u64; 3], StdMemoryBackend> = Cache::new(
+                4096,
+                4096,
+                ObjectSizeType::Small,
+                StdMemoryBackend::default(),
+            )
+            .unwrap();
+            let allocated_ptr = cache.alloc();
+            cache.free(allocated_ptr.cast::<u64>().add(1).cast());
+        }
+    }
+
+    #[test]
+    fn fixed_cache() {
+        unsafe {
+            static CACHE: Mutex<FixedCache<[u64; 4], 1000>> = Mutex::new(FixedCache::new());
+            let mut cache = CACHE.lock();
+            assert_eq!(cache.capacity(), 1000 / 32);
+
+            // Exhaust
+            let mut allocated_ptrs = Vec::new();
+            for i in 0..cache.capacity() {
+                let allocated_ptr = cache.alloc();
+                assert!(!allocated_ptr.is_null());
+                assert!(allocated_ptr.is_aligned());
+                let cache_addr = &*cache as *const FixedCache<[u64; 4], 1000> as usize;
+                assert!(
+                    (cache_addr..cache_addr + size_of::<FixedCache<[u64; 4], 1000>>())
+                        .contains(&(allocated_ptr as usize))
+                );
+                allocated_ptr.write([i as u64; 4]);
+                allocated_ptrs.push(allocated_ptr);
+            }
+            assert!(cache.alloc().is_null());
+            assert_eq!(cache.remaining_capacity(), 0);
+            for (i, allocated_ptr) in allocated_ptrs.iter().enumerate() {
+                assert_eq!(**allocated_ptr, [i as u64; 4]);
+            }
+
+            // Random test
+            for _ in 0..rand::thread_rng().gen_range(20..=40) {
+                if rand::thread_rng().gen_bool(0.5) {
+                    for _ in 0..rand::thread_rng().gen_range(0..=cache.capacity()) {
+                        let allocated_ptr = cache.alloc();
+                        if allocated_ptrs.len() == cache.capacity() {
+                            assert!(allocated_ptr.is_null());
+                        } else {
+                            allocated_ptr.write([allocated_ptr as u64; 4]);
+                            allocated_ptrs.push(allocated_ptr);
+                        }
+                    }
+                } else {
+                    allocated_ptrs.shuffle(&mut rand::thread_rng());
+                    for _ in 0..rand::thread_rng().gen_range(0..=allocated_ptrs.len()) {
+                        cache.free(allocated_ptrs.pop().unwrap());
+                    }
+                }
+                assert_eq!(cache.allocated_objects_number(), allocated_ptrs.len());
+                let unique_ptrs: HashSet<*mut [u64; 4]> =
+                    HashSet::from_iter(allocated_ptrs.iter().copied());
+                assert_eq!(unique_ptrs.len(), allocated_ptrs.len());
+            }
+
+            for allocated_ptr in allocated_ptrs {
+                cache.free(allocated_ptr);
+            }
+            assert_eq!(cache.remaining_capacity(), cache.capacity());
+        }
+    }
+
+    #[test]
+    fn set_occupancy_threshold_percent() {
+        unsafe {
+            const PAGE_SIZE: usize = 4096;
+            let mut cache: Cache<[u64; 16], StdMemoryBackend> = Cache::new(
+                PAGE_SIZE,
+                PAGE_SIZE,
+                ObjectSizeType::Small,
+                StdMemoryBackend::default(),
+            )
+            .unwrap();
+            let objects_per_slab = cache.objects_per_slab;
+            assert_eq!(cache.occupancy_threshold_percent(), 75);
+            assert!(cache.set_occupancy_threshold_percent(101).is_err());
+            assert!(cache.set_occupancy_threshold_percent(0).is_err());
+            assert_eq!(cache.occupancy_threshold_percent(), 75);
+
+            let check_lists = |cache: &Cache<[u64; 16], StdMemoryBackend>| {
+                let threshold = cache.occupacy_more_75_minimum_allocated_objects_number;
+                let mut previous_allocated_objects_number = usize::MAX;
+                for slab_info in cache.free_slabs_list_occupacy_less_75.iter() {
+                    let allocated_objects_number =
+                        objects_per_slab - (*slab_info.data.get()).free_objects_number;
+                    assert!(allocated_objects_number < threshold);
+                    // Sorted
+                    assert!(allocated_objects_number <= previous_allocated_objects_number);
+                    previous_allocated_objects_number = allocated_objects_number;
+                }
+                for slab_info in cache.free_slabs_list_occupacy_more_75.iter() {
+                    let free_objects_number = (*slab_info.data.get()).free_objects_number;
+                    assert!(objects_per_slab - free_objects_number >= threshold);
+                    assert_ne!(free_objects_number, 0);
+                }
+                assert_eq!(
+                    cache.statistics.free_slabs_number,
+                    cache.free_slabs_list_occupacy_less_75.iter().count()
+                        + cache.free_slabs_list_occupacy_more_75.iter().count()
+                );
+            };
+
+            // Slabs with different occupancy
+            let mut allocated_ptrs = Vec::new();
+            for _ in 0..objects_per_slab * 10 {
+                allocated_ptrs.push(cache.alloc());
+            }
+            for slab_allocated_ptrs in allocated_ptrs.chunks_mut(objects_per_slab) {
+                let freed_objects_number = rand::thread_rng().gen_range(1..objects_per_slab);
+                for allocated_ptr in slab_allocated_ptrs[..freed_objects_number].iter_mut() {
+                    cache.free(*allocated_ptr);
+                    *allocated_ptr = null_mut();
+                }
+            }
+            allocated_ptrs.retain(|allocated_ptr| !allocated_ptr.is_null());
+            check_lists(&cache);
+
+            for percent in [10, 50, 100, 90, 1, 75] {
+                if cache.set_occupancy_threshold_percent(percent).is_err() {
+                    assert_eq!((percent as usize * objects_per_slab) / 100, 0);
+                    continue;
+                }
+                assert_eq!(cache.occupancy_threshold_percent(), percent);
+                check_lists(&cache);
+
+                // Random test
+                for _ in 0..rand::thread_rng().gen_range(5..=10) {
+                    if rand::thread_rng().gen_bool(0.5) {
+                        for _ in 0..rand::thread_rng().gen_range(0..objects_per_slab * 2) {
+                            allocated_ptrs.push(cache.alloc());
+                        }
+                    } else {
+                        allocated_ptrs.shuffle(&mut rand::thread_rng());
+                        for _ in 0..rand::thread_rng().gen_range(0..=allocated_ptrs.len()) {
+                            cache.free(allocated_ptrs.pop().unwrap());
+                        }
+                    }
+                    check_lists(&cache);
+                }
+            }
+
+            for allocated_ptr in allocated_ptrs {
+                cache.free(allocated_ptr);
+            }
+            assert!(cache.memory_backend.allocated_slab_addrs.is_empty());
+        }
+    }
+
+    #[test]
+    fn slab_base_addresses() {
+        unsafe {
+            const PAGE_SIZE: usize = 4096;
+            for (slab_size, object_size_type) in [
+                (PAGE_SIZE, ObjectSizeType::Small),
+                (PAGE_SIZE * 4, ObjectSizeType::Large),
+            ] {
+                let mut cache: Cache<[u64; 32], StdMemoryBackend> = Cache::new(
+                    slab_size,
+                    PAGE_SIZE,
+                    object_size_type,
+                    StdMemoryBackend::default(),
+                )
+                .unwrap();
+                assert_eq!(cache.slab_base_addresses().count(), 0);
+
+                // Full, free (>75) and free (<75) slabs
+                let mut allocated_ptrs = Vec::new();
+                for _ in 0..cache.objects_per_slab * 5 + 1 {
+                    allocated_ptrs.push(cache.alloc());
+                }
+                cache.free(allocated_ptrs.pop().unwrap());
+                cache.free(allocated_ptrs.pop().unwrap());
+
+                let slab_addrs: HashSet<usize> = cache
+                    .slab_base_addresses()
+                    .map(|(slab_addr, size)| {
+                        assert_eq!(size, slab_size);
+                        slab_addr
+                    })
+                    .collect();
+                assert_eq!(
+                    slab_addrs,
+                    HashSet::from_iter(cache.memory_backend.allocated_slab_addrs.iter().copied())
+                );
+
+                for allocated_ptr in allocated_ptrs {
+                    cache.free(allocated_ptr);
+                }
+                assert_eq!(cache.slab_base_addresses().count(), 0);
+            }
+        }
+    }
+
+    #[test]
+    fn dump() {
+        unsafe {
+            const PAGE_SIZE: usize = 4096;
+            let mut cache: Cache<[u64; 64], StdMemoryBackend> = Cache::new(
+                PAGE_SIZE,
+                PAGE_SIZE,
+                ObjectSizeType::Small,
+                StdMemoryBackend::default(),
+            )
+            .unwrap();
+            assert_eq!(cache.objects_per_slab, 7);
+
+            let mut report = String::new();
+            cache.dump(&mut report).unwrap();
+            assert_eq!(
+                report,
+                "total: object size 512, slab size 4096, slabs 0 free 0 full, objects 0/0, fragmentation 0%\n"
+            );
+
+            let mut allocated_ptrs = Vec::new();
+            for _ in 0..8 {
+                allocated_ptrs.push(cache.alloc());
+            }
+            let slab_addrs = cache.memory_backend.allocated_slab_addrs.clone();
+            let mut report = String::new();
+            cache.dump(&mut report).unwrap();
+            assert_eq!(
+                report,
+                format!(
+                    "slab {:#x}: 7/7 objects (Full)\n\
+                     slab {:#x}: 1/7 objects (FreeLess75)\n\
+                     total: object size 512, slab size 4096, slabs 1 free 1 full, objects 8/14, fragmentation 42%\n",
+                    slab_addrs[0], slab_addrs[1]
+                )
+            );
+
+            for allocated_ptr in allocated_ptrs {
+                cache.free(allocated_ptr);
+            }
+        }
+    }
+
+    #[test]
+    fn max_allocated_objects() {
+        unsafe {
+            const PAGE_SIZE: usize = 4096;
+            const MAX_ALLOCATED_OBJECTS: usize = 100;
+            let mut cache: Cache<[u64; 16], StdMemoryBackend> = Cache::with_config(
+                PAGE_SIZE,
+                PAGE_SIZE,
+                ObjectSizeType::Small,
+                StdMemoryBackend::default(),
+                CacheConfig {
+                    max_allocated_objects: Some(MAX_ALLOCATED_OBJECTS),
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+
+            // Random test
+            let mut allocated_ptrs = Vec::new();
+            for _ in 0..rand::thread_rng().gen_range(20..=40) {
+                if rand::thread_rng().gen_bool(0.5) {
+                    for _ in 0..rand::thread_rng().gen_range(0..MAX_ALLOCATED_OBJECTS) {
+                        let allocated_ptr = if rand::thread_rng().gen_bool(0.5) {
+                            cache.alloc()
+                        } else {
+                            cache.alloc_aligned(256)
+                        };
+                        if allocated_ptrs.len() == MAX_ALLOCATED_OBJECTS {
+                            assert!(allocated_ptr.is_null());
+                        } else {
+                            assert!(!allocated_ptr.is_null());
+                            allocated_ptrs.push(allocated_ptr);
+                        }
+                    }
+                } else {
+                    allocated_ptrs.shuffle(&mut rand::thread_rng());
+                    for _ in 0..rand::thread_rng().gen_range(0..=allocated_ptrs.len()) {
+                        cache.free(allocated_ptrs.pop().unwrap());
+                    }
+                }
+                assert!(cache.cache_statistics().allocated_objects_number <= MAX_ALLOCATED_OBJECTS);
+            }
+
+            for allocated_ptr in allocated_ptrs {
+                cache.free(allocated_ptr);
+            }
+            assert!(cache.memory_backend.allocated_slab_addrs.is_empty());
+        }
+    }
+
+    #[test]
+    fn try_free_slab() {
+        unsafe {
+            const PAGE_SIZE: usize = 4096;
+
+            // Rejects every second slab free
+            struct TestMemoryBackend {
+                std_memory_backend: StdMemoryBackend,
+                try_free_slab_calls: usize,
+                rejected_slab_addrs: Vec<usize>,
+            }
+
+            impl MemoryBackend for TestMemoryBackend {
+                type Error = &'static str;
+
+                unsafe fn alloc_slab(
+                    &mut self,
+                    slab_size: usize,
+                    page_size: usize,
+                ) -> Result<*mut u8, Self::Error> {
+                    Ok(self.std_memory_backend.alloc_slab(slab_size, page_size)?)
+                }
+
+                unsafe fn free_slab(
+                    &mut self,
+                    _slab_ptr: *mut u8,
+                    _slab_size: usize,
+                    _page_size: usize,
+                ) {
+                    unreachable!();
+                }
+
+                unsafe fn try_free_slab(
+                    &mut self,
+                    slab_ptr: *mut u8,
+                    slab_size: usize,
+                    page_size: usize,
+                ) -> Result<(), &'static str> {
+                    self.try_free_slab_calls += 1;
+                    if self.try_free_slab_calls.is_multiple_of(2) {
+                        self.rejected_slab_addrs.push(slab_ptr as usize);
+                        return Err("Rejected");
+                    }
+                    self.std_memory_backend
+                        .free_slab(slab_ptr, slab_size, page_size);
+                    Ok(())
+                }
+
+                unsafe fn alloc_slab_info(&mut self) -> Result<*mut SlabInfo, Self::Error> {
+                    unreachable!();
+                }
+
+                unsafe fn free_slab_info(&mut self, _slab_info_ptr: *mut SlabInfo) {
+                    unreachable!();
+                }
+
+                unsafe fn save_slab_info_ptr(
+                    &mut self,
+                    _object_page_addr: usize,
+                    _slab_info_ptr: *mut SlabInfo,
+                ) {
+                    unreachable!();
+                }
+
+                unsafe fn get_slab_info_ptr(&mut self, _object_page_addr: usize) -> *mut SlabInfo {
+                    unreachable!();
+                }
+
+                unsafe fn delete_slab_info_ptr(&mut self, _page_addr: usize) {
+                    unreachable!();
+                }
+            }
+
+            let mut cache: Cache<[u64; 16], TestMemoryBackend> = Cache::with_config(
+                PAGE_SIZE,
+                PAGE_SIZE,
+                ObjectSizeType::Small,
+                TestMemoryBackend {
+                    std_memory_backend: StdMemoryBackend::default(),
+                    try_free_slab_calls: 0,
+                    rejected_slab_addrs: Vec::new(),
+                },
+                CacheConfig {
+                    reclaim: false,
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+
+            let mut allocated_ptrs = Vec::new();
+            for _ in 0..cache.objects_per_slab * 4 {
+                allocated_ptrs.push(cache.alloc());
+            }
+            for allocated_ptr in allocated_ptrs {
+                cache.free(allocated_ptr);
+            }
+            assert_eq!(cache.drain_free_slabs(), 2);
+            let backend_statistics = cache.backend_statistics();
+            assert_eq!(backend_statistics.slab_frees, 2);
+            assert_eq!(backend_statistics.slab_free_failures, 2);
+            // Rejected slabs are forgotten
+            assert!(cache.is_empty());
+            assert_eq!(cache.cache_statistics().free_slabs_number, 0);
+            assert_eq!(cache.cache_statistics().free_objects_number, 0);
+
+            let std_memory_backend = &mut cache.memory_backend.std_memory_backend;
+            assert_eq!(std_memory_backend.allocated_slab_addrs.len(), 2);
+            for rejected_slab_addr in cache.memory_backend.rejected_slab_addrs.clone() {
+                cache.memory_backend.std_memory_backend.free_slab(
+                    rejected_slab_addr as *mut u8,
+                    PAGE_SIZE,
+                    PAGE_SIZE,
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn compact_slab_infos() {
+        unsafe {
+            const PAGE_SIZE: usize = 4096;
+            const SLAB_SIZE: usize = PAGE_SIZE * 2;
+
+            let mut cache: Cache<[u64; 64], StdMemoryBackend> = Cache::with_config(
+                SLAB_SIZE,
+                PAGE_SIZE,
+                ObjectSizeType::Large,
+                StdMemoryBackend::default(),
+                CacheConfig {
+                    slab_info_lookup_cache: true,
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+
+            let mut allocated_ptrs = Vec::new();
+            for _ in 0..cache.objects_per_slab * 3 + 2 {
+                allocated_ptrs.push(cache.alloc());
+            }
+            // Leave slabs in all lists
+            let mut kept_ptrs = Vec::new();
+            for (i, allocated_ptr) in allocated_ptrs.into_iter().enumerate() {
+                if i >= cache.objects_per_slab && i.is_multiple_of(3) {
+                    cache.free(allocated_ptr);
+                } else {
+                    kept_ptrs.push(allocated_ptr);
+                }
+            }
+            let mut allocated_ptrs = kept_ptrs;
+            let cache_statistics = cache.cache_statistics();
+            let mut dump_before = String::new();
+            cache.dump(&mut dump_before).unwrap();
+
+            // Memory backend doesn't relocate
+            assert_eq!(cache.compact_slab_infos(), 0);
+
+            cache.memory_backend.relocate_slab_infos = true;
+            let slabs_number =
+                cache_statistics.free_slabs_number + cache_statistics.full_slabs_number;
+            assert_eq!(cache.compact_slab_infos(), slabs_number);
+            assert_eq!(
+                cache.memory_backend.allocated_slab_info_addrs.len(),
+                slabs_number
+            );
+            let mut dump_after = String::new();
+            cache.dump(&mut dump_after).unwrap();
+            assert_eq!(dump_before, dump_after);
+            cache.verify_free_lists().unwrap();
+
+            // Cache works with relocated SlabInfos
+            for _ in 0..cache.objects_per_slab {
+                allocated_ptrs.push(cache.alloc());
+            }
+            for allocated_ptr in allocated_ptrs {
+                cache.free(allocated_ptr);
+            }
+            cache.drain_free_slabs();
+            assert!(cache.memory_backend.allocated_slab_addrs.is_empty());
+            assert!(cache.memory_backend.allocated_slab_info_addrs.is_empty());
+            assert!(cache.memory_backend.ht_saved_slab_infos.is_empty());
+        }
+    }
+
+    #[test]
+    fn on_oom() {
+        unsafe {
+            use std::cell::Cell;
+            const PAGE_SIZE: usize = 4096;
+
+            std::thread_local! {
+                // Slabs which memory backend can allocate
+                static AVAILABLE_SLABS: Cell<usize> = const { Cell::new(1) };
+                static ON_OOM_CALLS: Cell<usize> = const { Cell::new(0) };
+                static CAN_RECLAIM: Cell<bool> = const { Cell::new(false) };
+            }
+            fn on_oom() -> bool {
+                ON_OOM_CALLS.with(|calls| calls.set(calls.get() + 1));
+                if CAN_RECLAIM.with(|can_reclaim| can_reclaim.get()) {
+                    // Memory of another cache has been freed
+                    AVAILABLE_SLABS.with(|available_slabs| available_slabs.set(1));
+                    true
+                } else {
+                    false
+                }
+            }
+
+            // Allocates only available slabs
+            #[derive(Default)]
+            struct TestMemoryBackend {
+                std_memory_backend: StdMemoryBackend,
+            }
+
+            impl MemoryBackend for TestMemoryBackend {
+                type Error = OutOfMemory;
+
+                unsafe fn alloc_slab(
+                    &mut self,
+                    slab_size: usize,
+                    page_size: usize,
+                ) -> Result<*mut u8, Self::Error> {
+                    let available_slabs =
+                        AVAILABLE_SLABS.with(|available_slabs| available_slabs.get());
+                    if available_slabs == 0 {
+                        return Err(OutOfMemory);
+                    }
+                    AVAILABLE_SLABS.with(|cell| cell.set(available_slabs - 1));
+                    self.std_memory_backend.alloc_slab(slab_size, page_size)
+                }
+
+                unsafe fn free_slab(
+                    &mut self,
+                    slab_ptr: *mut u8,
+                    slab_size: usize,
+                    page_size: usize,
+                ) {
+                    self.std_memory_backend
+                        .free_slab(slab_ptr, slab_size, page_size);
+                }
+
+                unsafe fn alloc_slab_info(&mut self) -> Result<*mut SlabInfo, Self::Error> {
+                    unreachable!();
+                }
+
+                unsafe fn free_slab_info(&mut self, _slab_info_ptr: *mut SlabInfo) {
+                    unreachable!();
+                }
+
+                unsafe fn save_slab_info_ptr(
+                    &mut self,
+                    _object_page_addr: usize,
+                    _slab_info_ptr: *mut SlabInfo,
+                ) {
+                    unreachable!();
+                }
+
+                unsafe fn get_slab_info_ptr(&mut self, _object_page_addr: usize) -> *mut SlabInfo {
+                    unreachable!();
+                }
+
+                unsafe fn delete_slab_info_ptr(&mut self, _page_addr: usize) {
+                    unreachable!();
+                }
+            }
+
+            let mut cache: Cache<[u64; 16], TestMemoryBackend> = Cache::with_config(
+                PAGE_SIZE,
+                PAGE_SIZE,
+                ObjectSizeType::Small,
+                TestMemoryBackend::default(),
+                CacheConfig {
+                    on_oom: Some(on_oom),
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+
+            let mut allocated_ptrs = Vec::new();
+            for _ in 0..cache.objects_per_slab {
+                allocated_ptrs.push(cache.alloc());
+            }
+            assert_eq!(ON_OOM_CALLS.with(|calls| calls.get()), 0);
+
+            // Nothing was reclaimed, alloc fails
+            assert!(cache.alloc().is_null());
+            assert_eq!(ON_OOM_CALLS.with(|calls| calls.get()), 1);
+
+            // Memory was reclaimed, alloc retries
+            CAN_RECLAIM.with(|can_reclaim| can_reclaim.set(true));
+            let allocated_ptr = cache.alloc();
+            assert!(!allocated_ptr.is_null());
+            allocated_ptrs.push(allocated_ptr);
+            assert_eq!(ON_OOM_CALLS.with(|calls| calls.get()), 2);
+            assert_eq!(cache.backend_statistics().slab_allocs, 2);
+
+            for allocated_ptr in allocated_ptrs {
+                cache.free(allocated_ptr);
+            }
+            assert!(cache
+                .memory_backend
+                .std_memory_backend
+                .allocated_slab_addrs
+                .is_empty());
+        }
+    }
+
+    #[test]
+    fn single_object_slab() {
+        unsafe {
+            use std::cell::RefCell;
+            const PAGE_SIZE: usize = 4096;
+
+            std::thread_local! {
+                static TRANSITIONS: RefCell<Vec<(SlabListKind, SlabListKind)>> =
+                    const { RefCell::new(Vec::new()) };
+            }
+            fn on_transition(_slab_ptr: *mut u8, from: SlabListKind, to: SlabListKind) {
+                TRANSITIONS.with(|t| t.borrow_mut().push((from, to)));
+            }
+
+            for object_size_type in [ObjectSizeType::Small, ObjectSizeType::Large] {
+                for reclaim in [false, true] {
+                    // 3 KiB object in 4 KiB slab
+                    let mut cache: Cache<[u64; 384], StdMemoryBackend> = Cache::with_config(
+                        PAGE_SIZE,
+                        PAGE_SIZE,
+                        object_size_type,
+                        StdMemoryBackend::default(),
+                        CacheConfig {
+                            reclaim,
+                            on_transition: Some(on_transition),
+                            ..Default::default()
+                        },
+                    )
+                    .unwrap();
+                    assert_eq!(cache.objects_per_slab(), 1);
+                    TRANSITIONS.with(|t| t.borrow_mut().clear());
+
+                    let mut allocated_ptrs = Vec::new();
+                    for i in 0..3 {
+                        allocated_ptrs.push(cache.alloc());
+                        assert_eq!(cache.full_slabs_list.iter().count(), i + 1);
+                        assert!(cache.free_slabs_list_occupacy_more_75.is_empty());
+                        assert!(cache.free_slabs_list_occupacy_less_75.is_empty());
+                    }
+                    assert_eq!(cache.cache_statistics().full_slabs_number, 3);
+                    assert_eq!(cache.cache_statistics().free_slabs_number, 0);
+                    cache.verify_free_lists().unwrap();
+
+                    for (i, allocated_ptr) in allocated_ptrs.into_iter().enumerate() {
+                        cache.free(allocated_ptr);
+                        assert_eq!(cache.full_slabs_list.iter().count(), 2 - i);
+                        assert!(cache.free_slabs_list_occupacy_more_75.is_empty());
+                        assert_eq!(
+                            cache.free_slabs_list_occupacy_less_75.iter().count(),
+                            if reclaim { 0 } else { i + 1 }
+                        );
+                    }
+                    assert_eq!(cache.cache_statistics().full_slabs_number, 0);
+                    cache.verify_free_lists().unwrap();
+                    if reclaim {
+                        assert_eq!(cache.cache_statistics().free_slabs_number, 0);
+                        assert!(cache.memory_backend.allocated_slab_addrs.is_empty());
+                    } else {
+                        assert_eq!(cache.cache_statistics().free_slabs_number, 3);
+                        // Empty slabs are reused
+                        let allocated_ptr = cache.alloc();
+                        assert_eq!(cache.backend_statistics().slab_allocs, 3);
+                        cache.free(allocated_ptr);
+                        assert_eq!(cache.drain_free_slabs(), 3);
+                    }
+
+                    let transitions = TRANSITIONS.with(|t| t.borrow().clone());
+                    // Slabs skip free (>75) list
+                    assert_eq!(
+                        transitions[..6],
+                        [
+                            (SlabListKind::FreeLess75, SlabListKind::Full),
+                            (SlabListKind::FreeLess75, SlabListKind::Full),
+                            (SlabListKind::FreeLess75, SlabListKind::Full),
+                            (SlabListKind::Full, SlabListKind::FreeLess75),
+                            (SlabListKind::Full, SlabListKind::FreeLess75),
+                            (SlabListKind::Full, SlabListKind::FreeLess75),
+                        ]
+                    );
+                    assert!(!transitions.iter().any(|&(from, to)| {
+                        from == SlabListKind::FreeMore75 || to == SlabListKind::FreeMore75
+                    }));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn peek_next_slab() {
+        unsafe {
+            const PAGE_SIZE: usize = 4096;
+
+            for distribution_policy in [DistributionPolicy::Concentrate, DistributionPolicy::Spread]
+            {
+                let mut cache: Cache<[u64; 16], StdMemoryBackend> = Cache::with_config(
+                    PAGE_SIZE,
+                    PAGE_SIZE,
+                    ObjectSizeType::Small,
+                    StdMemoryBackend::default(),
+                    CacheConfig {
+                        distribution_policy,
+                        reclaim: false,
+                        ..Default::default()
+                    },
+                )
+                .unwrap();
+                assert_eq!(cache.peek_next_slab(), None);
+
+                let mut allocated_ptrs = Vec::new();
+                for _ in 0..cache.objects_per_slab * 3 {
+                    allocated_ptrs.push(cache.alloc());
+                }
+                // Full cache, alloc would allocate a new slab
+                assert_eq!(cache.peek_next_slab(), None);
+
+                // Make slabs with different occupancy
+                let mut rng = thread_rng();
+                allocated_ptrs.shuffle(&mut rng);
+                for allocated_ptr in allocated_ptrs.split_off(cache.objects_per_slab * 3 / 2) {
+                    cache.free(allocated_ptr);
+                }
+                for _ in 0..cache.objects_per_slab * 2 {
+                    let next_slab_ptr = cache.peek_next_slab();
+                    let (allocated_ptr, slab_allocated) = cache.alloc_tracked();
+                    if let Some(next_slab_ptr) = next_slab_ptr {
+                        assert!(!slab_allocated);
+                        assert_eq!(
+                            next_slab_ptr as usize,
+                            align_down(allocated_ptr as usize, PAGE_SIZE)
+                        );
+                    } else {
+                        assert!(slab_allocated);
+                    }
+                    allocated_ptrs.push(allocated_ptr);
+                    if rng.gen_bool(0.3) {
+                        cache.free(
+                            allocated_ptrs.swap_remove(rng.gen_range(0..allocated_ptrs.len())),
+                        );
+                    }
+                }
+
+                for allocated_ptr in allocated_ptrs {
+                    cache.free(allocated_ptr);
+                }
+                cache.drain_free_slabs();
+            }
+        }
+    }
+
+    #[cfg(feature = "stats_history")]
+    #[test]
+    fn stats_history() {
+        unsafe {
+            const PAGE_SIZE: usize = 4096;
+
+            let mut cache: Cache<[u64; 16], StdMemoryBackend> = Cache::new(
+                PAGE_SIZE,
+                PAGE_SIZE,
+                ObjectSizeType::Small,
+                StdMemoryBackend::default(),
+            )
+            .unwrap();
+            assert_eq!(cache.stats_history().count(), 0);
+
+            let mut allocated_ptrs = Vec::new();
+            for _ in 0..3 {
+                allocated_ptrs.push(cache.alloc());
+            }
+            let allocated_objects_numbers: Vec<usize> = cache
+                .stats_history()
+                .map(|sample| sample.allocated_objects_number)
+                .collect();
+            assert_eq!(allocated_objects_numbers, [1, 2, 3]);
+
+            // Only the last samples are kept
+            for _ in 0..STATS_HISTORY_LENGTH {
+                allocated_ptrs.push(cache.alloc());
+            }
+            cache.free(allocated_ptrs.pop().unwrap());
+            let allocated_objects_numbers: Vec<usize> = cache
+                .stats_history()
+                .map(|sample| sample.allocated_objects_number)
+                .collect();
+            assert_eq!(allocated_objects_numbers.len(), STATS_HISTORY_LENGTH);
+            assert_eq!(
+                allocated_objects_numbers[STATS_HISTORY_LENGTH - 3..],
+                [
+                    STATS_HISTORY_LENGTH + 2,
+                    STATS_HISTORY_LENGTH + 3,
+                    STATS_HISTORY_LENGTH + 2
+                ]
+            );
+            let last_sample = cache.stats_history().last().unwrap();
+            assert_eq!(
+                last_sample.free_objects_number,
+                cache.cache_statistics().free_objects_number
+            );
+
+            for allocated_ptr in allocated_ptrs {
+                cache.free(allocated_ptr);
+            }
+        }
+    }
+
+    #[test]
+    fn alloc_no_grow() {
+        unsafe {
+            const PAGE_SIZE: usize = 4096;
+
+            let mut cache: Cache<[u64; 16], StdMemoryBackend> = Cache::new(
+                PAGE_SIZE,
+                PAGE_SIZE,
+                ObjectSizeType::Small,
+                StdMemoryBackend::default(),
+            )
+            .unwrap();
+            assert!(cache.alloc_no_grow().is_null());
+            assert_eq!(cache.backend_statistics().slab_allocs, 0);
+
+            assert_eq!(cache.reserve_contiguous(2), 2);
+            let mut allocated_ptrs = Vec::new();
+            for _ in 0..cache.objects_per_slab * 2 {
+                let allocated_ptr = cache.alloc_no_grow();
+                assert!(!allocated_ptr.is_null());
+                allocated_ptrs.push(allocated_ptr);
+            }
+            assert!(cache.alloc_no_grow().is_null());
+            assert_eq!(cache.backend_statistics().slab_allocs, 2);
+            cache.verify_free_lists().unwrap();
+
+            for allocated_ptr in allocated_ptrs {
+                cache.free(allocated_ptr);
+            }
+            assert!(cache.memory_backend.allocated_slab_addrs.is_empty());
+        }
+    }
+
+    #[cfg(debug_assertions)]
+    #[test]
+    #[should_panic(expected = "overlapping another slab")]
+    fn overlapping_slab() {
+        unsafe {
+            const PAGE_SIZE: usize = 4096;
+
+            // Returns the same slab every time
+            struct TestMemoryBackend {
+                slab_ptr: *mut u8,
+            }
+
+            impl MemoryBackend for TestMemoryBackend {
+                type Error = OutOfMemory;
+
+                unsafe fn alloc_slab(
+                    &mut self,
+                    slab_size: usize,
+                    page_size: usize,
+                ) -> Result<*mut u8, Self::Error> {
+                    if self.slab_ptr.is_null() {
+                        self.slab_ptr =
+                            alloc(Layout::from_size_align(slab_size, page_size).unwrap());
+                    }
+                    Ok(self.slab_ptr)
+                }
+
+                unsafe fn free_slab(
+                    &mut self,
+                    _slab_ptr: *mut u8,
+                    _slab_size: usize,
+                    _page_size: usize,
+                ) {
+                    unreachable!();
+                }
+
+                unsafe fn alloc_slab_info(&mut self) -> Result<*mut SlabInfo, Self::Error> {
+                    unreachable!();
+                }
+
+                unsafe fn free_slab_info(&mut self, _slab_info_ptr: *mut SlabInfo) {
+                    unreachable!();
+                }
+
+                unsafe fn save_slab_info_ptr(
+                    &mut self,
+                    _object_page_addr: usize,
+                    _slab_info_ptr: *mut SlabInfo,
+                ) {
+                    unreachable!();
+                }
+
+                unsafe fn get_slab_info_ptr(&mut self, _object_page_addr: usize) -> *mut SlabInfo {
+                    unreachable!();
+                }
+
+                unsafe fn delete_slab_info_ptr(&mut self, _page_addr: usize) {
+                    unreachable!();
+                }
+            }
+
+            let mut cache: Cache<[u64; 16], TestMemoryBackend> = Cache::new(
+                PAGE_SIZE,
+                PAGE_SIZE,
+                ObjectSizeType::Small,
+                TestMemoryBackend {
+                    slab_ptr: null_mut(),
+                },
+            )
+            .unwrap();
+            for _ in 0..cache.objects_per_slab + 1 {
+                let _ = cache.alloc();
+            }
+        }
+    }
+
+    #[test]
+    fn slab_size_for_objects() {
+        const PAGE_SIZE: usize = 4096;
+        type C = Cache<u8, StdMemoryBackend>;
+
+        assert_eq!(
+            C::slab_size_for_objects(64, 8, PAGE_SIZE, ObjectSizeType::Large, 64),
+            Ok(PAGE_SIZE)
+        );
+        // SlabInfo takes place of one object
+        assert_eq!(
+            C::slab_size_for_objects(64, 8, PAGE_SIZE, ObjectSizeType::Small, 64),
+            Ok(PAGE_SIZE * 2)
+        );
+        assert_eq!(
+            C::slab_size_for_objects(3072, 8, PAGE_SIZE, ObjectSizeType::Large, 64),
+            Ok(PAGE_SIZE * 64)
+        );
+        for object_size_type in [ObjectSizeType::Small, ObjectSizeType::Large] {
+            for min_objects in [0, 1, 7, 64, 100] {
+                let slab_size =
+                    C::slab_size_for_objects(384, 16, PAGE_SIZE, object_size_type, min_objects)
+                        .unwrap();
+                let cache: Cache<[u128; 24], StdMemoryBackend> = Cache::new(
+                    slab_size,
+                    PAGE_SIZE,
+                    object_size_type,
+                    StdMemoryBackend::default(),
+                )
+                .unwrap();
+                assert!(cache.objects_per_slab() >= min_objects);
+                if slab_size > PAGE_SIZE {
+                    assert!(Cache::<[u128; 24], StdMemoryBackend>::new(
+                        slab_size / 2,
+                        PAGE_SIZE,
+                        object_size_type,
+                        StdMemoryBackend::default(),
+                    )
+                    .map_or(true, |cache| cache.objects_per_slab() < min_objects));
+                }
+            }
+        }
+
+        assert!(C::slab_size_for_objects(64, 3, PAGE_SIZE, ObjectSizeType::Large, 1).is_err());
+        assert!(
+            C::slab_size_for_objects(64, 8, PAGE_SIZE, ObjectSizeType::Large, usize::MAX).is_err()
+        );
+    }
+
+    #[test]
+    fn free_whole_slab() {
+        unsafe {
+            const PAGE_SIZE: usize = 4096;
+
+            for (slab_size, object_size_type) in [
+                (PAGE_SIZE, ObjectSizeType::Small),
+                (PAGE_SIZE * 4, ObjectSizeType::Small),
+                (PAGE_SIZE * 2, ObjectSizeType::Large),
+            ] {
+                let mut cache: Cache<[u64; 16], StdMemoryBackend> = Cache::with_config(
+                    slab_size,
+                    PAGE_SIZE,
+                    object_size_type,
+                    StdMemoryBackend::default(),
+                    CacheConfig {
+                        reclaim: false,
+                        ..Default::default()
+                    },
+                )
+                .unwrap();
+
+                // Full slab, slab with a few free objects and slab with a few allocated objects
+                let objects_per_slab = cache.objects_per_slab();
+                let mut allocated_ptrs = Vec::new();
+                for _ in 0..objects_per_slab * 3 {
+                    allocated_ptrs.push(cache.alloc());
+                }
+                for allocated_ptr in
+                    allocated_ptrs.drain(objects_per_slab * 2 - 3..objects_per_slab * 3 - 3)
+                {
+                    cache.free(allocated_ptr);
+                }
+                let slab_addrs: Vec<usize> = cache
+                    .slab_base_addresses()
+                    .map(|(slab_addr, _)| slab_addr)
+                    .collect();
+                assert_eq!(slab_addrs.len(), 3);
+
+                for (i, slab_addr) in slab_addrs.into_iter().enumerate() {
+                    cache.free_whole_slab(slab_addr as *const u8);
+                    allocated_ptrs.retain(|allocated_ptr| {
+                        !(slab_addr..slab_addr + slab_size).contains(&(*allocated_ptr as usize))
+                    });
+                    let cache_statistics = cache.cache_statistics();
+                    assert_eq!(
+                        cache_statistics.free_slabs_number + cache_statistics.full_slabs_number,
+                        2 - i
+                    );
+                    assert_eq!(
+                        cache_statistics.allocated_objects_number,
+                        allocated_ptrs.len()
+                    );
+                    assert_eq!(
+                        cache_statistics.free_objects_number,
+                        (2 - i) * objects_per_slab - allocated_ptrs.len()
+                    );
+                    assert_eq!(cache.memory_backend.allocated_slab_addrs.len(), 2 - i);
+                    cache.verify_free_lists().unwrap();
+                }
+                assert!(cache.memory_backend.allocated_slab_info_addrs.is_empty());
+                assert!(cache.memory_backend.ht_saved_slab_infos.is_empty());
+            }
+        }
+    }
+
+    #[test]
+    fn object_alignment() {
+        unsafe {
+            const PAGE_SIZE: usize = 4096;
+
+            for object_size_type in [ObjectSizeType::Small, ObjectSizeType::Large] {
+                // 48 bytes object
+                let mut cache: Cache<[u64; 6], StdMemoryBackend> = Cache::with_config(
+                    PAGE_SIZE,
+                    PAGE_SIZE,
+                    object_size_type,
+                    StdMemoryBackend::default(),
+                    CacheConfig {
+                        object_alignment: Some(64),
+                        ..Default::default()
+                    },
+                )
+                .unwrap();
+                assert_eq!(cache.object_size(), 64);
+                if object_size_type == ObjectSizeType::Large {
+                    assert_eq!(cache.objects_per_slab(), PAGE_SIZE / 64);
+                }
+
+                let mut allocated_ptrs = Vec::new();
+                for _ in 0..cache.objects_per_slab() * 2 {
+                    let allocated_ptr = cache.alloc();
+                    assert_eq!(allocated_ptr as usize % 64, 0);
+                    allocated_ptr.write([u64::MAX; 6]);
+                    allocated_ptrs.push(allocated_ptr);
+                }
+                cache.verify_free_lists().unwrap();
+                for allocated_ptr in allocated_ptrs {
+                    cache.free(allocated_ptr);
+                }
+
+                // Type alignment is bigger
+                let cache: Cache<[u64; 6], StdMemoryBackend> = Cache::with_config(
+                    PAGE_SIZE,
+                    PAGE_SIZE,
+                    object_size_type,
+                    StdMemoryBackend::default(),
+                    CacheConfig {
+                        object_alignment: Some(4),
+                        ..Default::default()
+                    },
+                )
+                .unwrap();
+                assert_eq!(cache.object_size(), 48);
+            }
+
+            for object_alignment in [48, PAGE_SIZE * 2] {
+                assert!(Cache::<[u64; 6], StdMemoryBackend>::with_config(
+                    PAGE_SIZE * 4,
+                    PAGE_SIZE,
+                    ObjectSizeType::Large,
+                    StdMemoryBackend::default(),
+                    CacheConfig {
+                        object_alignment: Some(object_alignment),
+                        ..Default::default()
+                    },
+                )
+                .is_err());
+            }
+        }
+    }
+
+    #[test]
+    fn statistics_delta() {
+        unsafe {
+            const PAGE_SIZE: usize = 4096;
+
+            let mut cache: Cache<[u64; 16], StdMemoryBackend> = Cache::new(
+                PAGE_SIZE,
+                PAGE_SIZE,
+                ObjectSizeType::Small,
+                StdMemoryBackend::default(),
+            )
+            .unwrap();
+            let objects_per_slab = cache.objects_per_slab() as isize;
+
+            let mut allocated_ptrs = Vec::new();
+            for _ in 0..objects_per_slab + 3 {
+                allocated_ptrs.push(cache.alloc());
+            }
+            let first_sample = cache.cache_statistics();
+            assert_eq!(
+                first_sample.delta(&first_sample),
+                CacheStatisticsDelta {
+                    free_slabs_number: 0,
+                    full_slabs_number: 0,
+                    free_objects_number: 0,
+                    allocated_objects_number: 0,
+                }
+            );
+
+            // Allocated count decreases
+            for allocated_ptr in allocated_ptrs.drain(..5) {
+                cache.free(allocated_ptr);
+            }
+            let second_sample = cache.cache_statistics();
+            assert_eq!(
+                second_sample.delta(&first_sample),
+                CacheStatisticsDelta {
+                    free_slabs_number: 1,
+                    full_slabs_number: -1,
+                    free_objects_number: 5,
+                    allocated_objects_number: -5,
+                }
+            );
+            assert_eq!(
+                first_sample.delta(&second_sample).allocated_objects_number,
+                5
+            );
+
+            for allocated_ptr in allocated_ptrs {
+                cache.free(allocated_ptr);
+            }
+            assert_eq!(
+                cache.cache_statistics().delta(&first_sample),
+                CacheStatisticsDelta {
+                    free_slabs_number: -1,
+                    full_slabs_number: -1,
+                    free_objects_number: 3 - objects_per_slab,
+                    allocated_objects_number: -(objects_per_slab + 3),
+                }
+            );
+        }
+    }
+
+    #[test]
+    fn utilization_permille() {
+        unsafe {
+            const PAGE_SIZE: usize = 4096;
+
+            let mut cache: Cache<[u64; 16], StdMemoryBackend> = Cache::new(
+                PAGE_SIZE,
+                PAGE_SIZE,
+                ObjectSizeType::Large,
+                StdMemoryBackend::default(),
+            )
+            .unwrap();
+            assert_eq!(cache.objects_per_slab(), 32);
+            assert_eq!(cache.utilization_permille(), 0);
+
+            let mut allocated_ptrs = Vec::new();
+            for _ in 0..8 {
+                allocated_ptrs.push(cache.alloc());
+            }
+            assert_eq!(cache.utilization_permille(), 250);
+            for _ in 0..24 {
+                allocated_ptrs.push(cache.alloc());
+            }
+            assert_eq!(cache.utilization_permille(), 1000);
+            allocated_ptrs.push(cache.alloc());
+            assert_eq!(cache.cache_statistics().utilization_permille(), 515);
+
+            for allocated_ptr in allocated_ptrs {
+                cache.free(allocated_ptr);
+            }
+            assert_eq!(cache.utilization_permille(), 0);
+        }
+    }
+
+    #[test]
+    fn small_info_placement_head() {
+        unsafe {
+            const PAGE_SIZE: usize = 4096;
+
+            for slab_size in [PAGE_SIZE, PAGE_SIZE * 4] {
+                for lazy_carve in [false, true] {
+                    let mut cache: Cache<[u64; 5], StdMemoryBackend> = Cache::with_config(
+                        slab_size,
+                        PAGE_SIZE,
+                        ObjectSizeType::Small,
+                        StdMemoryBackend::default(),
+                        CacheConfig {
+                            small_info_placement: SmallInfoPlacement::Head,
+                            lazy_carve,
+                            ..Default::default()
+                        },
+                    )
+                    .unwrap();
+                    let objects_offset = size_of::<SlabInfo>().next_multiple_of(8);
+                    assert_eq!(cache.objects_per_slab(), (slab_size - objects_offset) / 40);
+
+                    let mut allocated_ptrs = Vec::new();
+                    for i in 0..cache.objects_per_slab() * 3 {
+                        let allocated_ptr = cache.alloc();
+                        // Objects follow SlabInfo
+                        let slab_addr = *cache
+                            .memory_backend
+                            .allocated_slab_addrs
+                            .iter()
+                            .find(|slab_addr| {
+                                (**slab_addr..**slab_addr + slab_size)
+                                    .contains(&(allocated_ptr as usize))
+                            })
+                            .unwrap();
+                        assert!(allocated_ptr as usize >= slab_addr + size_of::<SlabInfo>());
+                        assert!(allocated_ptr as usize + 40 <= slab_addr + slab_size);
+                        if i == 0 {
+                            assert_eq!(
+                                allocated_ptr as usize,
+                                cache.predicted_slot_addr(
+                                    slab_addr as *const u8,
+                                    cache.objects_per_slab() - 1
+                                )
+                            );
+                        }
+                        allocated_ptr.write([i as u64; 5]);
+                        allocated_ptrs.push(allocated_ptr);
+                    }
+                    cache.verify_free_lists().unwrap();
+
+                    allocated_ptrs.shuffle(&mut thread_rng());
+                    for allocated_ptr in allocated_ptrs.drain(..cache.objects_per_slab()) {
+                        cache.free(allocated_ptr);
+                    }
+                    cache.verify_free_lists().unwrap();
+                    for allocated_ptr in allocated_ptrs {
+                        cache.free(allocated_ptr);
+                    }
+                    assert!(cache.memory_backend.allocated_slab_addrs.is_empty());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn partial_slabs_by_emptiness() {
+        unsafe {
+            const PAGE_SIZE: usize = 4096;
+
+            for distribution_policy in [DistributionPolicy::Concentrate, DistributionPolicy::Spread]
+            {
+                let mut cache: Cache<[u64; 16], StdMemoryBackend> = Cache::with_config(
+                    PAGE_SIZE,
+                    PAGE_SIZE,
+                    ObjectSizeType::Small,
+                    StdMemoryBackend::default(),
+                    CacheConfig {
+                        distribution_policy,
+                        reclaim: false,
+                        ..Default::default()
+                    },
+                )
+                .unwrap();
+                let objects_per_slab = cache.objects_per_slab();
+
+                let mut allocated_ptrs = Vec::new();
+                for _ in 0..objects_per_slab * 8 {
+                    allocated_ptrs.push(cache.alloc());
+                }
+                let mut rng = thread_rng();
+                allocated_ptrs.shuffle(&mut rng);
+                for allocated_ptr in allocated_ptrs.split_off(objects_per_slab * 4) {
+                    cache.free(allocated_ptr);
+                }
+                // Leave one full and one empty slab
+                for _ in 0..objects_per_slab {
+                    allocated_ptrs.push(cache.alloc());
+                }
+
+                let partial_slabs: Vec<SlabStats> = cache.partial_slabs_by_emptiness().collect();
+                assert!(partial_slabs
+                    .windows(2)
+                    .all(|w| w[0].free_objects_number >= w[1].free_objects_number));
+                let mut expected_partial_slabs_number = 0;
+                for (slab_addr, _) in cache.slab_base_addresses() {
+                    let allocated_objects_number = allocated_ptrs
+                        .iter()
+                        .filter(|allocated_ptr| {
+                            (slab_addr..slab_addr + PAGE_SIZE).contains(&(**allocated_ptr as usize))
+                        })
+                        .count();
+                    if allocated_objects_number != 0 && allocated_objects_number != objects_per_slab
+                    {
+                        expected_partial_slabs_number += 1;
+                        assert!(partial_slabs.contains(&SlabStats {
+                            slab_addr,
+                            free_objects_number: objects_per_slab - allocated_objects_number,
+                            allocated_objects_number,
+                        }));
+                    }
+                }
+                assert_eq!(partial_slabs.len(), expected_partial_slabs_number);
+
+                for allocated_ptr in allocated_ptrs {
+                    cache.free(allocated_ptr);
+                }
+                assert_eq!(cache.partial_slabs_by_emptiness().count(), 0);
+                cache.drain_free_slabs();
+            }
+        }
+    }
+
+    #[test]
+    fn slab_size_less_than_page_size() {
+        for (slab_size, page_size, error) in [
+            (2048, 4096, "Slab size is less than page size"),
+            (0, 4096, "Slab size is less than page size"),
+            (4096, 0, "Page size is zero"),
+            (0, 0, "Page size is zero"),
+        ] {
+            for object_size_type in [ObjectSizeType::Small, ObjectSizeType::Large] {
+                assert_eq!(
+                    Cache::<[u64; 4], StdMemoryBackend>::new(
+                        slab_size,
+                        page_size,
+                        object_size_type,
+                        StdMemoryBackend::default(),
+                    )
+                    .err(),
+                    Some(error)
+                );
+            }
+        }
+        assert!(Cache::<[u64; 4], StdMemoryBackend>::slab_size_for_objects(
+            32,
+            8,
+            0,
+            ObjectSizeType::Small,
+            1
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn self_test() {
+        unsafe {
+            const PAGE_SIZE: usize = 4096;
+
+            for (slab_size, object_size_type) in [
+                (PAGE_SIZE, ObjectSizeType::Small),
+                (PAGE_SIZE * 2, ObjectSizeType::Small),
+                (PAGE_SIZE, ObjectSizeType::Large),
+            ] {
+                for lazy_carve in [false, true] {
+                    let mut cache: Cache<[u64; 32], StdMemoryBackend> = Cache::with_config(
+                        slab_size,
+                        PAGE_SIZE,
+                        object_size_type,
+                        StdMemoryBackend::default(),
+                        CacheConfig {
+                            lazy_carve,
+                            ..Default::default()
+                        },
+                    )
+                    .unwrap();
+                    cache.self_test(10000).unwrap();
+                    assert!(cache.is_empty());
+                    assert!(cache.memory_backend.allocated_slab_addrs.is_empty());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn try_alloc() {
+        unsafe {
+            const PAGE_SIZE: usize = 4096;
+
+            #[derive(Debug, PartialEq)]
+            enum TestError {
+                OutOfMemory,
+                RegionNotMapped,
+            }
+
+            impl From<OutOfMemory> for TestError {
+                fn from(_: OutOfMemory) -> Self {
+                    TestError::OutOfMemory
+                }
+            }
+
+            // Reports different failure reasons
+            #[derive(Default)]
+            struct TestMemoryBackend {
+                std_memory_backend: StdMemoryBackend,
+                region_mapped: bool,
+                slab_info_memory: bool,
+            }
+
+            impl MemoryBackend for TestMemoryBackend {
+                type Error = TestError;
+
+                unsafe fn alloc_slab(
+                    &mut self,
+                    slab_size: usize,
+                    page_size: usize,
+                ) -> Result<*mut u8, Self::Error> {
+                    if !self.region_mapped {
+                        return Err(TestError::RegionNotMapped);
+                    }
+                    Ok(self.std_memory_backend.alloc_slab(slab_size, page_size)?)
+                }
+
+                unsafe fn free_slab(
+                    &mut self,
+                    slab_ptr: *mut u8,
+                    slab_size: usize,
+                    page_size: usize,
+                ) {
+                    self.std_memory_backend
+                        .free_slab(slab_ptr, slab_size, page_size);
+                }
+
+                unsafe fn alloc_slab_info(&mut self) -> Result<*mut SlabInfo, Self::Error> {
+                    if !self.slab_info_memory {
+                        return Err(OutOfMemory.into());
+                    }
+                    Ok(self.std_memory_backend.alloc_slab_info()?)
+                }
+
+                unsafe fn free_slab_info(&mut self, slab_info_ptr: *mut SlabInfo) {
+                    self.std_memory_backend.free_slab_info(slab_info_ptr);
+                }
+
+                unsafe fn save_slab_info_ptr(
+                    &mut self,
+                    object_page_addr: usize,
+                    slab_info_ptr: *mut SlabInfo,
+                ) {
+                    self.std_memory_backend
+                        .save_slab_info_ptr(object_page_addr, slab_info_ptr);
+                }
+
+                unsafe fn get_slab_info_ptr(&mut self, object_page_addr: usize) -> *mut SlabInfo {
+                    self.std_memory_backend.get_slab_info_ptr(object_page_addr)
+                }
+
+                unsafe fn delete_slab_info_ptr(&mut self, page_addr: usize) {
+                    self.std_memory_backend.delete_slab_info_ptr(page_addr);
+                }
+            }
+
+            let mut cache: Cache<[u64; 64], TestMemoryBackend> = Cache::with_config(
+                PAGE_SIZE,
+                PAGE_SIZE,
+                ObjectSizeType::Large,
+                TestMemoryBackend::default(),
+                CacheConfig {
+                    max_allocated_objects: Some(8),
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+
+            assert_eq!(
+                cache.try_alloc(),
+                Err(CacheError::Backend(TestError::RegionNotMapped))
+            );
+            assert!(cache.alloc().is_null());
+
+            // Slab is allocated, but SlabInfo is not, slab must be returned
+            cache.memory_backend.region_mapped = true;
+            assert_eq!(
+                cache.try_alloc(),
+                Err(CacheError::Backend(TestError::OutOfMemory))
+            );
+            assert_eq!(cache.backend_statistics().slab_allocs, 1);
+            assert_eq!(cache.backend_statistics().slab_frees, 1);
+            assert!(cache
+                .memory_backend
+                .std_memory_backend
+                .allocated_slab_addrs
+                .is_empty());
+
+            cache.memory_backend.slab_info_memory = true;
+            let mut allocated_ptrs = Vec::new();
+            for _ in 0..8 {
+                allocated_ptrs.push(cache.try_alloc().unwrap());
+            }
+            assert_eq!(
+                cache.try_alloc(),
+                Err(CacheError::Cache(
+                    "Maximum allocated objects number is reached"
+                ))
+            );
+
+            for allocated_ptr in allocated_ptrs {
+                cache.free(allocated_ptr);
+            }
+            assert!(cache
+                .memory_backend
+                .std_memory_backend
+                .allocated_slab_addrs
+                .is_empty());
+        }
+    }
+
+    #[test]
+    fn alloc_indexed() {
+        unsafe {
+            use std::collections::HashSet;
+            const PAGE_SIZE: usize = 4096;
+
+            for object_size_type in [ObjectSizeType::Small, ObjectSizeType::Large] {
+                let mut cache: Cache<[u64; 12], StdMemoryBackend> = Cache::new(
+                    PAGE_SIZE,
+                    PAGE_SIZE,
+                    object_size_type,
+                    StdMemoryBackend::default(),
+                )
+                .unwrap();
+
+                let mut allocated = Vec::new();
+                let mut slab_indexes = HashSet::new();
+                for _ in 0..cache.objects_per_slab * 3 {
+                    let (allocated_ptr, index) = cache.alloc_indexed().unwrap();
+                    assert!((index as usize) < cache.objects_per_slab);
+                    // Slab is one page, objects start at slab start
+                    let slab_addr = align_down(allocated_ptr as usize, PAGE_SIZE);
+                    assert_eq!(
+                        allocated_ptr as usize,
+                        slab_addr + index as usize * size_of::<[u64; 12]>()
+                    );
+                    assert!(slab_indexes.insert((slab_addr, index)));
+                    allocated.push(allocated_ptr);
+                }
+
+                for allocated_ptr in allocated {
+                    cache.free(allocated_ptr);
+                }
+                assert!(cache.memory_backend.allocated_slab_addrs.is_empty());
+            }
+        }
+    }
+
+    #[test]
+    fn slab_info_canary() {
+        unsafe {
+            use std::panic::{catch_unwind, AssertUnwindSafe};
+            const PAGE_SIZE: usize = 4096;
+
+            let mut cache: Cache<[u64; 8], StdMemoryBackend> = Cache::new(
+                PAGE_SIZE,
+                PAGE_SIZE,
+                ObjectSizeType::Small,
+                StdMemoryBackend::default(),
+            )
+            .unwrap();
+            let allocated_ptr = cache.alloc();
+            assert!(!allocated_ptr.is_null());
+            assert_eq!(cache.verify_free_lists(), Ok(()));
+
+            // Overrun of the last object overwrites SlabInfo
+            let slab_ptr = cache.memory_backend.allocated_slab_addrs[0] as *mut u8;
+            let slab_info_ptr = calculate_slab_info_addr_in_small_object_cache(
+                slab_ptr,
+                PAGE_SIZE,
+                SmallInfoPlacement::Tail,
+            ) as *mut u8;
+            let mut saved_slab_info = [0u8; size_of::<SlabInfo>()];
+            saved_slab_info
+                .as_mut_ptr()
+                .copy_from(slab_info_ptr, size_of::<SlabInfo>());
+            slab_info_ptr.write_bytes(0xAB, size_of::<SlabInfo>());
+
+            let panic_payload = catch_unwind(AssertUnwindSafe(|| cache.free(allocated_ptr)))
+                .expect_err("Free with corrupted SlabInfo must panic");
+            let panic_message = panic_payload.downcast_ref::<String>().unwrap();
+            assert!(panic_message.contains("SlabInfo canary is corrupted"));
+
+            // Restore SlabInfo, lists are dropped with the cache
+            slab_info_ptr.copy_from(saved_slab_info.as_ptr(), size_of::<SlabInfo>());
+            cache.free(allocated_ptr);
+            assert!(cache.memory_backend.allocated_slab_addrs.is_empty());
+        }
+    }
+
+    #[test]
+    fn memory_backend_accessors() {
+        unsafe {
+            const PAGE_SIZE: usize = 4096;
+
+            let mut cache: Cache<[u64; 8], StdMemoryBackend> = Cache::new(
+                PAGE_SIZE,
+                PAGE_SIZE,
+                ObjectSizeType::Large,
+                StdMemoryBackend::default(),
+            )
+            .unwrap();
+            let allocated_ptr = cache.alloc();
+            assert!(!allocated_ptr.is_null());
+            assert_eq!(cache.memory_backend().allocated_slab_addrs.len(), 1);
+            assert_eq!(cache.memory_backend().allocated_slab_info_addrs.len(), 1);
+
+            cache.memory_backend_mut().get_slab_info_ptr_calls = 0;
+            cache.free(allocated_ptr);
+            assert_eq!(cache.memory_backend().get_slab_info_ptr_calls, 1);
+            assert!(cache.memory_backend().allocated_slab_addrs.is_empty());
+        }
+    }
+
+    #[cfg(feature = "alloc_sites")]
+    #[test]
+    fn alloc_sites() {
+        unsafe {
+            const PAGE_SIZE: usize = 4096;
+
+            let mut cache: Cache<[u64; 8], StdMemoryBackend> = Cache::new(
+                PAGE_SIZE,
+                PAGE_SIZE,
+                ObjectSizeType::Small,
+                StdMemoryBackend::default(),
+            )
+            .unwrap();
+
+            let mut allocated = Vec::new();
+            for i in 0..100 {
+                let site = i % 3;
+                allocated.push((cache.alloc_tagged(site), site));
+            }
+            let mut live_by_site: Vec<(u32, usize)> = cache.live_by_site().collect();
+            live_by_site.sort();
+            assert_eq!(live_by_site, [(0, 34), (1, 33), (2, 33)]);
+
+            // Site 1 has freed all its objects
+            allocated.retain(|&(allocated_ptr, site)| {
+                if site == 1 {
+                    cache.free_tagged(allocated_ptr, site);
+                }
+                site != 1
+            });
+            let mut live_by_site: Vec<(u32, usize)> = cache.live_by_site().collect();
+            live_by_site.sort();
+            assert_eq!(live_by_site, [(0, 34), (2, 33)]);
+
+            // All counters are taken, objects of new sites are not counted
+            for site in 100..100 + ALLOC_SITES_NUMBER as u32 {
+                allocated.push((cache.alloc_tagged(site), site));
+            }
+            assert_eq!(cache.live_by_site().count(), ALLOC_SITES_NUMBER);
+            assert!(cache
+                .live_by_site()
+                .all(|(site, _)| site != 100 + ALLOC_SITES_NUMBER as u32 - 1));
+
+            for (allocated_ptr, site) in allocated {
+                cache.free_tagged(allocated_ptr, site);
+            }
+            assert_eq!(cache.live_by_site().count(), 0);
+            assert!(cache.memory_backend.allocated_slab_addrs.is_empty());
+        }
+    }
+
+    #[test]
+    fn reserve_objects_and_slabs() {
+        unsafe {
+            const PAGE_SIZE: usize = 4096;
+
+            let mut cache: Cache<[u64; 8], StdMemoryBackend> = Cache::new(
+                PAGE_SIZE,
+                PAGE_SIZE,
+                ObjectSizeType::Small,
+                StdMemoryBackend::default(),
+            )
+            .unwrap();
+
+            cache.reserve_slabs(3).unwrap();
+            assert_eq!(cache.cache_statistics().free_slabs_number, 3);
+            assert_eq!(
+                cache.cache_statistics().free_objects_number,
+                3 * cache.objects_per_slab
+            );
+
+            // Free objects are enough, nothing is allocated
+            cache.reserve_objects(3 * cache.objects_per_slab).unwrap();
+            assert_eq!(cache.cache_statistics().free_slabs_number, 3);
+            // One more object needs one more slab
+            cache
+                .reserve_objects(3 * cache.objects_per_slab + 1)
+                .unwrap();
+            assert_eq!(cache.cache_statistics().free_slabs_number, 4);
+            // Slabs are added regardless of free objects
+            cache.reserve_slabs(1).unwrap();
+            assert_eq!(cache.cache_statistics().free_slabs_number, 5);
+            assert_eq!(cache.backend_statistics().slab_allocs, 5);
+
+            assert_eq!(cache.drain_free_slabs(), 5);
+            assert!(cache.memory_backend.allocated_slab_addrs.is_empty());
+        }
+    }
+
+    #[test]
+    fn sort_full_slabs() {
+        unsafe {
+            const PAGE_SIZE: usize = 4096;
+            const SLABS_NUMBER: usize = 8;
+
+            let mut cache: Cache<[u64; 64], StdMemoryBackend> = Cache::with_config(
+                PAGE_SIZE,
+                PAGE_SIZE,
+                ObjectSizeType::Small,
+                StdMemoryBackend::default(),
+                CacheConfig {
+                    sort_full_slabs: true,
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+
+            let mut allocated_ptrs = Vec::new();
+            for _ in 0..SLABS_NUMBER * cache.objects_per_slab {
+                allocated_ptrs.push(cache.alloc());
+            }
+            let full_slab_addrs = |cache: &Cache<[u64; 64], StdMemoryBackend>| {
+                let slab_addrs: Vec<usize> = cache
+                    .slab_base_addresses()
+                    .map(|(slab_addr, _)| slab_addr)
+                    .collect();
+                slab_addrs[slab_addrs.len() - cache.cache_statistics().full_slabs_number..].to_vec()
+            };
+            let mut slab_addrs = full_slab_addrs(&cache);
+            assert_eq!(slab_addrs.len(), SLABS_NUMBER);
+            assert!(slab_addrs.is_sorted());
+
+            // Slabs become free and full again in random order
+            let mut rng = thread_rng();
+            for _ in 0..100 {
+                let i = rng.gen_range(0..allocated_ptrs.len());
+                cache.free(allocated_ptrs[i]);
+                allocated_ptrs[i] = cache.alloc();
+                slab_addrs = full_slab_addrs(&cache);
+                assert!(slab_addrs.is_sorted());
+            }
+            assert_eq!(slab_addrs.len(), SLABS_NUMBER);
+
+            for allocated_ptr in allocated_ptrs {
+                cache.free(allocated_ptr);
+            }
+            assert!(cache.memory_backend.allocated_slab_addrs.is_empty());
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "not page aligned slab")]
+    fn not_page_aligned_slab() {
+        unsafe {
+            const PAGE_SIZE: usize = 4096;
+
+            // Returns slab shifted from the page start
+            #[derive(Default)]
+            struct TestMemoryBackend {
+                std_memory_backend: StdMemoryBackend,
+            }
+
+            impl MemoryBackend for TestMemoryBackend {
+                type Error = OutOfMemory;
+
+                unsafe fn alloc_slab(
+                    &mut self,
+                    slab_size: usize,
+                    page_size: usize,
+                ) -> Result<*mut u8, Self::Error> {
+                    Ok(self
+                        .std_memory_backend
+                        .alloc_slab(slab_size, page_size)?
+                        .add(64))
+                }
+
+                unsafe fn free_slab(
+                    &mut self,
+                    _slab_ptr: *mut u8,
+                    _slab_size: usize,
+                    _page_size: usize,
+                ) {
+                    unreachable!();
+                }
+
+                unsafe fn alloc_slab_info(&mut self) -> Result<*mut SlabInfo, Self::Error> {
+                    unreachable!();
+                }
+
+                unsafe fn free_slab_info(&mut self, _slab_info_ptr: *mut SlabInfo) {
+                    unreachable!();
+                }
+
+                unsafe fn save_slab_info_ptr(
+                    &mut self,
+                    _object_page_addr: usize,
+                    _slab_info_ptr: *mut SlabInfo,
+                ) {
+                    unreachable!();
+                }
+
+                unsafe fn get_slab_info_ptr(&mut self, _object_page_addr: usize) -> *mut SlabInfo {
+                    unreachable!();
+                }
+
+                unsafe fn delete_slab_info_ptr(&mut self, _page_addr: usize) {
+                    unreachable!();
+                }
+            }
+
+            let mut cache: Cache<[u64; 8], TestMemoryBackend> = Cache::new(
+                PAGE_SIZE,
+                PAGE_SIZE,
+                ObjectSizeType::Small,
+                TestMemoryBackend::default(),
+            )
+            .unwrap();
+            let _ = cache.alloc();
+        }
+    }
+
+    #[test]
+    fn free_slabs_where() {
+        unsafe {
+            const PAGE_SIZE: usize = 4096;
+
+            let mut cache: Cache<[u64; 8], StdMemoryBackend> = Cache::new(
+                PAGE_SIZE,
+                PAGE_SIZE,
+                ObjectSizeType::Small,
+                StdMemoryBackend::default(),
+            )
+            .unwrap();
+            cache.reserve_slabs(4).unwrap();
+            let allocated_ptr = cache.alloc();
+            let used_slab_addr = align_down(allocated_ptr as usize, PAGE_SIZE);
+            let other_slab_addr = *cache
+                .memory_backend
+                .allocated_slab_addrs
+                .iter()
+                .find(|&&slab_addr| slab_addr != used_slab_addr)
+                .unwrap();
+
+            // Slab with allocated object is skipped
+            let removed_slab_addrs = [used_slab_addr, other_slab_addr];
+            assert_eq!(
+                cache
+                    .free_slabs_where(|slab_ptr| removed_slab_addrs.contains(&(slab_ptr as usize))),
+                1
+            );
+            assert_eq!(cache.cache_statistics().free_slabs_number, 3);
+            assert!(!cache
+                .memory_backend
+                .allocated_slab_addrs
+                .contains(&other_slab_addr));
+            assert!(cache
+                .memory_backend
+                .allocated_slab_addrs
+                .contains(&used_slab_addr));
+
+            // Slab is released by free, because it is empty
+            cache.free(allocated_ptr);
+            assert_eq!(cache.free_slabs_where(|_| true), 2);
+            assert!(cache.memory_backend.allocated_slab_addrs.is_empty());
+        }
+    }
+
+    #[test]
+    fn alloc_slab_list_membership() {
+        unsafe {
+            const PAGE_SIZE: usize = 4096;
+
+            for distribution_policy in [DistributionPolicy::Concentrate, DistributionPolicy::Spread]
+            {
+                for percent in [1, 25, 50, 75, 99, 100] {
+                    let mut cache: Cache<[u64; 64], StdMemoryBackend> = Cache::with_config(
+                        PAGE_SIZE,
+                        PAGE_SIZE,
+                        ObjectSizeType::Small,
+                        StdMemoryBackend::default(),
+                        CacheConfig {
+                            distribution_policy,
+                            ..Default::default()
+                        },
+                    )
+                    .unwrap();
+                    cache.set_occupancy_threshold_percent(percent).unwrap();
+                    let threshold = cache.occupacy_more_75_minimum_allocated_objects_number;
+
+                    let mut allocated_ptrs = Vec::new();
+                    for allocated_objects_number in 1..=cache.objects_per_slab {
+                        allocated_ptrs.push(cache.alloc());
+                        let lists_lengths = (
+                            cache.free_slabs_list_occupacy_less_75.iter().count(),
+                            cache.free_slabs_list_occupacy_more_75.iter().count(),
+                            cache.full_slabs_list.iter().count(),
+                        );
+                        if allocated_objects_number == cache.objects_per_slab {
+                            assert_eq!(lists_lengths, (0, 0, 1));
+                        } else if allocated_objects_number >= threshold {
+                            assert_eq!(lists_lengths, (0, 1, 0));
+                        } else {
+                            assert_eq!(lists_lengths, (1, 0, 0));
+                        }
+                    }
+                    assert_eq!(cache.cache_statistics().full_slabs_number, 1);
+                    assert_eq!(cache.cache_statistics().free_slabs_number, 0);
+
+                    for allocated_ptr in allocated_ptrs {
+                        cache.free(allocated_ptr);
+                    }
+                    assert!(cache.memory_backend.allocated_slab_addrs.is_empty());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn alignment_bigger_than_page() {
+        #[allow(dead_code)]
+        #[repr(align(8192))]
+        struct Aligned([u8; 8192]);
+
+        let cache: Result<Cache<Aligned, StdMemoryBackend>, _> = Cache::new(
+            8192 * 4,
+            4096,
+            ObjectSizeType::Large,
+            StdMemoryBackend::default(),
+        );
+        assert_eq!(
+            cache.err(),
+            Some("Object alignment is bigger than page size, slabs are only page aligned")
+        );
+
+        // Page sized alignment is fine
+        #[allow(dead_code)]
+        #[repr(align(4096))]
+        struct PageAligned([u8; 4096]);
+
+        let cache: Result<Cache<PageAligned, StdMemoryBackend>, _> = Cache::new(
+            4096 * 4,
+            4096,
+            ObjectSizeType::Large,
+            StdMemoryBackend::default(),
+        );
+        assert!(cache.is_ok());
+    }
+
+    #[test]
+    fn reap_idle() {
+        unsafe {
+            const PAGE_SIZE: usize = 4096;
+
+            let mut cache: Cache<[u64; 8], StdMemoryBackend> = Cache::with_config(
+                PAGE_SIZE,
+                PAGE_SIZE,
+                ObjectSizeType::Small,
+                StdMemoryBackend::default(),
+                CacheConfig {
+                    reclaim: false,
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+            cache.reserve_slabs(2).unwrap();
+
+            // One slab is used, another one stays idle
+            for _ in 0..5 {
+                let allocated_ptr = cache.alloc();
+                cache.free(allocated_ptr);
+            }
+            assert_eq!(cache.cache_statistics().free_slabs_number, 2);
+
+            // Both slabs are empty, only the idle one is old enough
+            assert_eq!(cache.reap_idle(11), 0);
+            assert_eq!(cache.reap_idle(10), 1);
+            assert_eq!(cache.cache_statistics().free_slabs_number, 1);
+            // Used slab has just become empty
+            assert_eq!(cache.reap_idle(1), 0);
+
+            let allocated_ptr = cache.alloc();
+            // Slab with allocated object is not freed
+            assert_eq!(cache.reap_idle(0), 0);
+            cache.free(allocated_ptr);
+            assert_eq!(cache.reap_idle(0), 1);
+            assert!(cache.memory_backend.allocated_slab_addrs.is_empty());
+        }
+    }
+
+    #[test]
+    fn alloc_uninit() {
+        unsafe {
+            use core::mem::MaybeUninit;
+            const PAGE_SIZE: usize = 4096;
+
+            let mut cache: Cache<[u64; 8], StdMemoryBackend> = Cache::new(
+                PAGE_SIZE,
+                PAGE_SIZE,
+                ObjectSizeType::Small,
+                StdMemoryBackend::default(),
+            )
+            .unwrap();
+
+            let uninit_ptr = cache.alloc_uninit();
+            assert!(!uninit_ptr.is_null());
+            let value = (*uninit_ptr).write([7; 8]);
+            assert_eq!(*value, [7; 8]);
+            assert_eq!((*uninit_ptr).assume_init_read(), [7; 8]);
+            cache.free_uninit(uninit_ptr);
+
+            // Initialized object is freed as T
+            let uninit_ptr: *mut MaybeUninit<[u64; 8]> = cache.alloc_uninit();
+            (*uninit_ptr).write([1; 8]);
+            cache.free(uninit_ptr.cast());
+            assert_eq!(cache.cache_statistics().allocated_objects_number, 0);
+            assert!(cache.memory_backend.allocated_slab_addrs.is_empty());
+        }
+    }
+
+    #[test]
+    fn new_slab_objects_order() {
+        unsafe {
+            const PAGE_SIZE: usize = 4096;
+            const CACHE_LINE_SIZE: usize = 64;
+
+            for (lazy_carve, offset_free_links) in
+                [(false, false), (true, false), (false, true), (true, true)]
+            {
+                let mut cache: Cache<[u64; 2], StdMemoryBackend> = Cache::with_config(
+                    PAGE_SIZE,
+                    PAGE_SIZE,
+                    ObjectSizeType::Small,
+                    StdMemoryBackend::default(),
+                    CacheConfig {
+                        lazy_carve,
+                        offset_free_links,
+                        ..Default::default()
+                    },
+                )
+                .unwrap();
+
+                let mut allocated_ptrs = Vec::new();
+                for _ in 0..cache.objects_per_slab {
+                    allocated_ptrs.push(cache.alloc());
+                }
+                // Each next object is right before the previous one
+                for pair in allocated_ptrs.windows(2) {
+                    assert_eq!(pair[0] as usize - pair[1] as usize, size_of::<[u64; 2]>());
+                }
+                // Cache lines are filled one by one
+                let cache_lines: Vec<usize> = allocated_ptrs
+                    .iter()
+                    .map(|&allocated_ptr| allocated_ptr as usize / CACHE_LINE_SIZE)
+                    .collect();
+                assert!(cache_lines.is_sorted_by(|a, b| a >= b));
+
+                for allocated_ptr in allocated_ptrs {
+                    cache.free(allocated_ptr);
+                }
+                assert!(cache.memory_backend.allocated_slab_addrs.is_empty());
+            }
+        }
+    }
+
+    #[cfg(debug_assertions)]
+    #[test]
+    fn double_free_in_partial_slab() {
+        unsafe {
+            use std::panic::{catch_unwind, AssertUnwindSafe};
+            const PAGE_SIZE: usize = 4096;
+
+            for offset_free_links in [false, true] {
+                let mut cache: Cache<[u64; 8], StdMemoryBackend> = Cache::with_config(
+                    PAGE_SIZE,
+                    PAGE_SIZE,
+                    ObjectSizeType::Small,
+                    StdMemoryBackend::default(),
+                    CacheConfig {
+                        offset_free_links,
+                        ..Default::default()
+                    },
+                )
+                .unwrap();
+
+                let allocated_ptrs: Vec<*mut [u64; 8]> = (0..4).map(|_| cache.alloc()).collect();
+                cache.free(allocated_ptrs[1]);
+                cache.free(allocated_ptrs[2]);
+
+                // Slab has allocated objects, the object is in free objects list
+                let panic_payload =
+                    catch_unwind(AssertUnwindSafe(|| cache.free(allocated_ptrs[1])))
+                        .expect_err("Double free must panic");
+                let panic_message = panic_payload.downcast_ref::<String>().unwrap();
+                assert!(panic_message.contains("Double free"));
+                assert_eq!(cache.verify_free_lists(), Ok(()));
+
+                cache.free(allocated_ptrs[0]);
+                cache.free(allocated_ptrs[3]);
+                assert!(cache.memory_backend.allocated_slab_addrs.is_empty());
+            }
+        }
+    }
+
+    #[test]
+    fn cache_statistics_zeroed() {
+        unsafe {
+            const PAGE_SIZE: usize = 4096;
+            let zero_delta = CacheStatisticsDelta {
+                free_slabs_number: 0,
+                full_slabs_number: 0,
+                free_objects_number: 0,
+                allocated_objects_number: 0,
+            };
+
+            let mut cache: Cache<[u64; 16], StdMemoryBackend> = Cache::new(
+                PAGE_SIZE,
+                PAGE_SIZE,
+                ObjectSizeType::Small,
+                StdMemoryBackend::default(),
+            )
+            .unwrap();
+            assert_eq!(
+                cache.cache_statistics().delta(&CacheStatistics::zeroed()),
+                zero_delta
+            );
+            assert_eq!(
+                CacheStatistics::default().delta(&CacheStatistics::zeroed()),
+                zero_delta
+            );
+
+            // Aggregation starts from zero
+            let allocated_ptr = cache.alloc();
+            let mut total = CacheStatistics::default();
+            for statistics in [cache.cache_statistics(), cache.cache_statistics()] {
+                total.free_slabs_number += statistics.free_slabs_number;
+                total.full_slabs_number += statistics.full_slabs_number;
+                total.free_objects_number += statistics.free_objects_number;
+                total.allocated_objects_number += statistics.allocated_objects_number;
+            }
+            assert_eq!(total.allocated_objects_number, 2);
+            assert_eq!(total.free_slabs_number, 2);
+            assert_eq!(total.free_objects_number, 2 * (cache.objects_per_slab - 1));
+
+            cache.free(allocated_ptr);
+            assert!(cache.memory_backend.allocated_slab_addrs.is_empty());
+        }
+    }
+
+    #[test]
+    fn self_check_backend() {
+        unsafe {
+            const PAGE_SIZE: usize = 4096;
+
+            for (object_size_type, slab_size) in [
+                (ObjectSizeType::Large, PAGE_SIZE),
+                (ObjectSizeType::Large, PAGE_SIZE * 4),
+                (ObjectSizeType::Small, PAGE_SIZE * 4),
+            ] {
+                let mut cache: Cache<[u64; 64], StdMemoryBackend> = Cache::new(
+                    slab_size,
+                    PAGE_SIZE,
+                    object_size_type,
+                    StdMemoryBackend::default(),
+                )
+                .unwrap();
+                assert_eq!(cache.self_check_backend(), Ok(()));
+
+                let mut allocated_ptrs = Vec::new();
+                for _ in 0..cache.objects_per_slab + 3 {
+                    allocated_ptrs.push(cache.alloc());
+                }
+                // Some pages have no allocated objects
+                for allocated_ptr in allocated_ptrs.drain(..cache.objects_per_slab / 2) {
+                    cache.free(allocated_ptr);
+                }
+                assert_eq!(cache.self_check_backend(), Ok(()));
+
+                // Memory backend saves ptrs under wrong keys
+                let saved_slab_infos = cache.memory_backend.ht_saved_slab_infos.clone();
+                let slab_info_ptrs: HashSet<usize> = saved_slab_infos
+                    .values()
+                    .map(|&slab_info_ptr| slab_info_ptr as usize)
+                    .collect();
+                assert_eq!(slab_info_ptrs.len(), 2);
+                let wrong_slab_info_ptr = *saved_slab_infos.values().next().unwrap();
+                for slab_info_ptr in cache.memory_backend.ht_saved_slab_infos.values_mut() {
+                    *slab_info_ptr = wrong_slab_info_ptr;
+                }
+                assert_eq!(
+                    cache.self_check_backend(),
+                    Err("Memory backend returns SlabInfo ptr of another slab")
+                );
+                cache.memory_backend.ht_saved_slab_infos = saved_slab_infos;
+
+                for allocated_ptr in allocated_ptrs {
+                    cache.free(allocated_ptr);
+                }
+                assert!(cache.memory_backend.allocated_slab_addrs.is_empty());
+            }
+        }
+    }
+
+    #[test]
+    fn emergency_reserve() {
+        unsafe {
+            const PAGE_SIZE: usize = 4096;
+
+            // Slabs allocation can be failed
+            #[derive(Default)]
+            struct TestMemoryBackend {
+                std_memory_backend: StdMemoryBackend,
+                out_of_memory: bool,
+            }
+
+            impl MemoryBackend for TestMemoryBackend {
+                type Error = OutOfMemory;
+
+                unsafe fn alloc_slab(
+                    &mut self,
+                    slab_size: usize,
+                    page_size: usize,
+                ) -> Result<*mut u8, Self::Error> {
+                    if self.out_of_memory {
+                        return Err(OutOfMemory);
+                    }
+                    self.std_memory_backend.alloc_slab(slab_size, page_size)
+                }
+
+                unsafe fn free_slab(
+                    &mut self,
+                    slab_ptr: *mut u8,
+                    slab_size: usize,
+                    page_size: usize,
+                ) {
+                    self.std_memory_backend
+                        .free_slab(slab_ptr, slab_size, page_size);
+                }
+
+                unsafe fn alloc_slab_info(&mut self) -> Result<*mut SlabInfo, Self::Error> {
+                    self.std_memory_backend.alloc_slab_info()
+                }
+
+                unsafe fn free_slab_info(&mut self, slab_info_ptr: *mut SlabInfo) {
+                    self.std_memory_backend.free_slab_info(slab_info_ptr);
+                }
+
+                unsafe fn save_slab_info_ptr(
+                    &mut self,
+                    object_page_addr: usize,
+                    slab_info_ptr: *mut SlabInfo,
+                ) {
+                    self.std_memory_backend
+                        .save_slab_info_ptr(object_page_addr, slab_info_ptr);
+                }
+
+                unsafe fn get_slab_info_ptr(&mut self, object_page_addr: usize) -> *mut SlabInfo {
+                    self.std_memory_backend.get_slab_info_ptr(object_page_addr)
+                }
+
+                unsafe fn delete_slab_info_ptr(&mut self, page_addr: usize) {
+                    self.std_memory_backend.delete_slab_info_ptr(page_addr);
+                }
+            }
+
+            let mut cache: Cache<[u64; 64], TestMemoryBackend> = Cache::new(
+                PAGE_SIZE,
+                PAGE_SIZE,
+                ObjectSizeType::Large,
+                TestMemoryBackend::default(),
+            )
+            .unwrap();
+            assert_eq!(cache.set_emergency_reserve(1), Ok(()));
+            assert_eq!(cache.emergency_slabs_number(), 1);
+            // Reserved slab is not counted
+            assert_eq!(cache.cache_statistics().free_slabs_number, 0);
+            assert_eq!(cache.cache_statistics().free_objects_number, 0);
+            assert_eq!(cache.verify_free_lists(), Ok(()));
+
+            // Reserve is not used while memory backend works
+            let mut allocated_ptrs = Vec::new();
+            for _ in 0..cache.objects_per_slab {
+                allocated_ptrs.push(cache.alloc());
+            }
+            assert_eq!(cache.emergency_slabs_number(), 1);
+            assert_eq!(
+                cache
+                    .memory_backend
+                    .std_memory_backend
+                    .allocated_slab_addrs
+                    .len(),
+                2
+            );
+
+            // Memory backend fails, the reserved slab is used
+            cache.memory_backend.out_of_memory = true;
+            for _ in 0..cache.objects_per_slab {
+                let allocated_ptr = cache.alloc();
+                assert!(!allocated_ptr.is_null());
+                allocated_ptrs.push(allocated_ptr);
+            }
+            assert_eq!(cache.emergency_slabs_number(), 0);
+            assert_eq!(cache.cache_statistics().full_slabs_number, 2);
+            assert!(cache.alloc().is_null());
+            assert_eq!(
+                cache.set_emergency_reserve(1),
+                Err(CacheError::Backend(OutOfMemory))
+            );
+
+            // Memory backend works again, the reserve is refilled
+            cache.memory_backend.out_of_memory = false;
+            allocated_ptrs.push(cache.alloc());
+            assert_eq!(cache.emergency_slabs_number(), 1);
+            assert_eq!(
+                cache
+                    .memory_backend
+                    .std_memory_backend
+                    .allocated_slab_addrs
+                    .len(),
+                4
+            );
+            assert_eq!(cache.slab_base_addresses().count(), 4);
+            assert_eq!(cache.verify_free_lists(), Ok(()));
+
+            for allocated_ptr in allocated_ptrs {
+                cache.free(allocated_ptr);
+            }
+            cache.drain_free_slabs();
+            // Reserve is not drained
+            assert_eq!(
+                cache
+                    .memory_backend
+                    .std_memory_backend
+                    .allocated_slab_addrs
+                    .len(),
+                1
+            );
+            assert_eq!(cache.set_emergency_reserve(0), Ok(()));
+            assert!(cache
+                .memory_backend
+                .std_memory_backend
+                .allocated_slab_addrs
+                .is_empty());
+        }
+    }
+
+    #[test]
+    fn new_from_orders() {
+        let cache: Cache<[u64; 64], StdMemoryBackend> =
+            Cache::new_from_orders(14, 12, ObjectSizeType::Small, StdMemoryBackend::default())
+                .unwrap();
+        assert_eq!(cache.slab_size, 4096 * 4);
+        assert_eq!(cache.page_size, 4096);
+
+        assert!(Cache::<[u64; 64], StdMemoryBackend>::new_from_orders(
+            12,
+            14,
+            ObjectSizeType::Small,
+            StdMemoryBackend::default(),
+        )
+        .is_err());
+        assert_eq!(
+            Cache::<[u64; 64], StdMemoryBackend>::new_from_orders(
+                usize::BITS,
+                12,
+                ObjectSizeType::Small,
+                StdMemoryBackend::default(),
+            )
+            .err(),
+            Some("Slab order is too big")
+        );
+        assert_eq!(
+            Cache::<[u64; 64], StdMemoryBackend>::new_from_orders(
+                12,
+                usize::BITS,
+                ObjectSizeType::Small,
+                StdMemoryBackend::default(),
+            )
+            .err(),
+            Some("Page order is too big")
+        );
+    }
+
+    #[test]
+    fn alloc_flags() {
+        unsafe {
+            const PAGE_SIZE: usize = 4096;
+
+            let flags = AllocFlags::ZERO | AllocFlags::NO_GROW;
+            assert!(flags.contains(AllocFlags::ZERO));
+            assert!(flags.contains(AllocFlags::NO_GROW));
+            assert!(!AllocFlags::ZERO.contains(flags));
+            assert!(flags.contains(AllocFlags::EMPTY));
+            assert_eq!(AllocFlags::default(), AllocFlags::EMPTY);
+
+            let mut cache: Cache<[u64; 8], StdMemoryBackend> = Cache::new(
+                PAGE_SIZE,
+                PAGE_SIZE,
+                ObjectSizeType::Small,
+                StdMemoryBackend::default(),
+            )
+            .unwrap();
+
+            // No free objects, slab is not allocated
+            assert!(cache.alloc_flags(AllocFlags::NO_GROW).is_null());
+            assert!(cache.memory_backend.allocated_slab_addrs.is_empty());
+
+            // Keeps the slab allocated
+            let keeper_ptr = cache.alloc_flags(AllocFlags::EMPTY);
+            assert!(!keeper_ptr.is_null());
+            let allocated_ptr = cache.alloc_flags(AllocFlags::NO_GROW);
+            assert!(!allocated_ptr.is_null());
+            allocated_ptr.write([u64::MAX; 8]);
+            cache.free(allocated_ptr);
+
+            // Same object is allocated again and zeroed
+            let allocated_ptr = cache.alloc_flags(AllocFlags::ZERO | AllocFlags::NO_GROW);
+            assert_eq!(allocated_ptr.read(), [0; 8]);
+            allocated_ptr.write([u64::MAX; 8]);
+            cache.free(allocated_ptr);
+            let allocated_ptr = cache.alloc_zeroed();
+            assert_eq!(allocated_ptr.read(), [0; 8]);
+            cache.free(allocated_ptr);
+            assert_eq!(cache.memory_backend.allocated_slab_addrs.len(), 1);
+
+            cache.free(keeper_ptr);
+            assert!(cache.memory_backend.allocated_slab_addrs.is_empty());
+        }
+    }
+
+    #[test]
+    fn into_backend() {
+        unsafe {
+            const PAGE_SIZE: usize = 4096;
+
+            let mut cache: Cache<[u64; 64], StdMemoryBackend> = Cache::new(
+                PAGE_SIZE,
+                PAGE_SIZE,
+                ObjectSizeType::Large,
+                StdMemoryBackend::default(),
+            )
+            .unwrap();
+            let allocated_ptr = cache.alloc();
+            // Cache has allocated object
+            let mut cache = cache.into_backend().err().unwrap();
+            cache.free(allocated_ptr);
+            assert_eq!(cache.set_emergency_reserve(1), Ok(()));
+            // Cache has slab in the emergency reserve
+            let mut cache = cache.into_backend().err().unwrap();
+            assert_eq!(cache.set_emergency_reserve(0), Ok(()));
+            let memory_backend = cache.into_backend().ok().unwrap();
+            assert!(memory_backend.allocated_slab_addrs.is_empty());
+            assert!(memory_backend.allocated_slab_info_addrs.is_empty());
+            assert!(memory_backend.ht_saved_slab_infos.is_empty());
+
+            // Drop returns empty slabs, memory backend is borrowed to check it after drop
+            struct TestMemoryBackend<'a>(&'a mut StdMemoryBackend);
+
+            impl MemoryBackend for TestMemoryBackend<'_> {
+                type Error = OutOfMemory;
+
+                unsafe fn alloc_slab(
+                    &mut self,
+                    slab_size: usize,
+                    page_size: usize,
+                ) -> Result<*mut u8, Self::Error> {
+                    self.0.alloc_slab(slab_size, page_size)
+                }
+
+                unsafe fn free_slab(
+                    &mut self,
+                    slab_ptr: *mut u8,
+                    slab_size: usize,
+                    page_size: usize,
+                ) {
+                    self.0.free_slab(slab_ptr, slab_size, page_size);
+                }
+
+                unsafe fn alloc_slab_info(&mut self) -> Result<*mut SlabInfo, Self::Error> {
+                    self.0.alloc_slab_info()
+                }
+
+                unsafe fn free_slab_info(&mut self, slab_info_ptr: *mut SlabInfo) {
+                    self.0.free_slab_info(slab_info_ptr);
+                }
+
+                unsafe fn save_slab_info_ptr(
+                    &mut self,
+                    object_page_addr: usize,
+                    slab_info_ptr: *mut SlabInfo,
+                ) {
+                    self.0.save_slab_info_ptr(object_page_addr, slab_info_ptr);
+                }
+
+                unsafe fn get_slab_info_ptr(&mut self, object_page_addr: usize) -> *mut SlabInfo {
+                    self.0.get_slab_info_ptr(object_page_addr)
+                }
+
+                unsafe fn delete_slab_info_ptr(&mut self, page_addr: usize) {
+                    self.0.delete_slab_info_ptr(page_addr);
+                }
+            }
+
+            let mut memory_backend = memory_backend;
+            let mut cache: Cache<[u64; 64], TestMemoryBackend> = Cache::new(
+                PAGE_SIZE,
+                PAGE_SIZE,
+                ObjectSizeType::Large,
+                TestMemoryBackend(&mut memory_backend),
+            )
+            .unwrap();
+            let mut allocated_ptrs = Vec::new();
+            for _ in 0..cache.objects_per_slab + 1 {
+                allocated_ptrs.push(cache.alloc());
+            }
+            // The only object of the second slab
+            let leaked_ptr = allocated_ptrs.pop().unwrap();
+            for allocated_ptr in allocated_ptrs {
+                cache.free(allocated_ptr);
+            }
+            assert_eq!(cache.reserve_slabs(1), Ok(()));
+            assert_eq!(cache.set_emergency_reserve(2), Ok(()));
+            assert_eq!(cache.memory_backend.0.allocated_slab_addrs.len(), 4);
+            drop(cache);
+            // Slab with allocated object is leaked
+            assert_eq!(memory_backend.allocated_slab_addrs.len(), 1);
+            assert_eq!(
+                memory_backend.allocated_slab_addrs.first().copied(),
+                Some(align_down(leaked_ptr as usize, PAGE_SIZE))
+            );
+        }
+    }
+
+    #[test]
+    fn assert_level() {
+        unsafe {
+            use std::panic::{catch_unwind, AssertUnwindSafe};
+            const PAGE_SIZE: usize = 4096;
+
+            assert!(!AssertLevel::Off.enables(AssertLevel::Normal));
+            assert_eq!(
+                AssertLevel::Normal.enables(AssertLevel::Normal),
+                cfg!(debug_assertions)
+            );
+            assert!(!AssertLevel::Normal.enables(AssertLevel::Paranoid));
+            assert!(AssertLevel::Paranoid.enables(AssertLevel::Normal));
+            assert!(AssertLevel::Paranoid.enables(AssertLevel::Paranoid));
+
+            for assert_level in [AssertLevel::Off, AssertLevel::Normal, AssertLevel::Paranoid] {
+                let mut cache: Cache<[u64; 64], StdMemoryBackend> = Cache::with_config(
+                    PAGE_SIZE * 2,
+                    PAGE_SIZE,
+                    ObjectSizeType::Large,
+                    StdMemoryBackend::default(),
+                    CacheConfig {
+                        assert_level,
+                        ..Default::default()
+                    },
+                )
+                .unwrap();
+                let mut allocated_ptrs = Vec::new();
+                for _ in 0..500 {
+                    if allocated_ptrs.is_empty() || thread_rng().gen_bool(0.6) {
+                        allocated_ptrs.push(cache.alloc());
+                    } else {
+                        let index = thread_rng().gen_range(0..allocated_ptrs.len());
+                        cache.free(allocated_ptrs.swap_remove(index));
+                    }
+                }
+                assert_eq!(cache.check_invariants(), Ok(()));
+                for allocated_ptr in allocated_ptrs {
+                    cache.free(allocated_ptr);
+                }
+                assert!(cache.memory_backend.allocated_slab_addrs.is_empty());
+            }
+
+            // Paranoid level checks memory backend after every operation
+            let mut cache: Cache<[u64; 64], StdMemoryBackend> = Cache::with_config(
+                PAGE_SIZE,
+                PAGE_SIZE,
+                ObjectSizeType::Large,
+                StdMemoryBackend::default(),
+                CacheConfig {
+                    assert_level: AssertLevel::Paranoid,
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+            let mut allocated_ptrs = Vec::new();
+            for _ in 0..cache.objects_per_slab + 3 {
+                allocated_ptrs.push(cache.alloc());
+            }
+            // Pages of the first slab get SlabInfo ptr of the second one, the freed object is found correctly
+            let allocated_ptr = allocated_ptrs.pop().unwrap();
+            let allocated_page_addr = align_down(allocated_ptr as usize, PAGE_SIZE);
+            let saved_slab_infos = cache.memory_backend.ht_saved_slab_infos.clone();
+            let wrong_slab_info_ptr = saved_slab_infos[&allocated_page_addr];
+            for slab_info_ptr in cache.memory_backend.ht_saved_slab_infos.values_mut() {
+                *slab_info_ptr = wrong_slab_info_ptr;
+            }
+            let panic_payload = catch_unwind(AssertUnwindSafe(|| cache.free(allocated_ptr)))
+                .expect_err("Paranoid check must panic");
+            let panic_message = panic_payload.downcast_ref::<String>().unwrap();
+            assert!(panic_message.contains("Memory backend check failed"));
+
+            cache.memory_backend.ht_saved_slab_infos = saved_slab_infos;
+            for allocated_ptr in allocated_ptrs {
+                cache.free(allocated_ptr);
+            }
+            assert!(cache.memory_backend.allocated_slab_addrs.is_empty());
+        }
+    }
+
+    #[test]
+    fn alloc_on_node() {
+        unsafe {
+            const PAGE_SIZE: usize = 4096;
+
+            // Remembers node of every slab, node 7 has no memory
+            #[derive(Default)]
+            struct TestMemoryBackend {
+                std_memory_backend: StdMemoryBackend,
+                slab_nodes: HashMap<usize, u32>,
+                out_of_memory: bool,
+            }
+
+            impl MemoryBackend for TestMemoryBackend {
+                type Error = OutOfMemory;
+
+                unsafe fn alloc_slab(
+                    &mut self,
+                    slab_size: usize,
+                    page_size: usize,
+                ) -> Result<*mut u8, Self::Error> {
+                    if self.out_of_memory {
+                        return Err(OutOfMemory);
+                    }
+                    self.std_memory_backend.alloc_slab(slab_size, page_size)
+                }
+
+                unsafe fn alloc_slab_on_node(
+                    &mut self,
+                    slab_size: usize,
+                    page_size: usize,
+                    node: u32,
+                ) -> Result<*mut u8, Self::Error> {
+                    if node == 7 {
+                        return Err(OutOfMemory);
+                    }
+                    let slab_ptr = self.alloc_slab(slab_size, page_size)?;
+                    self.slab_nodes.insert(slab_ptr as usize, node);
+                    Ok(slab_ptr)
+                }
+
+                unsafe fn free_slab(
+                    &mut self,
+                    slab_ptr: *mut u8,
+                    slab_size: usize,
+                    page_size: usize,
+                ) {
+                    self.slab_nodes.remove(&(slab_ptr as usize));
+                    self.std_memory_backend
+                        .free_slab(slab_ptr, slab_size, page_size);
+                }
+
+                unsafe fn alloc_slab_info(&mut self) -> Result<*mut SlabInfo, Self::Error> {
+                    unreachable!();
+                }
+
+                unsafe fn free_slab_info(&mut self, _slab_info_ptr: *mut SlabInfo) {
+                    unreachable!();
+                }
+
+                unsafe fn save_slab_info_ptr(
+                    &mut self,
+                    _object_page_addr: usize,
+                    _slab_info_ptr: *mut SlabInfo,
+                ) {
+                    unreachable!();
+                }
+
+                unsafe fn get_slab_info_ptr(&mut self, _object_page_addr: usize) -> *mut SlabInfo {
+                    unreachable!();
+                }
+
+                unsafe fn delete_slab_info_ptr(&mut self, _page_addr: usize) {
+                    unreachable!();
+                }
+            }
+
+            let mut cache: Cache<[u64; 8], TestMemoryBackend> = Cache::new(
+                PAGE_SIZE,
+                PAGE_SIZE,
+                ObjectSizeType::Small,
+                TestMemoryBackend::default(),
+            )
+            .unwrap();
+            let slab_node = |cache: &Cache<[u64; 8], TestMemoryBackend>,
+                             object_ptr: *mut [u64; 8]| {
+                cache
+                    .memory_backend
+                    .slab_nodes
+                    .get(&align_down(object_ptr as usize, PAGE_SIZE))
+                    .copied()
+            };
+
+            let mut allocated_ptrs = Vec::new();
+            allocated_ptrs.push(cache.alloc_on_node(0));
+            assert_eq!(slab_node(&cache, allocated_ptrs[0]), Some(0));
+            // Slab of node 0 has free objects, but node 1 gets its own slab
+            allocated_ptrs.push(cache.alloc_on_node(1));
+            assert_eq!(slab_node(&cache, allocated_ptrs[1]), Some(1));
+            assert_eq!(cache.memory_backend.slab_nodes.len(), 2);
+            for _ in 0..cache.objects_per_slab - 1 {
+                let allocated_ptr = cache.alloc_on_node(0);
+                assert_eq!(slab_node(&cache, allocated_ptr), Some(0));
+                allocated_ptrs.push(allocated_ptr);
+            }
+            // Slab of node 0 is full
+            allocated_ptrs.push(cache.alloc_on_node(0));
+            assert_eq!(cache.memory_backend.slab_nodes.len(), 3);
+
+            // Node 7 has no memory, object is taken from any slab
+            let allocated_ptr = cache.alloc_on_node(7);
+            assert!(slab_node(&cache, allocated_ptr).is_some());
+            allocated_ptrs.push(allocated_ptr);
+            assert_eq!(cache.memory_backend.slab_nodes.len(), 3);
+
+            for allocated_ptr in allocated_ptrs {
+                cache.free(allocated_ptr);
+            }
+            assert!(cache.memory_backend.slab_nodes.is_empty());
+            assert!(cache
+                .memory_backend
+                .std_memory_backend
+                .allocated_slab_addrs
+                .is_empty());
+
+            // Concentrate order is kept, the slab of node 1 is more occupied
+            let mut allocated_ptrs = Vec::new();
+            for _ in 0..10 {
+                allocated_ptrs.push(cache.alloc());
+            }
+            for _ in 0..20 {
+                allocated_ptrs.push(cache.alloc_on_node(1));
+            }
+            assert_eq!(
+                cache.peek_next_slab(),
+                Some(align_down(allocated_ptrs[10] as usize, PAGE_SIZE) as *const u8)
+            );
+
+            // Memory backend fails, object is taken from the emergency reserve
+            assert_eq!(cache.set_emergency_reserve(1), Ok(()));
+            while cache.remaining_capacity() != 0 {
+                allocated_ptrs.push(cache.alloc());
+            }
+            cache.memory_backend.out_of_memory = true;
+            let allocated_ptr = cache.alloc_on_node(1);
+            assert!(!allocated_ptr.is_null());
+            assert_eq!(slab_node(&cache, allocated_ptr), None);
+            assert_eq!(cache.emergency_slabs_number(), 0);
+            allocated_ptrs.push(allocated_ptr);
+            cache.memory_backend.out_of_memory = false;
+
+            for allocated_ptr in allocated_ptrs {
+                cache.free(allocated_ptr);
+            }
+            assert_eq!(cache.set_emergency_reserve(0), Ok(()));
+            assert!(cache
+                .memory_backend
+                .std_memory_backend
+                .allocated_slab_addrs
+                .is_empty());
+        }
+    }
+
+    #[test]
+    fn split_free_lists() {
+        unsafe {
+            const PAGE_SIZE: usize = 4096;
+
+            static TRANSITIONS: Mutex<Vec<(SlabListKind, SlabListKind)>> = Mutex::new(Vec::new());
+            fn on_transition(_slab_ptr: *mut u8, from: SlabListKind, to: SlabListKind) {
+                TRANSITIONS.lock().push((from, to));
+            }
+
+            let mut cache: Cache<[u64; 64], StdMemoryBackend> = Cache::with_config(
+                PAGE_SIZE * 4,
+                PAGE_SIZE,
+                ObjectSizeType::Small,
+                StdMemoryBackend::default(),
+                CacheConfig {
+                    split_free_lists: false,
+                    on_transition: Some(on_transition),
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+            assert_eq!(cache.occupancy_threshold_percent(), 100);
+            assert_eq!(
+                cache.set_occupancy_threshold_percent(50),
+                Err("Free lists are not split")
+            );
+
+            let mut allocated_ptrs = Vec::new();
+            for _ in 0..cache.objects_per_slab {
+                allocated_ptrs.push(cache.alloc());
+                assert!(cache.free_slabs_list_occupacy_more_75.is_empty());
+                assert_eq!(cache.check_invariants(), Ok(()));
+            }
+            assert_eq!(cache.cache_statistics().full_slabs_number, 1);
+            for allocated_ptr in allocated_ptrs.drain(..) {
+                cache.free(allocated_ptr);
+                assert!(cache.free_slabs_list_occupacy_more_75.is_empty());
+                assert_eq!(cache.check_invariants(), Ok(()));
+            }
+            assert!(cache.memory_backend.allocated_slab_addrs.is_empty());
+            assert_eq!(
+                *TRANSITIONS.lock(),
+                [
+                    (SlabListKind::FreeLess75, SlabListKind::Full),
+                    (SlabListKind::Full, SlabListKind::FreeLess75)
+                ]
+            );
+
+            // Migrated cache keeps one free list
+            let cache = cache.migrate_to_slab_size(PAGE_SIZE * 2).ok().unwrap();
+            assert_eq!(cache.occupancy_threshold_percent(), 100);
+        }
+    }
+
+    #[test]
+    fn donate_slab() {
+        unsafe {
+            const PAGE_SIZE: usize = 4096;
+
+            // Can't allocate slabs yet, but frees them
+            #[derive(Default)]
+            struct TestMemoryBackend {
+                freed_slab_addrs: Vec<usize>,
+            }
+
+            impl MemoryBackend for TestMemoryBackend {
+                type Error = OutOfMemory;
+
+                unsafe fn alloc_slab(
+                    &mut self,
+                    _slab_size: usize,
+                    _page_size: usize,
+                ) -> Result<*mut u8, Self::Error> {
+                    Err(OutOfMemory)
+                }
+
+                unsafe fn free_slab(
+                    &mut self,
+                    slab_ptr: *mut u8,
+                    slab_size: usize,
+                    page_size: usize,
+                ) {
+                    self.freed_slab_addrs.push(slab_ptr as usize);
+                    dealloc(
+                        slab_ptr,
+                        Layout::from_size_align(slab_size, page_size).unwrap(),
+                    );
+                }
+
+                unsafe fn alloc_slab_info(&mut self) -> Result<*mut SlabInfo, Self::Error> {
+                    unreachable!();
+                }
+
+                unsafe fn free_slab_info(&mut self, _slab_info_ptr: *mut SlabInfo) {
+                    unreachable!();
+                }
+
+                unsafe fn save_slab_info_ptr(
+                    &mut self,
+                    _object_page_addr: usize,
+                    _slab_info_ptr: *mut SlabInfo,
+                ) {
+                    unreachable!();
+                }
+
+                unsafe fn get_slab_info_ptr(&mut self, _object_page_addr: usize) -> *mut SlabInfo {
+                    unreachable!();
+                }
+
+                unsafe fn delete_slab_info_ptr(&mut self, _page_addr: usize) {
+                    unreachable!();
+                }
+            }
+
+            let mut cache: Cache<[u64; 8], TestMemoryBackend> = Cache::new(
+                PAGE_SIZE,
+                PAGE_SIZE,
+                ObjectSizeType::Small,
+                TestMemoryBackend::default(),
+            )
+            .unwrap();
+            assert!(cache.alloc().is_null());
+
+            let slab_ptr = alloc(Layout::from_size_align(PAGE_SIZE, PAGE_SIZE).unwrap());
+            assert_eq!(
+                cache.donate_slab(null_mut()),
+                Err(CacheError::Cache("Donated slab is null"))
+            );
+            assert_eq!(
+                cache.donate_slab(slab_ptr.add(64)),
+                Err(CacheError::Cache("Donated slab is not page aligned"))
+            );
+            assert_eq!(cache.donate_slab(slab_ptr), Ok(()));
+            assert_eq!(
+                cache.donate_slab(slab_ptr),
+                Err(CacheError::Cache(
+                    "Donated slab overlaps another slab of the cache"
+                ))
+            );
+            assert_eq!(cache.cache_statistics().free_slabs_number, 1);
+            assert_eq!(cache.backend_statistics().slab_allocs, 0);
+
+            let mut allocated_ptrs = Vec::new();
+            for _ in 0..cache.objects_per_slab {
+                let allocated_ptr = cache.alloc();
+                assert_eq!(
+                    align_down(allocated_ptr as usize, PAGE_SIZE),
+                    slab_ptr as usize
+                );
+                allocated_ptrs.push(allocated_ptr);
+            }
+            // Memory backend still can't allocate slabs
+            assert!(cache.alloc().is_null());
+
+            // Empty donated slab is not returned to memory backend, the cache stops using it
+            for allocated_ptr in allocated_ptrs {
+                cache.free(allocated_ptr);
+            }
+            assert!(cache.is_empty());
+            assert_eq!(cache.backend_statistics().slab_frees, 0);
+
+            // Same when drained or reaped, donated slabs are not counted as freed
+            assert_eq!(cache.donate_slab(slab_ptr), Ok(()));
+            assert_eq!(cache.drain_free_slabs(), 0);
+            assert!(cache.is_empty());
+            assert_eq!(cache.donate_slab(slab_ptr), Ok(()));
+            assert_eq!(cache.free_slabs_where(|_| true), 0);
+            assert!(cache.is_empty());
+            assert_eq!(cache.donate_slab(slab_ptr), Ok(()));
+            assert_eq!(cache.reap_idle(0), 0);
+            assert!(cache.is_empty());
+            assert_eq!(cache.backend_statistics().slab_frees, 0);
+            let memory_backend = cache.into_backend().ok().unwrap();
+            assert!(memory_backend.freed_slab_addrs.is_empty());
+            dealloc(
+                slab_ptr,
+                Layout::from_size_align(PAGE_SIZE, PAGE_SIZE).unwrap(),
+            );
+        }
+    }
+
+    #[test]
+    fn sorted_batch_reclaim() {
+        unsafe {
+            const PAGE_SIZE: usize = 4096;
+
+            // Remembers order of slab frees
+            #[derive(Default)]
+            struct TestMemoryBackend {
+                std_memory_backend: StdMemoryBackend,
+                freed_slab_addrs: Vec<usize>,
+            }
+
+            impl MemoryBackend for TestMemoryBackend {
+                type Error = OutOfMemory;
+
+                unsafe fn alloc_slab(
+                    &mut self,
+                    slab_size: usize,
+                    page_size: usize,
+                ) -> Result<*mut u8, Self::Error> {
+                    self.std_memory_backend.alloc_slab(slab_size, page_size)
+                }
+
+                unsafe fn free_slab(
+                    &mut self,
+                    slab_ptr: *mut u8,
+                    slab_size: usize,
+                    page_size: usize,
+                ) {
+                    self.freed_slab_addrs.push(slab_ptr as usize);
+                    self.std_memory_backend
+                        .free_slab(slab_ptr, slab_size, page_size);
+                }
+
+                unsafe fn alloc_slab_info(&mut self) -> Result<*mut SlabInfo, Self::Error> {
+                    unreachable!();
+                }
+
+                unsafe fn free_slab_info(&mut self, _slab_info_ptr: *mut SlabInfo) {
+                    unreachable!();
+                }
+
+                unsafe fn save_slab_info_ptr(
+                    &mut self,
+                    _object_page_addr: usize,
+                    _slab_info_ptr: *mut SlabInfo,
+                ) {
+                    unreachable!();
+                }
+
+                unsafe fn get_slab_info_ptr(&mut self, _object_page_addr: usize) -> *mut SlabInfo {
+                    unreachable!();
+                }
+
+                unsafe fn delete_slab_info_ptr(&mut self, _page_addr: usize) {
+                    unreachable!();
+                }
+            }
+
+            for sorted_batch_reclaim in [false, true] {
+                let mut cache: Cache<[u64; 64], TestMemoryBackend> = Cache::with_config(
+                    PAGE_SIZE,
+                    PAGE_SIZE,
+                    ObjectSizeType::Small,
+                    TestMemoryBackend::default(),
+                    CacheConfig {
+                        sorted_batch_reclaim,
+                        ..Default::default()
+                    },
+                )
+                .unwrap();
+                let mut allocated_ptrs = Vec::new();
+                for _ in 0..cache.objects_per_slab * 8 {
+                    allocated_ptrs.push(cache.alloc());
+                }
+                // One object keeps the first slab
+                let kept_ptr = allocated_ptrs.remove(0);
+                allocated_ptrs.shuffle(&mut thread_rng());
+                cache.free_iter(allocated_ptrs);
+                assert_eq!(cache.memory_backend.freed_slab_addrs.len(), 7);
+                assert_eq!(cache.cache_statistics().free_slabs_number, 1);
+                assert_eq!(cache.check_invariants(), Ok(()));
+
+                let mut sorted_freed_slab_addrs = cache.memory_backend.freed_slab_addrs.clone();
+                sorted_freed_slab_addrs.sort();
+                if sorted_batch_reclaim {
+                    assert_eq!(
+                        cache.memory_backend.freed_slab_addrs,
+                        sorted_freed_slab_addrs
+                    );
+                }
+                cache.free(kept_ptr);
+                assert!(cache
+                    .memory_backend
+                    .std_memory_backend
+                    .allocated_slab_addrs
+                    .is_empty());
+            }
+        }
+    }
+
+    #[test]
+    fn slow_path_clock() {
+        unsafe {
+            use core::cell::Cell;
+            const PAGE_SIZE: usize = 4096;
+
+            std::thread_local! {
+                static CLOCK_CALLS: Cell<u64> = const { Cell::new(0) };
+            }
+            // Every call takes more time than the previous one
+            fn clock() -> u64 {
+                CLOCK_CALLS.with(|clock_calls| {
+                    clock_calls.set(clock_calls.get() + 1);
+                    clock_calls.get() * clock_calls.get()
+                })
+            }
+
+            let mut cache: Cache<[u64; 64], StdMemoryBackend> = Cache::new(
+                PAGE_SIZE,
+                PAGE_SIZE,
+                ObjectSizeType::Small,
+                StdMemoryBackend::default(),
+            )
+            .unwrap();
+            let allocated_ptr = cache.alloc();
+            // No clock, no time
+            assert_eq!(cache.backend_statistics().slow_path_time_total, 0);
+            cache.free(allocated_ptr);
+
+            let mut cache: Cache<[u64; 64], StdMemoryBackend> = Cache::with_config(
+                PAGE_SIZE,
+                PAGE_SIZE,
+                ObjectSizeType::Small,
+                StdMemoryBackend::default(),
+                CacheConfig {
+                    clock: Some(clock),
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+            let mut allocated_ptrs = Vec::new();
+            // Slow path: clock is 1, then 4
+            allocated_ptrs.push(cache.alloc());
+            assert_eq!(cache.backend_statistics().slow_path_time_last, 3);
+            // Fast path doesn't call clock
+            for _ in 1..cache.objects_per_slab {
+                allocated_ptrs.push(cache.alloc());
+            }
+            assert_eq!(CLOCK_CALLS.with(|clock_calls| clock_calls.get()), 2);
+            // Slow path: clock is 9, then 16
+            allocated_ptrs.push(cache.alloc());
+            let backend_statistics = cache.backend_statistics();
+            assert_eq!(backend_statistics.slow_path_time_last, 7);
+            assert_eq!(backend_statistics.slow_path_time_max, 7);
+            assert_eq!(backend_statistics.slow_path_time_total, 10);
+
+            for allocated_ptr in allocated_ptrs {
+                cache.free(allocated_ptr);
+            }
+            assert!(cache.memory_backend.allocated_slab_addrs.is_empty());
+        }
+    }
+
+    #[test]
+    fn free_reporting() {
+        unsafe {
+            const PAGE_SIZE: usize = 4096;
+
+            for reclaim in [true, false] {
+                let mut cache: Cache<[u64; 64], StdMemoryBackend> = Cache::with_config(
+                    PAGE_SIZE * 2,
+                    PAGE_SIZE,
+                    ObjectSizeType::Large,
+                    StdMemoryBackend::default(),
+                    CacheConfig {
+                        reclaim,
+                        ..Default::default()
+                    },
+                )
+                .unwrap();
+                let mut allocated_ptrs = Vec::new();
+                for _ in 0..cache.objects_per_slab {
+                    allocated_ptrs.push(cache.alloc());
+                }
+                let slab_ptr = cache.slab_base_addresses().next().unwrap().0 as *const u8;
+                let last_ptr = allocated_ptrs.pop().unwrap();
+                for allocated_ptr in allocated_ptrs {
+                    assert_eq!(
+                        cache.free_reporting(allocated_ptr),
+                        FreeOutcome {
+                            slab_released: false,
+                            returned_to_backend: false,
+                            released_slab_ptr: None
+                        }
+                    );
+                }
+                let free_outcome = cache.free_reporting(last_ptr);
+                if reclaim {
+                    assert_eq!(
+                        free_outcome,
+                        FreeOutcome {
+                            slab_released: true,
+                            returned_to_backend: true,
+                            released_slab_ptr: Some(slab_ptr)
+                        }
+                    );
+                } else {
+                    assert!(!free_outcome.slab_released);
+                    cache.drain_free_slabs();
+                }
+                assert!(cache.memory_backend.allocated_slab_addrs.is_empty());
+            }
+        }
+    }
+
+    #[test]
+    fn handle_encoding() {
+        unsafe {
+            const PAGE_SIZE: usize = 4096;
+
+            let geometry = CacheGeometry {
+                objects_offset: 64,
+                object_stride: 48,
+                objects_per_slab: 5,
+            };
+            let slab_ptr = PAGE_SIZE as *const u8;
+            assert_eq!(
+                geometry.object_ptr(slab_ptr, 2) as usize,
+                PAGE_SIZE + 64 + 96
+            );
+            assert_eq!(
+                geometry.object_index(slab_ptr, geometry.object_ptr(slab_ptr, 4)),
+                Some(4)
+            );
+            assert_eq!(geometry.object_index(slab_ptr, slab_ptr), None);
+            assert_eq!(
+                geometry.object_index(slab_ptr, slab_ptr.wrapping_add(64 + 50)),
+                None
+            );
+            assert_eq!(
+                geometry.object_index(slab_ptr, slab_ptr.wrapping_add(64 + 48 * 5)),
+                None
+            );
+            assert_eq!(geometry.object_index_bits(), 3);
+            assert_eq!(geometry.encode_handle(6, 4), (6 << 3) | 4);
+            assert_eq!(geometry.decode_handle((6 << 3) | 4), (6, 4));
+            let geometry = CacheGeometry {
+                objects_per_slab: 1,
+                ..geometry
+            };
+            assert_eq!(geometry.object_index_bits(), 0);
+            assert_eq!(geometry.decode_handle(geometry.encode_handle(7, 0)), (7, 0));
+
+            for (object_size_type, slab_size) in [
+                (ObjectSizeType::Small, PAGE_SIZE),
+                (ObjectSizeType::Large, PAGE_SIZE * 2),
+            ] {
+                let mut cache: Cache<[u64; 9], StdMemoryBackend> = Cache::new(
+                    slab_size,
+                    PAGE_SIZE,
+                    object_size_type,
+                    StdMemoryBackend::default(),
+                )
+                .unwrap();
+                let geometry = cache.geometry();
+                assert_eq!(geometry.object_stride, cache.object_size());
+                assert_eq!(geometry.objects_per_slab, cache.objects_per_slab());
+
+                // Handle based object table
+                let mut slab_ptrs: Vec<*const u8> = Vec::new();
+                let mut handles = Vec::new();
+                for i in 0..cache.objects_per_slab * 3 {
+                    let (allocated_ptr, object_index) = cache.alloc_indexed().unwrap();
+                    allocated_ptr.write([i as u64; 9]);
+                    let slab_ptr = allocated_ptr
+                        .cast::<u8>()
+                        .wrapping_sub(
+                            geometry.objects_offset
+                                + object_index as usize * geometry.object_stride,
+                        )
+                        .cast_const();
+                    let slab_index = match slab_ptrs.iter().position(|&ptr| ptr == slab_ptr) {
+                        Some(slab_index) => slab_index,
+                        None => {
+                            slab_ptrs.push(slab_ptr);
+                            slab_ptrs.len() - 1
+                        }
+                    };
+                    let handle = cache.encode_handle(slab_index as u64, slab_ptr, allocated_ptr);
+                    assert_eq!(
+                        geometry.decode_handle(handle),
+                        (slab_index as u64, object_index as usize)
+                    );
+                    handles.push(handle);
+                }
+                assert_eq!(slab_ptrs.len(), 3);
+
+                for (i, handle) in handles.into_iter().enumerate() {
+                    let object_ptr = cache.decode_handle(handle, &slab_ptrs);
+                    assert_eq!(object_ptr.read(), [i as u64; 9]);
+                    cache.free(object_ptr);
+                }
+                assert!(cache.memory_backend.allocated_slab_addrs.is_empty());
+            }
+        }
+    }
+
+    #[test]
+    fn absorb_emergency_reserve() {
+        unsafe {
+            use alloc::rc::Rc;
+            use core::cell::RefCell;
+
+            const PAGE_SIZE: usize = 4096;
+
+            // Memory backends of both caches share the same storage
+            #[derive(Clone)]
+            struct TestMemoryBackend(Rc<RefCell<StdMemoryBackend>>);
+
+            impl MemoryBackend for TestMemoryBackend {
+                type Error = OutOfMemory;
+
+                unsafe fn alloc_slab(
+                    &mut self,
+                    slab_size: usize,
+                    page_size: usize,
+                ) -> Result<*mut u8, Self::Error> {
+                    self.0.borrow_mut().alloc_slab(slab_size, page_size)
+                }
+
+                unsafe fn free_slab(
+                    &mut self,
+                    slab_ptr: *mut u8,
+                    slab_size: usize,
+                    page_size: usize,
+                ) {
+                    self.0
+                        .borrow_mut()
+                        .free_slab(slab_ptr, slab_size, page_size);
+                }
+
+                unsafe fn alloc_slab_info(&mut self) -> Result<*mut SlabInfo, Self::Error> {
+                    self.0.borrow_mut().alloc_slab_info()
+                }
+
+                unsafe fn free_slab_info(&mut self, slab_info_ptr: *mut SlabInfo) {
+                    self.0.borrow_mut().free_slab_info(slab_info_ptr);
+                }
+
+                unsafe fn relocate_slab_info(
+                    &mut self,
+                    slab_info_ptr: *mut SlabInfo,
+                ) -> *mut SlabInfo {
+                    self.0.borrow_mut().relocate_slab_info(slab_info_ptr)
+                }
+
+                unsafe fn save_slab_info_ptr(
+                    &mut self,
+                    object_page_addr: usize,
+                    slab_info_ptr: *mut SlabInfo,
+                ) {
+                    self.0
+                        .borrow_mut()
+                        .save_slab_info_ptr(object_page_addr, slab_info_ptr);
+                }
+
+                unsafe fn get_slab_info_ptr(&mut self, object_page_addr: usize) -> *mut SlabInfo {
+                    self.0.borrow_mut().get_slab_info_ptr(object_page_addr)
+                }
+
+                unsafe fn delete_slab_info_ptr(&mut self, page_addr: usize) {
+                    self.0.borrow_mut().delete_slab_info_ptr(page_addr);
+                }
+            }
+
+            let memory_backend =
+                TestMemoryBackend(Rc::new(RefCell::new(StdMemoryBackend::default())));
+            let new_cache = || -> Cache<[u64; 32], TestMemoryBackend> {
+                Cache::new(
+                    PAGE_SIZE * 2,
+                    PAGE_SIZE,
+                    ObjectSizeType::Large,
+                    memory_backend.clone(),
+                )
+                .unwrap()
+            };
+            let mut cache = new_cache();
+            let mut other_cache = new_cache();
+            assert_eq!(other_cache.set_emergency_reserve(2), Ok(()));
+
+            // Reserve is moved, other cache is dropped without it
+            cache.absorb(other_cache);
+            assert_eq!(cache.emergency_slabs_number(), 2);
+            assert_eq!(cache.emergency_reserve_slabs, 2);
+            assert_eq!(memory_backend.0.borrow().allocated_slab_addrs.len(), 2);
+            assert!(!cache.is_empty());
+            assert_eq!(cache.verify_free_lists(), Ok(()));
+
+            // Reserve slabs are relocated
+            memory_backend.0.borrow_mut().relocate_slab_infos = true;
+            assert_eq!(cache.compact_slab_infos(), 2);
+            assert_eq!(cache.emergency_slabs_number(), 2);
+            assert_eq!(cache.verify_free_lists(), Ok(()));
+
+            // Reserve slab is freed
+            let slab_ptr = (*cache.emergency_slabs_list.front().get().unwrap().data.get()).slab_ptr;
+            cache.free_whole_slab(slab_ptr);
+            assert_eq!(cache.emergency_slabs_number(), 1);
+            assert_eq!(memory_backend.0.borrow().allocated_slab_addrs.len(), 1);
+            assert_eq!(cache.cache_statistics().free_slabs_number, 0);
+
+            assert_eq!(cache.set_emergency_reserve(0), Ok(()));
+            assert!(cache.is_empty());
+            drop(cache);
+            assert!(memory_backend.0.borrow().allocated_slab_addrs.is_empty());
+            assert!(memory_backend
+                .0
+                .borrow()
+                .allocated_slab_info_addrs
+                .is_empty());
+            assert!(memory_backend.0.borrow().ht_saved_slab_infos.is_empty());
+        }
+    }
+
+    #[test]
+    fn no_reclaim_drop() {
+        unsafe {
+            const PAGE_SIZE: usize = 4096;
+
+            // Memory can't be freed, memory backend is borrowed to check it after drop
+            struct TestMemoryBackend<'a>(&'a mut StdMemoryBackend);
+
+            impl MemoryBackend for TestMemoryBackend<'_> {
+                type Error = OutOfMemory;
+
+                unsafe fn alloc_slab(
+                    &mut self,
+                    slab_size: usize,
+                    page_size: usize,
+                ) -> Result<*mut u8, Self::Error> {
+                    self.0.alloc_slab(slab_size, page_size)
+                }
+
+                unsafe fn free_slab(
+                    &mut self,
+                    _slab_ptr: *mut u8,
+                    _slab_size: usize,
+                    _page_size: usize,
+                ) {
+                    panic!("Slab is freed");
+                }
+
+                unsafe fn alloc_slab_info(&mut self) -> Result<*mut SlabInfo, Self::Error> {
+                    self.0.alloc_slab_info()
+                }
+
+                unsafe fn free_slab_info(&mut self, _slab_info_ptr: *mut SlabInfo) {
+                    panic!("SlabInfo is freed");
+                }
+
+                unsafe fn save_slab_info_ptr(
+                    &mut self,
+                    object_page_addr: usize,
+                    slab_info_ptr: *mut SlabInfo,
+                ) {
+                    self.0.save_slab_info_ptr(object_page_addr, slab_info_ptr);
+                }
+
+                unsafe fn get_slab_info_ptr(&mut self, object_page_addr: usize) -> *mut SlabInfo {
+                    self.0.get_slab_info_ptr(object_page_addr)
+                }
+
+                unsafe fn delete_slab_info_ptr(&mut self, page_addr: usize) {
+                    self.0.delete_slab_info_ptr(page_addr);
+                }
+            }
+
+            let mut memory_backend = StdMemoryBackend::default();
+            let mut cache: Cache<[u64; 64], TestMemoryBackend> = Cache::with_config(
+                PAGE_SIZE,
+                PAGE_SIZE,
+                ObjectSizeType::Large,
+                TestMemoryBackend(&mut memory_backend),
+                CacheConfig {
+                    reclaim: false,
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+            let mut allocated_ptrs = Vec::new();
+            for _ in 0..cache.objects_per_slab + 1 {
+                allocated_ptrs.push(cache.alloc());
+            }
+            // Second slab keeps its object
+            allocated_ptrs.pop();
+            for allocated_ptr in allocated_ptrs {
+                cache.free(allocated_ptr);
+            }
+            assert_eq!(cache.set_emergency_reserve(1), Ok(()));
+            // Empty slab and the reserve are leaked too
+            drop(cache);
+            assert_eq!(memory_backend.allocated_slab_addrs.len(), 3);
+            assert_eq!(memory_backend.allocated_slab_info_addrs.len(), 3);
+        }
+    }
+
+    #[test]
+    fn free_whole_slab_unsaved_first_page() {
+        unsafe {
+            const PAGE_SIZE: usize = 4096;
+
+            for object_size_type in [ObjectSizeType::Small, ObjectSizeType::Large] {
+                let mut cache: Cache<[u64; 16], StdMemoryBackend> = Cache::new(
+                    PAGE_SIZE * 2,
+                    PAGE_SIZE,
+                    object_size_type,
+                    StdMemoryBackend::default(),
+                )
+                .unwrap();
+                // Object is allocated from the last page, SlabInfo ptr is not saved for the first one
+                let allocated_ptr = cache.alloc();
+                let slab_addr = cache.slab_base_addresses().next().unwrap().0;
+                assert!(allocated_ptr as usize >= slab_addr + PAGE_SIZE);
+                assert!(!cache
+                    .memory_backend
+                    .ht_saved_slab_infos
+                    .contains_key(&slab_addr));
+
+                cache.free_whole_slab(slab_addr as *const u8);
+                assert_eq!(cache.cache_statistics().allocated_objects_number, 0);
+                assert!(cache.memory_backend.allocated_slab_addrs.is_empty());
+                assert!(cache.memory_backend.allocated_slab_info_addrs.is_empty());
+                assert!(cache.memory_backend.ht_saved_slab_infos.is_empty());
+            }
+        }
+    }
+
+    #[test]
+    fn alloc_aligned_no_aligned_objects() {
+        unsafe {
+            const PAGE_SIZE: usize = 4096;
+
+            // SlabInfo is before objects, no object is at the page start
+            let mut cache: Cache<[u64; 3], StdMemoryBackend> = Cache::with_config(
+                PAGE_SIZE,
+                PAGE_SIZE,
+                ObjectSizeType::Small,
+                StdMemoryBackend::default(),
+                CacheConfig {
+                    small_info_placement: SmallInfoPlacement::Head,
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+            for _ in 0..10 {
+                assert!(cache.alloc_aligned(PAGE_SIZE).is_null());
+            }
+            // Slabs are not allocated
+            assert_eq!(cache.backend_statistics().slab_allocs, 0);
+            assert!(cache.memory_backend.allocated_slab_addrs.is_empty());
+
+            // Only slabs aligned more than to page have an aligned object
+            let mut cache: Cache<[u64; 64], StdMemoryBackend> = Cache::new(
+                PAGE_SIZE,
+                PAGE_SIZE,
+                ObjectSizeType::Large,
+                StdMemoryBackend::default(),
+            )
+            .unwrap();
+            let mut allocated_ptrs = Vec::new();
+            for _ in 0..10 {
+                let allocated_ptr = cache.alloc_aligned(PAGE_SIZE * 2);
+                if !allocated_ptr.is_null() {
+                    assert_eq!(allocated_ptr as usize % (PAGE_SIZE * 2), 0);
+                    allocated_ptrs.push(allocated_ptr);
+                }
+                // Slab without aligned object is freed
+                assert_eq!(
+                    cache.memory_backend.allocated_slab_addrs.len(),
+                    allocated_ptrs.len()
+                );
+            }
+            for allocated_ptr in allocated_ptrs {
+                cache.free(allocated_ptr);
+            }
+            assert!(cache.memory_backend.allocated_slab_addrs.is_empty());
+            assert!(cache.memory_backend.allocated_slab_info_addrs.is_empty());
+        }
+    }
+
+    #[test]
+    fn zero_objects_invalid_free() {
+        unsafe {
+            use std::panic::{catch_unwind, AssertUnwindSafe};
+
+            let mut cache: Cache<[u64; 8], StdMemoryBackend> = Cache::with_config(
+                4096,
+                4096,
+                ObjectSizeType::Small,
+                StdMemoryBackend::default(),
+                CacheConfig {
+                    zero_objects: true,
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+            let allocated_ptr = cache.alloc();
+            allocated_ptr.write([u64::MAX; 8]);
+
+            // Pointer inside the object is rejected, object memory is not zeroed
+            let invalid_ptr: *mut [u64; 8] = allocated_ptr.cast::<u64>().add(1).cast();
+            assert!(catch_unwind(AssertUnwindSafe(|| cache.free(invalid_ptr))).is_err());
+            assert_eq!(*allocated_ptr, [u64::MAX; 8]);
+
+            cache.free(allocated_ptr);
+            assert!(cache.memory_backend.allocated_slab_addrs.is_empty());
+        }
+    }
+
+    #[test]
+    fn no_reclaim_slab_info_failure() {
+        unsafe {
+            const PAGE_SIZE: usize = 4096;
+
+            // Can't free slabs, SlabInfo allocation fails
+            #[derive(Default)]
+            struct TestMemoryBackend {
+                std_memory_backend: StdMemoryBackend,
+            }
+
+            impl MemoryBackend for TestMemoryBackend {
+                type Error = OutOfMemory;
+
+                unsafe fn alloc_slab(
+                    &mut self,
+                    slab_size: usize,
+                    page_size: usize,
+                ) -> Result<*mut u8, Self::Error> {
+                    self.std_memory_backend.alloc_slab(slab_size, page_size)
+                }
+
+                unsafe fn alloc_slabs(
+                    &mut self,
+                    count: usize,
+                    slab_size: usize,
+                    page_size: usize,
+                ) -> Result<*mut u8, Self::Error> {
+                    self.std_memory_backend
+                        .alloc_slab(count * slab_size, page_size)
+                }
+
+                unsafe fn free_slab(
+                    &mut self,
+                    _slab_ptr: *mut u8,
+                    _slab_size: usize,
+                    _page_size: usize,
+                ) {
+                    panic!("Slab is freed");
+                }
+
+                unsafe fn alloc_slab_info(&mut self) -> Result<*mut SlabInfo, Self::Error> {
+                    Err(OutOfMemory)
+                }
+
+                unsafe fn free_slab_info(&mut self, _slab_info_ptr: *mut SlabInfo) {
+                    unreachable!();
+                }
+
+                unsafe fn save_slab_info_ptr(
+                    &mut self,
+                    _object_page_addr: usize,
+                    _slab_info_ptr: *mut SlabInfo,
+                ) {
+                    unreachable!();
+                }
+
+                unsafe fn get_slab_info_ptr(&mut self, _object_page_addr: usize) -> *mut SlabInfo {
+                    unreachable!();
+                }
+
+                unsafe fn delete_slab_info_ptr(&mut self, _page_addr: usize) {
+                    unreachable!();
+                }
+            }
+
+            let mut cache: Cache<[u64; 64], TestMemoryBackend> = Cache::with_config(
+                PAGE_SIZE,
+                PAGE_SIZE,
+                ObjectSizeType::Large,
+                TestMemoryBackend::default(),
+                CacheConfig {
+                    reclaim: false,
+                    ..Default::default()
+                },
+            )
+            .unwrap();
+            // Slabs without SlabInfo are leaked, one slab and one region of 4 slabs
+            assert!(cache.alloc().is_null());
+            assert_eq!(cache.reserve_contiguous(4), 0);
+            assert_eq!(cache.backend_statistics().slab_frees, 0);
+            assert_eq!(cache.backend_statistics().slab_allocs, 5);
+            assert_eq!(
+                cache
+                    .memory_backend
+                    .std_memory_backend
+                    .allocated_slab_addrs
+                    .len(),
+                2
+            );
+            assert!(cache.is_empty());
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "not the start of an object")]
+    fn free_after_last_object() {
+        unsafe {
+            let mut cache: Cache<[u64; 8], StdMemoryBackend> = Cache::new(
+                4096,
+                4096,
+                ObjectSizeType::Small,
+                StdMemoryBackend::default(),
+            )
+            .unwrap();
+            let allocated_ptr = cache.alloc();
+            // Object sized step after the last object, into the slab tail with SlabInfo
+            let slab_addr = align_down(allocated_ptr as usize, 4096);
+            let invalid_ptr = slab_addr + cache.objects_per_slab * cache.object_size;
+            cache.free(invalid_ptr as *mut [u64; 8]);
+        }
+    }
+
+    #[cfg(debug_assertions)]
+    #[test]
+    #[should_panic(expected = "Double free!")]
+    fn fixed_cache_double_free() {
+        unsafe {
+            let mut cache: FixedCache<[u64; 4], 1000> = FixedCache::new();
+            let allocated_ptr = cache.alloc();
+            let _other_allocated_ptr = cache.alloc();
+            cache.free(allocated_ptr);
+            // Another object is still allocated, so the count doesn't catch it
+            cache.free(allocated_ptr);
+        }
+    }
+
+    #[test]
+    fn free_reporting_not_returned() {
+        unsafe {
+            const PAGE_SIZE: usize = 4096;
+
+            // Fails to free slabs
+            #[derive(Default)]
+            struct TestMemoryBackend {
+                std_memory_backend: StdMemoryBackend,
+            }
+
+            impl MemoryBackend for TestMemoryBackend {
+                type Error = &'static str;
+
+                unsafe fn alloc_slab(
+                    &mut self,
+                    slab_size: usize,
+                    page_size: usize,
+                ) -> Result<*mut u8, Self::Error> {
+                    Ok(self.std_memory_backend.alloc_slab(slab_size, page_size)?)
+                }
+
+                unsafe fn free_slab(
+                    &mut self,
+                    _slab_ptr: *mut u8,
+                    _slab_size: usize,
+                    _page_size: usize,
+                ) {
+                    unreachable!();
+                }
+
+                unsafe fn try_free_slab(
+                    &mut self,
+                    _slab_ptr: *mut u8,
+                    _slab_size: usize,
+                    _page_size: usize,
+                ) -> Result<(), Self::Error> {
+                    Err("Slab is busy")
+                }
+
+                unsafe fn alloc_slab_info(&mut self) -> Result<*mut SlabInfo, Self::Error> {
+                    unreachable!();
+                }
+
+                unsafe fn free_slab_info(&mut self, _slab_info_ptr: *mut SlabInfo) {
+                    unreachable!();
+                }
+
+                unsafe fn save_slab_info_ptr(
+                    &mut self,
+                    _object_page_addr: usize,
+                    _slab_info_ptr: *mut SlabInfo,
+                ) {
+                    unreachable!();
+                }
+
+                unsafe fn get_slab_info_ptr(&mut self, _object_page_addr: usize) -> *mut SlabInfo {
+                    unreachable!();
+                }
+
+                unsafe fn delete_slab_info_ptr(&mut self, _page_addr: usize) {
+                    unreachable!();
+                }
+            }
+
+            let mut cache: Cache<[u64; 64], TestMemoryBackend> = Cache::new(
+                PAGE_SIZE,
+                PAGE_SIZE,
+                ObjectSizeType::Small,
+                TestMemoryBackend::default(),
+            )
+            .unwrap();
+            let slab_layout = Layout::from_size_align(PAGE_SIZE, PAGE_SIZE).unwrap();
+
+            // Memory backend fails to free the slab, the cache releases it anyway
+            let allocated_ptr = cache.alloc();
+            let slab_ptr = align_down(allocated_ptr as usize, PAGE_SIZE) as *mut u8;
+            assert_eq!(
+                cache.free_reporting(allocated_ptr),
+                FreeOutcome {
+                    slab_released: true,
+                    returned_to_backend: false,
+                    released_slab_ptr: Some(slab_ptr)
+                }
+            );
+            assert_eq!(cache.backend_statistics().slab_free_failures, 1);
+            assert!(cache.is_empty());
+
+            // Donated slab is released, but its memory is not given to memory backend
+            let donated_slab_ptr = alloc(slab_layout);
+            assert_eq!(cache.donate_slab(donated_slab_ptr), Ok(()));
+            let allocated_ptr = cache.alloc();
+            assert_eq!(
+                align_down(allocated_ptr as usize, PAGE_SIZE),
+                donated_slab_ptr as usize
+            );
+            assert_eq!(
+                cache.free_reporting(allocated_ptr),
+                FreeOutcome {
+                    slab_released: true,
+                    returned_to_backend: false,
+                    released_slab_ptr: Some(donated_slab_ptr)
+                }
+            );
+            assert_eq!(cache.backend_statistics().slab_free_failures, 1);
+            assert!(cache.is_empty());
+
+            dealloc(donated_slab_ptr, slab_layout);
+            dealloc(slab_ptr, slab_layout);
+        }
+    }
 }